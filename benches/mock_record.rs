@@ -0,0 +1,73 @@
+//! Mock `record` throughput
+//!
+//! Run with `cargo bench --features bench`. To compare against a baseline,
+//! save one on the base branch with
+//! `cargo bench --features bench -- --save-baseline main`, then run
+//! `cargo bench --features bench -- --baseline main` on the change.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tokio::runtime::Runtime;
+use tyl_metrics_port::{
+    throughput_probe, DropStrategy, MetricsManager, MockMetricsAdapter, MockMetricsConfig,
+};
+
+/// Records per iteration batch
+const RECORDS: u64 = 10_000;
+
+fn bench_record(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let configs = [
+        (
+            "discard",
+            MockMetricsConfig {
+                store_metrics: false,
+                ..MockMetricsConfig::default()
+            },
+        ),
+        // Room for the whole batch, so every record appends (the default
+        // limit of 1000 would start evicting)
+        (
+            "store",
+            MockMetricsConfig::default().with_max_stored(RECORDS as usize),
+        ),
+        // Full halfway through the batch, so later records also evict
+        (
+            "store_drop_oldest",
+            MockMetricsConfig::default()
+                .with_max_stored(RECORDS as usize / 2)
+                .with_drop_strategy(DropStrategy::DropOldest),
+        ),
+        // The probe records one name, which lands in a single shard, so each
+        // shard gets room for the whole batch
+        (
+            "store_sharded",
+            MockMetricsConfig::default()
+                .with_shards(8)
+                .with_max_stored(8 * RECORDS as usize),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("mock_record");
+    group.throughput(Throughput::Elements(RECORDS));
+    for (name, config) in configs {
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.to_async(&runtime).iter_custom(|iterations| {
+                let config = config.clone();
+                async move {
+                    let mut total = std::time::Duration::ZERO;
+                    for _ in 0..iterations {
+                        // A fresh adapter per batch keeps the store size steady
+                        let adapter = MockMetricsAdapter::new(config.clone());
+                        total += throughput_probe(&adapter, RECORDS as usize).await;
+                        adapter.flush().await.unwrap();
+                    }
+                    total
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_record);
+criterion_main!(benches);
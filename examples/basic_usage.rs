@@ -12,7 +12,8 @@
 use std::time::Duration;
 use tokio::time::sleep;
 use tyl_metrics_port::{
-    Labels, MetricRequest, MetricType, MetricsManager, MockMetricsAdapter, MockMetricsConfig,
+    format_snapshot_table, Labels, MetricRequest, MetricType, MetricsManager, MockMetricsAdapter,
+    MockMetricsConfig,
 };
 
 #[tokio::main]
@@ -42,7 +43,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Multiple counter increments
     for i in 1..=5 {
         let request = MetricRequest::counter("requests_processed", 1.0)
-            .with_label("batch", &format!("batch_{}", i));
+            .with_label("batch", format!("batch_{}", i));
         metrics.record(&request).await?;
     }
 
@@ -114,6 +115,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             MetricType::Gauge => gauge_count += 1,
             MetricType::Histogram => histogram_count += 1,
             MetricType::Timer => timer_count += 1,
+            MetricType::Info => {}
         }
     }
 
@@ -126,10 +128,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🔍 Searching metrics by name...");
     let http_metrics = metrics.find_metrics_by_name("http_requests_total").await;
     for metric in http_metrics {
-        let value = match &metric.value {
-            tyl_metrics_port::MetricValue::Single(val) => *val,
-            tyl_metrics_port::MetricValue::Histogram { sum, .. } => *sum,
-        };
+        let value = metric.value.as_f64().unwrap_or_default();
         println!(
             "   Found: {} = {} (labels: {})",
             metric.name,
@@ -142,10 +141,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🔍 Searching gauge metrics...");
     let gauge_metrics = metrics.find_metrics_by_type(MetricType::Gauge).await;
     for metric in gauge_metrics {
-        let value = match &metric.value {
-            tyl_metrics_port::MetricValue::Single(val) => *val,
-            tyl_metrics_port::MetricValue::Histogram { sum, .. } => *sum,
-        };
+        let value = metric.value.as_f64().unwrap_or_default();
         println!("   Gauge: {} = {}", metric.name, value);
     }
 
@@ -154,22 +150,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let snapshot = metrics.get_snapshot().await?;
     println!("   Snapshot contains {} metrics", snapshot.len());
 
-    // Display some sample metrics
-    if !snapshot.is_empty() {
-        println!("   Sample metrics:");
-        for metric in snapshot.iter().take(3) {
-            let value_str = match &metric.value {
-                tyl_metrics_port::MetricValue::Single(val) => format!("{:.3}", val),
-                tyl_metrics_port::MetricValue::Histogram { sum, count, .. } => {
-                    format!("histogram(sum={:.3}, count={})", sum, count)
-                }
-            };
-            println!(
-                "     {} = {} {:?}",
-                metric.name, value_str, metric.metric_type
-            );
-        }
-    }
+    // Display the aggregated series as a table
+    print!("{}", format_snapshot_table(&snapshot));
 
     println!("\n✅ MockMetricsAdapter demonstration completed!");
     println!("💡 This mock adapter is perfect for:");
@@ -0,0 +1,59 @@
+//! Throughput probe for catching performance regressions
+//!
+//! `throughput_probe` times a run of simple records against any adapter,
+//! so CI can assert a floor on records per second; the criterion benchmark in
+//! `benches/mock_record.rs` uses it to measure the mock's `record` path.
+
+use super::*;
+use crate::clock::Instant;
+use std::time::Duration;
+
+/// Record `n` unlabeled counters and return the elapsed time
+///
+/// Requests are built before the clock starts, so only `record` is timed.
+/// Records run one after another, so the result measures latency-bound
+/// throughput rather than contention. Failed records are timed like
+/// successful ones, so make sure the adapter accepts them (e.g. the mock's
+/// storage limit uses a dropping strategy).
+///
+/// Only available with the `bench` feature.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{throughput_probe, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = MockMetricsAdapter::default();
+/// let elapsed = throughput_probe(&mock, 1_000).await;
+///
+/// let per_second = 1_000.0 / elapsed.as_secs_f64();
+/// assert!(per_second > 0.0);
+/// # });
+/// ```
+pub async fn throughput_probe<M: MetricsManager>(adapter: &M, n: usize) -> Duration {
+    let requests: Vec<MetricRequest> = (0..n)
+        .map(|_| MetricRequest::counter("throughput_probe_total", 1.0))
+        .collect();
+
+    let started = Instant::now();
+    for request in &requests {
+        let _ = adapter.record(request).await;
+    }
+    started.elapsed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_throughput_probe_records_n_counters() {
+        let mock = MockMetricsAdapter::default();
+
+        throughput_probe(&mock, 250).await;
+        assert_eq!(
+            mock.counter_total("throughput_probe_total", None).await,
+            250.0
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Pluggable time source
+//!
+//! Time-dependent behavior, such as the mock's TTL expiry, reads the current
+//! time through `Clock` so tests can control it with a `MockClock` instead of
+//! sleeping.
+//!
+//! Every wall-clock and monotonic time read in the crate goes through this
+//! module. `std::time::SystemTime::now` and `Instant::now` panic on
+//! `wasm32-unknown-unknown`, so with the `wasm` feature on that target they
+//! are backed by the browser's `Date.now()` and `performance.now()` instead.
+
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub(crate) use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub(crate) use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Current wall-clock time in Unix epoch nanoseconds
+pub(crate) fn unix_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+}
+
+/// Source of the current time
+pub trait Clock: Debug + Send + Sync {
+    /// Current time in Unix epoch nanoseconds
+    fn now_nanos(&self) -> u64;
+}
+
+/// The system wall clock
+///
+/// With the `wasm` feature on `wasm32` targets this reads `Date.now()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_nanos(&self) -> u64 {
+        unix_nanos()
+    }
+}
+
+/// Manually advanced clock for tests
+///
+/// Clones share the same time, so keep one clone to advance while the
+/// component under test holds another.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use tyl_metrics_port::{Clock, MockClock};
+///
+/// let clock = MockClock::new(1_000);
+/// let shared = clock.clone();
+///
+/// clock.advance(Duration::from_nanos(500));
+/// assert_eq!(shared.now_nanos(), 1_500);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    now: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    /// Create a clock reading `start_nanos` (Unix epoch nanoseconds)
+    pub fn new(start_nanos: u64) -> Self {
+        Self {
+            now: Arc::new(AtomicU64::new(start_nanos)),
+        }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, by: Duration) {
+        self.now.fetch_add(by.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Set the clock to `nanos` (Unix epoch nanoseconds)
+    pub fn set(&self, nanos: u64) {
+        self.now.store(nanos, Ordering::Relaxed);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_nanos(&self) -> u64 {
+        self.now.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_set_and_advance() {
+        let clock = MockClock::default();
+        assert_eq!(clock.now_nanos(), 0);
+
+        clock.set(5_000_000_000);
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(clock.now_nanos(), 7_000_000_000);
+    }
+
+    #[test]
+    fn test_monotonic_instant_measures_elapsed_time() {
+        let started = Instant::now();
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(started.elapsed() >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_system_clock_tracks_wall_time() {
+        let before = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+        assert!(SystemClock.now_nanos() >= before);
+    }
+}
@@ -0,0 +1,436 @@
+//! Buffering decorator for metrics adapters
+
+use super::*;
+use crate::errors::metrics_config_error;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// When a buffering adapter sends its accumulated metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferSettings {
+    /// Send as soon as this many metrics are buffered
+    pub batch_size: usize,
+
+    /// Send whatever is buffered at least this often
+    pub flush_interval: Duration,
+}
+
+impl BufferSettings {
+    /// Create buffer settings
+    pub fn new(batch_size: usize, flush_interval: Duration) -> Self {
+        Self {
+            batch_size,
+            flush_interval,
+        }
+    }
+
+    /// Check that batches can fill and the interval flush can be scheduled
+    fn validate(&self) -> Result<()> {
+        if self.batch_size == 0 {
+            return Err(metrics_config_error(
+                "batch_size",
+                "Batch size must be greater than zero",
+            ));
+        }
+        if self.flush_interval.is_zero() {
+            return Err(metrics_config_error(
+                "flush_interval",
+                "Flush interval must be greater than zero",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for BufferSettings {
+    fn default() -> Self {
+        Self::new(100, Duration::from_secs(1))
+    }
+}
+
+/// State shared between the adapter and its background flush task
+struct BufferState<M> {
+    inner: M,
+    buffer: Mutex<Vec<MetricRequest>>,
+    /// Error from a background send, reported on the next `record` or `flush`
+    deferred_error: Mutex<Option<TylError>>,
+}
+
+impl<M: MetricsManager> BufferState<M> {
+    fn take_buffer(&self) -> Vec<MetricRequest> {
+        std::mem::take(&mut *self.buffer.lock().unwrap_or_else(|p| p.into_inner()))
+    }
+
+    fn take_deferred_error(&self) -> Option<TylError> {
+        self.deferred_error
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .take()
+    }
+
+    /// Send everything buffered to the inner adapter
+    async fn send_buffered(&self) -> Result<()> {
+        let batch = self.take_buffer();
+        if batch.is_empty() {
+            return Ok(());
+        }
+        self.inner.record_batch(&batch).await
+    }
+}
+
+/// Decorator that batches records before forwarding them
+///
+/// Metrics are buffered in memory and sent to the inner adapter's
+/// `record_batch` once `batch_size` accumulate or every `flush_interval`,
+/// whichever comes first. `flush` sends immediately, and dropping the adapter
+/// sends whatever is left in the background.
+///
+/// Errors from background sends are returned by the next `record` or `flush`;
+/// that `record` still buffers its request.
+/// Timers, health checks, and snapshots go straight to the inner adapter, so
+/// snapshots don't include metrics still waiting in the buffer.
+///
+/// The interval flush runs on a Tokio task, so the adapter must be created
+/// inside a Tokio runtime; `new` returns a configuration error otherwise.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{BufferSettings, BufferingMetricsAdapter, MetricRequest, MetricsManager, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = BufferingMetricsAdapter::new(mock.clone(), BufferSettings::default()).unwrap();
+///
+/// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// assert_eq!(mock.get_metrics_count().await, 0);
+///
+/// metrics.flush().await.unwrap();
+/// assert_eq!(mock.get_metrics_count().await, 1);
+/// # });
+/// ```
+pub struct BufferingMetricsAdapter<M: MetricsManager + 'static> {
+    state: Arc<BufferState<M>>,
+    settings: BufferSettings,
+    ticker: JoinHandle<()>,
+}
+
+impl<M: MetricsManager + 'static> BufferingMetricsAdapter<M> {
+    /// Wrap an adapter, buffering records according to `settings`
+    ///
+    /// # Errors
+    /// Returns a configuration error if `batch_size` or `flush_interval` is
+    /// zero, or if there is no Tokio runtime to run the interval flush on.
+    pub fn new(inner: M, settings: BufferSettings) -> Result<Self> {
+        settings.validate()?;
+        let runtime = tokio::runtime::Handle::try_current().map_err(|_| {
+            metrics_config_error(
+                "runtime",
+                "BufferingMetricsAdapter must be created inside a Tokio runtime",
+            )
+        })?;
+        let state = Arc::new(BufferState {
+            inner,
+            buffer: Mutex::new(Vec::new()),
+            deferred_error: Mutex::new(None),
+        });
+        // Start the schedule now, not when the task first gets polled
+        let schedule = tokio::time::interval_at(
+            tokio::time::Instant::now() + settings.flush_interval,
+            settings.flush_interval,
+        );
+        let ticker = runtime.spawn(Self::flush_periodically(Arc::downgrade(&state), schedule));
+
+        Ok(Self {
+            state,
+            settings,
+            ticker,
+        })
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.state.inner
+    }
+
+    /// Get the buffer settings
+    pub fn settings(&self) -> BufferSettings {
+        self.settings
+    }
+
+    /// Get the number of metrics waiting to be sent
+    pub fn buffered_count(&self) -> usize {
+        self.state
+            .buffer
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .len()
+    }
+
+    /// Background loop sending the buffer on every tick until the adapter is dropped
+    async fn flush_periodically(state: Weak<BufferState<M>>, mut ticker: tokio::time::Interval) {
+        loop {
+            ticker.tick().await;
+            let Some(state) = state.upgrade() else {
+                break;
+            };
+
+            if let Err(error) = state.send_buffered().await {
+                *state
+                    .deferred_error
+                    .lock()
+                    .unwrap_or_else(|p| p.into_inner()) = Some(error);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager + 'static> MetricsManager for BufferingMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, BufferSettings>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        config.settings.validate()?;
+        let inner = M::new(config.inner).await?;
+        Self::new(inner, config.settings)
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        // A pending background error is reported, but this request is still
+        // buffered so it isn't lost along with the failed batch
+        let deferred = self.state.take_deferred_error();
+
+        let full = {
+            let mut buffer = self.state.buffer.lock().unwrap_or_else(|p| p.into_inner());
+            buffer.push(request.clone());
+            buffer.len() >= self.settings.batch_size
+        };
+
+        let sent = if full {
+            self.state.send_buffered().await
+        } else {
+            Ok(())
+        };
+
+        match deferred {
+            Some(error) => Err(error),
+            None => sent,
+        }
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.state.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.state.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.state.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.state.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        // Send and flush before reporting a background error, so an explicit
+        // shutdown flush doesn't leave the buffer behind
+        let deferred = self.state.take_deferred_error();
+        let sent = self.state.send_buffered().await;
+        let flushed = self.state.inner.flush().await;
+
+        match deferred {
+            Some(error) => Err(error),
+            None => sent.and(flushed),
+        }
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.state.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.state.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.state.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.state.inner.snapshot_len().await
+    }
+}
+
+impl<M: MetricsManager + 'static> Drop for BufferingMetricsAdapter<M> {
+    fn drop(&mut self) {
+        self.ticker.abort();
+
+        // Send what's left in the background; without a runtime it is lost
+        if self.buffered_count() > 0 {
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                let state = self.state.clone();
+                runtime.spawn(async move {
+                    let _ = state.send_buffered().await;
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_n(count: usize) -> Vec<MetricRequest> {
+        (0..count)
+            .map(|i| MetricRequest::counter("requests", i as f64))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_buffering_size_trigger() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let settings = BufferSettings::new(3, Duration::from_secs(60));
+        let metrics = BufferingMetricsAdapter::new(mock.clone(), settings).unwrap();
+
+        for request in &record_n(2) {
+            metrics.record(request).await.unwrap();
+        }
+        assert_eq!(mock.get_metrics_count().await, 0);
+        assert_eq!(metrics.buffered_count(), 2);
+
+        metrics
+            .record(&MetricRequest::counter("requests", 2.0))
+            .await
+            .unwrap();
+        assert_eq!(mock.get_metrics_count().await, 3);
+        assert_eq!(metrics.buffered_count(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_buffering_time_trigger() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let settings = BufferSettings::new(100, Duration::from_millis(20));
+        let metrics = BufferingMetricsAdapter::new(mock.clone(), settings).unwrap();
+
+        for request in &record_n(2) {
+            metrics.record(request).await.unwrap();
+        }
+        tokio::time::advance(Duration::from_millis(19)).await;
+        assert_eq!(mock.get_metrics_count().await, 0);
+
+        // Sleeping on paused time auto-advances past the tick and lets the
+        // flush task run
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        assert_eq!(mock.get_metrics_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_buffering_drop_flushes_remaining() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics =
+            BufferingMetricsAdapter::new(mock.clone(), BufferSettings::default()).unwrap();
+
+        for request in &record_n(5) {
+            metrics.record(request).await.unwrap();
+        }
+        drop(metrics);
+
+        for _ in 0..50 {
+            if mock.get_metrics_count().await == 5 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        assert_eq!(mock.get_metrics_count().await, 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_buffering_surfaces_background_errors() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let settings = BufferSettings::new(100, Duration::from_millis(10));
+        let metrics = BufferingMetricsAdapter::new(mock.clone(), settings).unwrap();
+
+        metrics
+            .record(&MetricRequest::counter("invalid name", 1.0))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(11)).await;
+
+        // The background send failed; the next record reports it once but
+        // still buffers its own request
+        assert!(metrics
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .is_err());
+        assert_eq!(metrics.buffered_count(), 1);
+        assert!(metrics
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .is_ok());
+        metrics.flush().await.unwrap();
+        assert_eq!(mock.get_metrics_count().await, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_buffering_flush_sends_buffer_before_reporting_errors() {
+        let config = MockMetricsConfig::default().with_record_latency(Duration::from_millis(5));
+        let mock = Arc::new(MockMetricsAdapter::new(config));
+        let settings = BufferSettings::new(100, Duration::from_millis(10));
+        let metrics = BufferingMetricsAdapter::new(mock.clone(), settings).unwrap();
+
+        // The background send fails while another request gets buffered
+        mock.fail_next_records(1);
+        metrics
+            .record(&MetricRequest::counter("lost", 1.0))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(12)).await;
+        metrics
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        // flush reports the failure only after sending what's buffered
+        assert!(metrics.flush().await.is_err());
+        assert_eq!(metrics.buffered_count(), 0);
+        assert_eq!(mock.get_metrics_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_buffering_rejects_zero_batch_size() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let settings = BufferSettings::new(0, Duration::from_secs(1));
+
+        let error = BufferingMetricsAdapter::new(mock, settings).err().unwrap();
+        assert!(error
+            .to_string()
+            .contains("Batch size must be greater than zero"));
+    }
+
+    #[test]
+    fn test_buffering_requires_runtime() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+
+        let error = BufferingMetricsAdapter::new(mock, BufferSettings::default())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("inside a Tokio runtime"));
+    }
+
+    #[tokio::test]
+    async fn test_buffering_rejects_zero_flush_interval() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let settings = BufferSettings::new(10, Duration::ZERO);
+
+        let error = BufferingMetricsAdapter::new(mock, settings).err().unwrap();
+        assert!(error
+            .to_string()
+            .contains("Flush interval must be greater than zero"));
+    }
+}
@@ -0,0 +1,327 @@
+//! Circuit breaker decorator for metrics adapters
+
+use super::*;
+use crate::clock::Instant;
+use crate::errors::metrics_config_error;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// When the breaker opens and how long it stays open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerSettings {
+    /// Consecutive transient record failures that open the breaker
+    pub failure_threshold: u32,
+
+    /// How long the breaker stays open before letting a trial record through
+    pub reset_timeout: Duration,
+}
+
+impl CircuitBreakerSettings {
+    /// Create circuit breaker settings
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold,
+            reset_timeout,
+        }
+    }
+
+    /// Check that the breaker can be tripped
+    fn validate(&self) -> Result<()> {
+        if self.failure_threshold == 0 {
+            return Err(metrics_config_error(
+                "failure_threshold",
+                "Failure threshold must be at least 1",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for CircuitBreakerSettings {
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(30))
+    }
+}
+
+/// State of a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Records are forwarded; failures are being counted
+    Closed,
+
+    /// Records fail fast without reaching the inner adapter
+    Open,
+
+    /// The reset timeout elapsed; the next record is a trial of the backend
+    HalfOpen,
+}
+
+/// Mutable breaker bookkeeping
+#[derive(Debug, Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    /// When the breaker last opened (or a trial record was let through)
+    opened_at: Option<Instant>,
+    /// Whether a trial record is in flight
+    probing: bool,
+}
+
+/// Decorator that stops recording while the backend keeps failing
+///
+/// After `failure_threshold` consecutive `record`, `record_batch`, or
+/// `record_validated` failures the breaker opens, and records fail at once
+/// with `metrics_connection_error` instead of reaching the inner adapter.
+/// Once `reset_timeout` has elapsed it half-opens and lets one trial record
+/// through: success closes the breaker, failure opens it for another
+/// `reset_timeout`. Only transient errors (see `is_retryable`) count as
+/// failures, so invalid metrics never trip it. Timers, health checks, and
+/// snapshots are passed through.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use tyl_metrics_port::{CircuitBreakerMetricsAdapter, CircuitBreakerSettings, CircuitState, MetricRequest, MetricsManager, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = MockMetricsAdapter::default();
+/// mock.fail_next_records(2);
+///
+/// let metrics = CircuitBreakerMetricsAdapter::new(mock, CircuitBreakerSettings::new(2, Duration::from_secs(30)));
+/// let request = MetricRequest::counter("requests", 1.0);
+/// assert!(metrics.record(&request).await.is_err());
+/// assert!(metrics.record(&request).await.is_err());
+///
+/// assert_eq!(metrics.state(), CircuitState::Open);
+/// assert!(metrics.record(&request).await.is_err());
+/// assert_eq!(metrics.inner().record_calls(), 2);
+/// # });
+/// ```
+pub struct CircuitBreakerMetricsAdapter<M> {
+    inner: M,
+    settings: CircuitBreakerSettings,
+    breaker: Mutex<Breaker>,
+}
+
+impl<M: MetricsManager> CircuitBreakerMetricsAdapter<M> {
+    /// Wrap an adapter, breaking the circuit according to `settings`
+    pub fn new(inner: M, settings: CircuitBreakerSettings) -> Self {
+        Self {
+            inner,
+            settings,
+            breaker: Mutex::new(Breaker::default()),
+        }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the circuit breaker settings
+    pub fn settings(&self) -> CircuitBreakerSettings {
+        self.settings
+    }
+
+    /// Current breaker state
+    pub fn state(&self) -> CircuitState {
+        let breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        match breaker.opened_at {
+            _ if breaker.probing => CircuitState::HalfOpen,
+            Some(opened_at) if opened_at.elapsed() < self.settings.reset_timeout => {
+                CircuitState::Open
+            }
+            Some(_) => CircuitState::HalfOpen,
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Number of transient failures since the last success
+    pub fn consecutive_failures(&self) -> u32 {
+        self.breaker
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .consecutive_failures
+    }
+
+    /// Let a record through, or fail fast while the breaker is open
+    fn admit(&self) -> Result<()> {
+        let mut breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        match breaker.opened_at {
+            None => Ok(()),
+            Some(opened_at) if opened_at.elapsed() < self.settings.reset_timeout => {
+                Err(metrics_connection_error(
+                    "circuit_breaker",
+                    format!(
+                        "Circuit open after {} consecutive failures",
+                        breaker.consecutive_failures
+                    ),
+                ))
+            }
+            Some(_) => {
+                // Re-arm the timeout so concurrent records keep failing fast
+                // while the trial runs, and a cancelled trial can't wedge it
+                breaker.opened_at = Some(Instant::now());
+                breaker.probing = true;
+                Ok(())
+            }
+        }
+    }
+
+    /// Update the breaker with the outcome of a forwarded record
+    fn observe(&self, result: Result<()>) -> Result<()> {
+        let mut breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        breaker.probing = false;
+        match &result {
+            Ok(()) => *breaker = Breaker::default(),
+            Err(error) if is_retryable(error) => {
+                breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+                if breaker.consecutive_failures >= self.settings.failure_threshold {
+                    breaker.opened_at = Some(Instant::now());
+                }
+            }
+            Err(_) => {}
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for CircuitBreakerMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, CircuitBreakerSettings>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        config.settings.validate()?;
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.admit()?;
+        self.observe(self.inner.record(request).await)
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        self.admit()?;
+        self.observe(self.inner.record_batch(requests).await)
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        self.admit()?;
+        self.observe(self.inner.record_validated(request).await)
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESET: Duration = Duration::from_millis(30);
+
+    fn breaker(mock: MockMetricsAdapter) -> CircuitBreakerMetricsAdapter<MockMetricsAdapter> {
+        CircuitBreakerMetricsAdapter::new(mock, CircuitBreakerSettings::new(3, RESET))
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_fails_fast_and_recovers() {
+        let mock = MockMetricsAdapter::default();
+        mock.fail_next_records(3);
+        let metrics = breaker(mock);
+        let request = MetricRequest::counter("requests", 1.0);
+
+        for _ in 0..3 {
+            assert_eq!(metrics.state(), CircuitState::Closed);
+            assert!(metrics.record(&request).await.is_err());
+        }
+        assert_eq!(metrics.state(), CircuitState::Open);
+
+        let error = metrics.record(&request).await.unwrap_err();
+        assert!(error.to_string().contains("Circuit open"));
+        assert_eq!(metrics.inner().record_calls(), 3);
+
+        tokio::time::sleep(RESET).await;
+        assert_eq!(metrics.state(), CircuitState::HalfOpen);
+        metrics.record(&request).await.unwrap();
+        assert_eq!(metrics.state(), CircuitState::Closed);
+        assert_eq!(metrics.consecutive_failures(), 0);
+        assert_eq!(metrics.inner().get_metrics_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_trial_reopens_circuit() {
+        let mock = MockMetricsAdapter::default();
+        mock.fail_next_records(4);
+        let metrics = breaker(mock);
+        let request = MetricRequest::counter("requests", 1.0);
+
+        for _ in 0..3 {
+            assert!(metrics.record(&request).await.is_err());
+        }
+        tokio::time::sleep(RESET).await;
+
+        assert!(metrics.record(&request).await.is_err());
+        assert_eq!(metrics.inner().record_calls(), 4);
+        assert_eq!(metrics.state(), CircuitState::Open);
+        assert!(metrics.record(&request).await.is_err());
+        assert_eq!(metrics.inner().record_calls(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_validation_errors_do_not_trip_circuit() {
+        let metrics = breaker(MockMetricsAdapter::default());
+
+        for _ in 0..5 {
+            assert!(metrics
+                .record(&MetricRequest::counter("invalid name", 1.0))
+                .await
+                .is_err());
+        }
+        assert_eq!(metrics.state(), CircuitState::Closed);
+        assert_eq!(metrics.consecutive_failures(), 0);
+
+        let config = DecoratorConfig::new(
+            MockMetricsConfig::default(),
+            CircuitBreakerSettings::new(0, RESET),
+        );
+        assert!(
+            <CircuitBreakerMetricsAdapter<MockMetricsAdapter> as MetricsManager>::new(config)
+                .await
+                .is_err()
+        );
+    }
+}
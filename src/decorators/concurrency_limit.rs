@@ -0,0 +1,262 @@
+//! Concurrency limiting decorator for metrics adapters
+
+use super::*;
+use crate::errors::metrics_config_error;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// How many records may be in flight at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcurrencyLimits {
+    /// Maximum number of concurrent record calls
+    pub max_in_flight: usize,
+
+    /// How long a record may wait for a slot before failing; `None` waits forever
+    pub acquire_timeout: Option<Duration>,
+}
+
+impl ConcurrencyLimits {
+    /// Allow `max_in_flight` concurrent records, waiting as long as needed
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            max_in_flight,
+            acquire_timeout: None,
+        }
+    }
+
+    /// Fail records that wait longer than `timeout` for a slot
+    pub fn with_acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.acquire_timeout = Some(timeout);
+        self
+    }
+
+    /// Check that at least one record may be in flight
+    fn validate(&self) -> Result<()> {
+        if self.max_in_flight == 0 || self.max_in_flight > Semaphore::MAX_PERMITS {
+            return Err(metrics_config_error(
+                "max_in_flight",
+                format!(
+                    "Concurrency limit must be between 1 and {}",
+                    Semaphore::MAX_PERMITS
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Decorator that bounds concurrent record calls to protect a slow backend
+///
+/// `record`, `record_batch`, and `record_validated` each take a slot from a
+/// semaphore before forwarding and give it back when the inner call
+/// finishes; callers beyond the limit wait in FIFO order. With an
+/// `acquire_timeout`, a caller that waits too long fails with
+/// `metrics_timeout_error_after`. After `close`, waiting and new callers fail with
+/// `metrics_adapter_error`. Timers, health checks, and snapshots are not
+/// limited.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{ConcurrencyLimitMetricsAdapter, ConcurrencyLimits, MetricRequest, MetricsManager, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = ConcurrencyLimitMetricsAdapter::new(mock.clone(), ConcurrencyLimits::new(4)).unwrap();
+///
+/// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// assert_eq!(metrics.available_permits(), 4);
+/// # });
+/// ```
+pub struct ConcurrencyLimitMetricsAdapter<M> {
+    inner: M,
+    limits: ConcurrencyLimits,
+    permits: Semaphore,
+}
+
+impl<M: MetricsManager> ConcurrencyLimitMetricsAdapter<M> {
+    /// Wrap an adapter, bounding concurrent records according to `limits`
+    ///
+    /// # Errors
+    /// Returns a configuration error if `max_in_flight` is zero, which would
+    /// make every record wait forever, or above `Semaphore::MAX_PERMITS`.
+    pub fn new(inner: M, limits: ConcurrencyLimits) -> Result<Self> {
+        limits.validate()?;
+        Ok(Self {
+            inner,
+            permits: Semaphore::new(limits.max_in_flight),
+            limits,
+        })
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the concurrency limits
+    pub fn limits(&self) -> &ConcurrencyLimits {
+        &self.limits
+    }
+
+    /// Number of records that could start right now without waiting
+    pub fn available_permits(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// Stop admitting records; waiting and later records fail
+    pub fn close(&self) {
+        self.permits.close();
+    }
+
+    /// Run a record operation while holding a slot
+    async fn limited(
+        &self,
+        operation: &str,
+        future: impl Future<Output = Result<()>>,
+    ) -> Result<()> {
+        let acquire = self.permits.acquire();
+        let permit = match self.limits.acquire_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, acquire)
+                .await
+                .map_err(|_| metrics_timeout_error_after(operation, timeout))?,
+            None => acquire.await,
+        };
+        let _permit = permit.map_err(|_| {
+            metrics_adapter_error("concurrency_limit", "Adapter is closed to new records")
+        })?;
+
+        future.await
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for ConcurrencyLimitMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, ConcurrencyLimits>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        config.settings.validate()?;
+        let inner = M::new(config.inner).await?;
+        Self::new(inner, config.settings)
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.limited("record", self.inner.record(request)).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        self.limited("record_batch", self.inner.record_batch(requests))
+            .await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        self.limited("record", self.inner.record_validated(request))
+            .await
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Instant;
+
+    fn slow_mock(latency: Duration) -> MockMetricsAdapter {
+        MockMetricsAdapter::new(MockMetricsConfig::default().with_record_latency(latency))
+    }
+
+    #[tokio::test]
+    async fn test_single_permit_serializes_records() {
+        let metrics = ConcurrencyLimitMetricsAdapter::new(
+            slow_mock(Duration::from_millis(50)),
+            ConcurrencyLimits::new(1),
+        )
+        .unwrap();
+        let first = MetricRequest::counter("requests", 1.0);
+        let second = MetricRequest::counter("requests", 1.0);
+
+        let started = Instant::now();
+        let (a, b) = tokio::join!(metrics.record(&first), metrics.record(&second));
+        a.unwrap();
+        b.unwrap();
+
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert_eq!(metrics.inner().get_metrics_count().await, 2);
+        assert_eq!(metrics.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_timeout_and_close() {
+        let limits = ConcurrencyLimits::new(1).with_acquire_timeout(Duration::from_millis(10));
+        let metrics =
+            ConcurrencyLimitMetricsAdapter::new(slow_mock(Duration::from_millis(100)), limits)
+                .unwrap();
+        let request = MetricRequest::counter("requests", 1.0);
+
+        let (slow, waiting) = tokio::join!(metrics.record(&request), metrics.record(&request));
+        slow.unwrap();
+        assert!(waiting
+            .unwrap_err()
+            .to_string()
+            .contains("Metrics timeout error for record after 10ms"));
+
+        metrics.close();
+        assert!(metrics
+            .record(&request)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("closed"));
+    }
+
+    #[tokio::test]
+    async fn test_zero_permits_rejected() {
+        let config = DecoratorConfig::new(MockMetricsConfig::default(), ConcurrencyLimits::new(0));
+        assert!(
+            <ConcurrencyLimitMetricsAdapter<MockMetricsAdapter> as MetricsManager>::new(config)
+                .await
+                .is_err()
+        );
+        assert!(ConcurrencyLimitMetricsAdapter::new(
+            MockMetricsAdapter::default(),
+            ConcurrencyLimits::new(0)
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Predicate-based conditional recording decorator for metrics adapters
+
+use super::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Predicate deciding whether a request is recorded
+type RecordPredicate = Box<dyn Fn(&MetricRequest) -> bool + Send + Sync>;
+
+/// Decorator that records only the metrics a predicate accepts
+///
+/// Every request is passed to the predicate before forwarding; requests it
+/// rejects are dropped without error and counted by `filtered_count`. This
+/// keeps conditions such as "only sampled tenants" out of business code.
+/// Timers are checked when they start, against a zero-duration timer request
+/// with the timer's name and labels; a rejected timer records nothing. Scope
+/// counter guards are checked the same way against a `counter_inc` request.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{ConditionalMetricsAdapter, MetricRequest, MetricsManager, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = ConditionalMetricsAdapter::new(mock.clone(), |request: &MetricRequest| {
+///     !request.name().starts_with("debug_")
+/// });
+///
+/// metrics.record(&MetricRequest::counter("debug_cache_probes", 1.0)).await.unwrap();
+/// assert_eq!(mock.get_metrics_count().await, 0);
+/// assert_eq!(metrics.filtered_count(), 1);
+/// # });
+/// ```
+pub struct ConditionalMetricsAdapter<M> {
+    inner: M,
+    predicate: RecordPredicate,
+    filtered: AtomicU64,
+}
+
+impl<M: MetricsManager> ConditionalMetricsAdapter<M> {
+    /// Wrap an adapter, recording only requests for which `predicate` returns true
+    pub fn new<F>(inner: M, predicate: F) -> Self
+    where
+        F: Fn(&MetricRequest) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            inner,
+            predicate: Box::new(predicate),
+            filtered: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Number of requests and timers dropped by the predicate
+    pub fn filtered_count(&self) -> u64 {
+        self.filtered.load(Ordering::Relaxed)
+    }
+
+    /// Check a request against the predicate, counting it if rejected
+    fn accepts(&self, request: &MetricRequest) -> bool {
+        let accepted = (self.predicate)(request);
+        if !accepted {
+            self.filtered.fetch_add(1, Ordering::Relaxed);
+        }
+        accepted
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for ConditionalMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, RecordPredicate>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        if !self.accepts(request) {
+            return Ok(());
+        }
+        self.inner.record(request).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        let accepted: Vec<MetricRequest> = requests
+            .iter()
+            .filter(|request| self.accepts(request))
+            .cloned()
+            .collect();
+        self.inner.record_batch(&accepted).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        if !self.accepts(request.request()) {
+            return Ok(());
+        }
+        self.inner.record_validated(request).await
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        let probe = MetricRequest::timer(name, Duration::ZERO).with_labels(labels.clone());
+        if !self.accepts(&probe) {
+            return TimerGuard::new(name.to_string(), labels, |_| {});
+        }
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        let probe = MetricRequest::counter_inc(name, 1.0).with_labels(labels.clone());
+        if !self.accepts(&probe) {
+            return CounterGuard::new(name.to_string(), labels, |_| {});
+        }
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn vip_only(request: &MetricRequest) -> bool {
+        request.labels().get("tenant").map(String::as_str) == Some("vip")
+    }
+
+    #[tokio::test]
+    async fn test_conditional_drops_non_vip_records() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = ConditionalMetricsAdapter::new(mock.clone(), vip_only);
+
+        metrics
+            .record(&MetricRequest::counter("requests", 1.0).with_label("tenant", "vip"))
+            .await
+            .unwrap();
+        metrics
+            .record(&MetricRequest::counter("requests", 1.0).with_label("tenant", "free"))
+            .await
+            .unwrap();
+        metrics
+            .record_batch(&[
+                MetricRequest::counter("requests", 1.0),
+                MetricRequest::counter("requests", 1.0).with_label("tenant", "vip"),
+            ])
+            .await
+            .unwrap();
+        drop(metrics.start_timer(
+            "request_duration",
+            [("tenant".to_string(), "free".to_string())].into(),
+        ));
+
+        let stored = mock.get_stored_metrics().await;
+        assert_eq!(stored.len(), 2);
+        assert!(stored.iter().all(|m| m.labels["tenant"] == "vip"));
+        assert_eq!(metrics.filtered_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_checks_counter_guards_as_counters() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = ConditionalMetricsAdapter::new(mock.clone(), |request: &MetricRequest| {
+            *request.metric_type() == MetricType::Counter
+        });
+
+        drop(metrics.start_scope_counter("jobs_total", Labels::new()));
+        drop(metrics.start_timer("job_duration", Labels::new()));
+
+        assert_eq!(mock.counter_total("jobs_total", None).await, 1.0);
+        assert_eq!(mock.get_metrics_count().await, 1);
+        assert_eq!(metrics.filtered_count(), 1);
+    }
+}
@@ -0,0 +1,252 @@
+//! Default label injection decorator for metrics adapters
+
+use super::*;
+use crate::utils::{merge_labels_with, validate_labels_with};
+
+/// Decorator that adds a fixed set of labels to every metric
+///
+/// Useful for labels every metric of a service should carry, such as
+/// `service`, `env`, and `region`. Labels passed at the call site win on key
+/// collisions. A record fails with a validation error if merging would exceed
+/// the label limit (32 by default; see `with_validation_limits`).
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{DefaultLabelsMetricsAdapter, Labels, MetricRequest, MetricsManager, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let defaults: Labels = [("env".to_string(), "prod".to_string())].into();
+/// let metrics = DefaultLabelsMetricsAdapter::new(mock.clone(), defaults);
+///
+/// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// assert_eq!(mock.get_stored_metrics().await[0].labels["env"], "prod");
+/// # });
+/// ```
+pub struct DefaultLabelsMetricsAdapter<M> {
+    inner: M,
+    default_labels: Labels,
+    limits: ValidationLimits,
+}
+
+impl<M: MetricsManager> DefaultLabelsMetricsAdapter<M> {
+    /// Wrap an adapter, adding `default_labels` to every metric
+    pub fn new(inner: M, default_labels: Labels) -> Self {
+        Self {
+            inner,
+            default_labels,
+            limits: ValidationLimits::default(),
+        }
+    }
+
+    /// Check merged labels against custom size limits
+    pub fn with_validation_limits(mut self, limits: ValidationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the labels added to every metric
+    pub fn default_labels(&self) -> &Labels {
+        &self.default_labels
+    }
+
+    /// Merge the default labels under a call site's labels
+    fn merge_labels(&self, labels: &mut Labels) -> Result<()> {
+        merge_labels_with(labels, &self.default_labels, "default", &self.limits)
+    }
+
+    /// Copy a request with the default labels merged in
+    fn with_defaults(&self, request: &MetricRequest) -> Result<MetricRequest> {
+        let mut merged = request.clone();
+        self.merge_labels(merged.labels_mut())?;
+        Ok(merged)
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for DefaultLabelsMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, Labels>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        validate_labels_with(&config.settings, &ValidationLimits::default())?;
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.inner.record(&self.with_defaults(request)?).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        // As with the default `record_batch`, over-limit requests don't stop
+        // the rest; the first error is returned once the batch is recorded
+        let mut first_error = None;
+        let merged: Vec<_> = requests
+            .iter()
+            .filter_map(|r| match self.with_defaults(r) {
+                Ok(merged) => Some(merged),
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                    None
+                }
+            })
+            .collect();
+
+        let result = self.inner.record_batch(&merged).await;
+        match first_error {
+            Some(error) => Err(error),
+            None => result,
+        }
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        // Name and value are unchanged; only the merged labels need checking
+        let merged = self.with_defaults(request.request())?;
+        validate_labels_with(merged.labels(), &self.limits)?;
+        self.inner
+            .record_validated(&ValidatedMetricRequest::assume_valid(merged))
+            .await
+    }
+
+    fn start_timer(&self, name: &str, mut labels: Labels) -> TimerGuard {
+        // Timers can't fail here; an over-limit set is left to the inner adapter
+        for (key, value) in &self.default_labels {
+            labels.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, mut labels: Labels) -> CounterGuard {
+        // Guards can't fail here; an over-limit set is left to the inner adapter
+        for (key, value) in &self.default_labels {
+            labels.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn service_labels() -> Labels {
+        [
+            ("service", "checkout"),
+            ("env", "prod"),
+            ("region", "eu-west-1"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn test_default_labels_call_site_wins() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = DefaultLabelsMetricsAdapter::new(mock.clone(), service_labels());
+
+        let request = MetricRequest::counter("orders_total", 1.0)
+            .with_label("env", "staging")
+            .with_label("method", "POST");
+        metrics.record(&request).await.unwrap();
+
+        let stored = mock.get_stored_metrics().await.remove(0);
+        assert_eq!(stored.labels.len(), 4);
+        assert_eq!(stored.labels["env"], "staging");
+        assert_eq!(stored.labels["service"], "checkout");
+        assert_eq!(stored.labels["region"], "eu-west-1");
+        assert_eq!(request.labels().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_default_labels_over_limit() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = DefaultLabelsMetricsAdapter::new(mock.clone(), service_labels());
+
+        let request = MetricRequest::counter("orders_total", 1.0)
+            .with_labels((0..30).map(|i| (format!("label_{i}"), "x")));
+        let error = metrics.record(&request).await.unwrap_err();
+
+        assert!(error.to_string().contains("exceeds the limit of 32"));
+        assert_eq!(mock.get_metrics_count().await, 0);
+
+        // Overlapping keys don't count twice
+        let request = MetricRequest::counter("orders_total", 1.0)
+            .with_labels((0..29).map(|i| (format!("label_{i}"), "x")))
+            .with_label("env", "dev");
+        assert!(metrics.record(&request).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_default_labels_batch_records_valid_requests() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = DefaultLabelsMetricsAdapter::new(mock.clone(), service_labels());
+
+        let over_limit = MetricRequest::counter("orders_total", 1.0)
+            .with_labels((0..30).map(|i| (format!("label_{i}"), "x")));
+        let error = metrics
+            .record_batch(&[
+                MetricRequest::counter("orders_total", 1.0),
+                over_limit,
+                MetricRequest::gauge("queue_depth", 3.0),
+            ])
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("exceeds the limit of 32"));
+        assert_eq!(mock.get_metrics_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_default_labels_custom_limits() {
+        let limits = ValidationLimits::default().with_max_labels(64);
+        let mock = Arc::new(
+            MockMetricsAdapter::builder()
+                .validation_limits(limits)
+                .build()
+                .unwrap(),
+        );
+        let metrics = DefaultLabelsMetricsAdapter::new(mock.clone(), service_labels())
+            .with_validation_limits(limits);
+
+        let request = MetricRequest::counter("orders_total", 1.0)
+            .with_labels((0..40).map(|i| (format!("label_{i}"), "x")));
+        metrics.record(&request).await.unwrap();
+        assert_eq!(mock.get_stored_metrics().await[0].labels.len(), 43);
+    }
+}
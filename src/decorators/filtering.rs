@@ -0,0 +1,237 @@
+//! Label filtering decorator for metrics adapters
+
+use super::*;
+use std::collections::HashSet;
+
+/// Which label keys may leave the process
+///
+/// Denied keys are always removed. If an allow-list is set, only keys on it
+/// are kept; the deny-list still applies on top.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelFilter {
+    /// Label keys that are always removed
+    pub deny_keys: HashSet<String>,
+
+    /// If set, the only label keys that are kept
+    pub allow_keys: Option<HashSet<String>>,
+}
+
+impl LabelFilter {
+    /// Create a filter that keeps every label
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove the given label keys
+    pub fn deny<I, K>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        self.deny_keys.extend(keys.into_iter().map(Into::into));
+        self
+    }
+
+    /// Keep only the given label keys
+    pub fn allow<I, K>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        self.allow_keys
+            .get_or_insert_with(HashSet::new)
+            .extend(keys.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether a label key passes the filter
+    pub fn keeps(&self, key: &str) -> bool {
+        !self.deny_keys.contains(key)
+            && self
+                .allow_keys
+                .as_ref()
+                .map_or(true, |allowed| allowed.contains(key))
+    }
+
+    /// Apply the filter to a label set in place
+    fn apply(&self, labels: &mut Labels) {
+        labels.retain(|key, _| self.keeps(key));
+    }
+}
+
+/// Decorator that strips labels before they reach the inner adapter
+///
+/// Every request is copied with its labels filtered by a `LabelFilter`; the
+/// caller's request is never modified. Timer labels are filtered too, which
+/// keeps sensitive labels such as `user_email` from ever leaving the process.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{FilteringMetricsAdapter, LabelFilter, MetricRequest, MetricsManager, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = FilteringMetricsAdapter::new(mock.clone(), LabelFilter::new().deny(["user_email"]));
+///
+/// let request = MetricRequest::counter("logins", 1.0).with_label("user_email", "a@example.com");
+/// metrics.record(&request).await.unwrap();
+/// assert!(mock.get_stored_metrics().await[0].labels.is_empty());
+/// # });
+/// ```
+pub struct FilteringMetricsAdapter<M> {
+    inner: M,
+    filter: LabelFilter,
+}
+
+impl<M: MetricsManager> FilteringMetricsAdapter<M> {
+    /// Wrap an adapter, filtering labels with `filter`
+    pub fn new(inner: M, filter: LabelFilter) -> Self {
+        Self { inner, filter }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the label filter
+    pub fn filter(&self) -> &LabelFilter {
+        &self.filter
+    }
+
+    /// Copy a request with its labels filtered
+    fn filtered(&self, request: &MetricRequest) -> MetricRequest {
+        let mut filtered = request.clone();
+        self.filter.apply(filtered.labels_mut());
+        filtered
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for FilteringMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, LabelFilter>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.inner.record(&self.filtered(request)).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        let filtered: Vec<MetricRequest> = requests.iter().map(|r| self.filtered(r)).collect();
+        self.inner.record_batch(&filtered).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        // Dropping labels can't make a valid request invalid
+        let filtered = ValidatedMetricRequest::assume_valid(self.filtered(request.request()));
+        self.inner.record_validated(&filtered).await
+    }
+
+    fn start_timer(&self, name: &str, mut labels: Labels) -> TimerGuard {
+        self.filter.apply(&mut labels);
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, mut labels: Labels) -> CounterGuard {
+        self.filter.apply(&mut labels);
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn sample_request() -> MetricRequest {
+        MetricRequest::counter("logins_total", 2.0)
+            .with_label("user_email", "a@example.com")
+            .with_label("method", "password")
+            .with_label("region", "eu")
+    }
+
+    async fn record_filtered(filter: LabelFilter) -> (MetricRequest, MetricSnapshot) {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = FilteringMetricsAdapter::new(mock.clone(), filter);
+
+        let request = sample_request();
+        metrics.record(&request).await.unwrap();
+        let stored = mock.get_stored_metrics().await.remove(0);
+        (request, stored)
+    }
+
+    #[tokio::test]
+    async fn test_filtering_deny_list() {
+        let (request, stored) = record_filtered(LabelFilter::new().deny(["user_email"])).await;
+
+        assert!(!stored.labels.contains_key("user_email"));
+        assert_eq!(stored.labels.len(), 2);
+        // The caller's request is untouched
+        assert_eq!(request.labels().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_filtering_allow_list() {
+        let filter = LabelFilter::new()
+            .allow(["method", "user_email"])
+            .deny(["user_email"]);
+        let (_, stored) = record_filtered(filter).await;
+
+        let keys: Vec<&String> = stored.labels.keys().collect();
+        assert_eq!(keys, vec!["method"]);
+    }
+
+    #[tokio::test]
+    async fn test_filtering_keeps_name_and_value() {
+        let (request, stored) = record_filtered(LabelFilter::new().deny(["region"])).await;
+
+        assert_eq!(stored.name, request.name());
+        assert_eq!(stored.metric_type, *request.metric_type());
+        assert_eq!(stored.value, MetricValue::Single(2.0));
+    }
+
+    #[tokio::test]
+    async fn test_filtering_timer_labels() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics =
+            FilteringMetricsAdapter::new(mock.clone(), LabelFilter::new().deny(["user_id"]));
+
+        let labels: Labels = [("user_id".to_string(), "42".to_string())].into();
+        drop(metrics.start_timer("request_duration", labels));
+
+        assert!(mock.get_stored_metrics().await[0].labels.is_empty());
+    }
+}
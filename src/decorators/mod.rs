@@ -0,0 +1,70 @@
+//! Composable decorators for metrics adapters
+//!
+//! Each decorator wraps any `MetricsManager` and implements `MetricsManager`
+//! itself, so cross-cutting behavior (timeouts, retries, filtering, ...) can be
+//! layered on top of any adapter without the adapter knowing about it.
+//!
+//! Decorators are usually built directly around an existing adapter with their
+//! inherent `new`. The trait constructor takes a `DecoratorConfig` holding the
+//! inner adapter's config plus the decorator's own settings.
+
+use super::*;
+
+mod buffering;
+mod circuit_breaker;
+mod concurrency_limit;
+mod conditional;
+mod default_labels;
+mod filtering;
+mod prefix;
+mod quantizing;
+mod rate_limit;
+mod redacting;
+mod relabel;
+mod retry;
+mod timeout;
+#[cfg(feature = "tracing")]
+mod trace_context;
+#[cfg(feature = "tracing")]
+mod traced;
+#[cfg(feature = "unicode")]
+mod unicode;
+pub use buffering::{BufferSettings, BufferingMetricsAdapter};
+pub use circuit_breaker::{CircuitBreakerMetricsAdapter, CircuitBreakerSettings, CircuitState};
+pub use concurrency_limit::{ConcurrencyLimitMetricsAdapter, ConcurrencyLimits};
+pub use conditional::ConditionalMetricsAdapter;
+pub use default_labels::DefaultLabelsMetricsAdapter;
+pub use filtering::{FilteringMetricsAdapter, LabelFilter};
+pub use prefix::PrefixMetricsAdapter;
+pub use quantizing::{Quantization, QuantizeSettings, QuantizingMetricsAdapter, RoundingMode};
+pub use rate_limit::{RateLimitMetricsAdapter, RateLimits};
+pub use redacting::{RedactingMetricsAdapter, RedactionMode};
+pub use relabel::{RelabelMetricsAdapter, RelabelRule};
+pub use retry::{RetryMetricsAdapter, RetryPolicy};
+pub use timeout::TimeoutMetricsAdapter;
+#[cfg(feature = "tracing")]
+pub use trace_context::{TraceContext, TraceContextMetricsAdapter};
+#[cfg(feature = "tracing")]
+pub use traced::TracingMetricsAdapter;
+#[cfg(feature = "unicode")]
+pub use unicode::UnicodeNormalizingMetricsAdapter;
+
+/// Configuration for creating a decorator through `MetricsManager::new`
+///
+/// `inner` is passed to the wrapped adapter's constructor and `settings`
+/// configures the decorator itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecoratorConfig<C, S> {
+    /// Configuration for the wrapped adapter
+    pub inner: C,
+
+    /// Decorator-specific settings
+    pub settings: S,
+}
+
+impl<C, S> DecoratorConfig<C, S> {
+    /// Create a decorator configuration
+    pub fn new(inner: C, settings: S) -> Self {
+        Self { inner, settings }
+    }
+}
@@ -0,0 +1,163 @@
+//! Metric name prefix decorator for metrics adapters
+
+use super::*;
+
+/// Decorator that namespaces every metric name with a prefix
+///
+/// Records and timers are forwarded as `<prefix>_<name>`, normalized with
+/// `normalize_metric_name` so a prefix ending in `_` doesn't produce double
+/// underscores. The inner adapter stores the prefixed names, so snapshots
+/// reflect them too.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter, PrefixMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = PrefixMetricsAdapter::new(mock.clone(), "billing");
+///
+/// metrics.record(&MetricRequest::counter("invoices_total", 1.0)).await.unwrap();
+/// assert_eq!(mock.get_stored_metrics().await[0].name, "billing_invoices_total");
+/// # });
+/// ```
+pub struct PrefixMetricsAdapter<M> {
+    inner: M,
+    prefix: String,
+}
+
+impl<M: MetricsManager> PrefixMetricsAdapter<M> {
+    /// Wrap an adapter, prefixing every metric name with `prefix`
+    pub fn new(inner: M, prefix: impl Into<String>) -> Self {
+        Self {
+            inner,
+            prefix: prefix.into(),
+        }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the prefix
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Build the prefixed form of a metric name
+    pub fn prefixed_name(&self, name: &str) -> String {
+        normalize_metric_name(&format!("{}_{}", self.prefix, name))
+    }
+
+    /// Copy a request with its name prefixed
+    fn prefixed(&self, request: &MetricRequest) -> MetricRequest {
+        let mut prefixed = request.clone();
+        prefixed.set_name(self.prefixed_name(request.name()));
+        prefixed
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for PrefixMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, String>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        validate_metric_name(&config.settings)?;
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.inner.record(&self.prefixed(request)).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        let prefixed: Vec<MetricRequest> = requests.iter().map(|r| self.prefixed(r)).collect();
+        self.inner.record_batch(&prefixed).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        // Only the name changed, so only the name needs checking again
+        let prefixed = self.prefixed(request.request());
+        validate_metric_name(prefixed.name())?;
+        self.inner
+            .record_validated(&ValidatedMetricRequest::assume_valid(prefixed))
+            .await
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.inner.start_timer(&self.prefixed_name(name), labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.inner
+            .start_counter_guard(&self.prefixed_name(name), labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_prefix_applied_to_records_and_snapshots() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = PrefixMetricsAdapter::new(mock.clone(), "svc");
+
+        metrics
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .unwrap();
+        drop(metrics.start_timer("latency", Labels::new()));
+
+        let snapshot = metrics.get_snapshot().await.unwrap();
+        let names: Vec<&str> = snapshot.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["svc_requests", "svc_latency"]);
+        assert!(names.iter().all(|name| validate_metric_name(name).is_ok()));
+    }
+
+    #[test]
+    fn test_prefix_avoids_double_underscores() {
+        let metrics = PrefixMetricsAdapter::new(MockMetricsAdapter::default(), "svc_");
+        assert_eq!(metrics.prefixed_name("requests"), "svc_requests");
+    }
+
+    #[tokio::test]
+    async fn test_prefix_rejects_invalid_prefix() {
+        let config = DecoratorConfig::new(MockMetricsConfig::default(), "bad prefix".to_string());
+        let result =
+            <PrefixMetricsAdapter<MockMetricsAdapter> as MetricsManager>::new(config).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,337 @@
+//! Value quantization decorator for metrics adapters
+
+use super::*;
+use crate::errors::metrics_config_error;
+
+/// Most decimal places a quantization can keep; `f64` holds ~15 significant digits
+const MAX_DECIMALS: u32 = 15;
+
+/// Which way a value is moved onto the quantization grid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round to the closest grid point, halfway cases away from zero
+    #[default]
+    Nearest,
+
+    /// Round down, towards negative infinity
+    Floor,
+
+    /// Round up, towards positive infinity
+    Ceil,
+}
+
+impl RoundingMode {
+    /// Apply the rounding mode to a value
+    fn apply(self, value: f64) -> f64 {
+        match self {
+            RoundingMode::Nearest => value.round(),
+            RoundingMode::Floor => value.floor(),
+            RoundingMode::Ceil => value.ceil(),
+        }
+    }
+}
+
+/// The grid values are quantized to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantization {
+    /// Keep this many decimal places, e.g. `Decimals(2)` gives `123.46`
+    Decimals(u32),
+
+    /// Snap to multiples of this step, e.g. `Step(0.5)` gives `2.5`
+    Step(f64),
+}
+
+/// How values are quantized by `QuantizingMetricsAdapter`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuantizeSettings {
+    /// The grid values are snapped to
+    pub quantization: Quantization,
+
+    /// How values between grid points are rounded
+    pub mode: RoundingMode,
+}
+
+impl QuantizeSettings {
+    /// Keep `decimals` decimal places, rounding to nearest
+    pub fn decimals(decimals: u32) -> Self {
+        Self {
+            quantization: Quantization::Decimals(decimals),
+            mode: RoundingMode::Nearest,
+        }
+    }
+
+    /// Snap to multiples of `step`, rounding to nearest
+    pub fn step(step: f64) -> Self {
+        Self {
+            quantization: Quantization::Step(step),
+            mode: RoundingMode::Nearest,
+        }
+    }
+
+    /// Use a different rounding mode
+    pub fn with_mode(mut self, mode: RoundingMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Quantize a single value
+    ///
+    /// Non-finite values, and values too large to scale, are returned
+    /// unchanged.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{QuantizeSettings, RoundingMode};
+    ///
+    /// assert_eq!(QuantizeSettings::decimals(2).quantize(123.456), 123.46);
+    /// assert_eq!(QuantizeSettings::decimals(2).with_mode(RoundingMode::Floor).quantize(123.456), 123.45);
+    /// assert_eq!(QuantizeSettings::step(0.5).quantize(2.7), 2.5);
+    /// ```
+    pub fn quantize(&self, value: f64) -> f64 {
+        if !value.is_finite() {
+            return value;
+        }
+
+        // Dividing by an exact power of ten keeps results like 123.46 exact,
+        // where multiplying by a step of 0.01 would not
+        let quantized = match self.quantization {
+            Quantization::Decimals(decimals) => {
+                let scale = 10f64.powi(decimals.min(MAX_DECIMALS) as i32);
+                self.mode.apply(value * scale) / scale
+            }
+            Quantization::Step(step) => self.mode.apply(value / step) * step,
+        };
+
+        if quantized.is_finite() {
+            quantized
+        } else {
+            value
+        }
+    }
+
+    /// Check that the grid is usable
+    fn validate(&self) -> Result<()> {
+        match self.quantization {
+            Quantization::Decimals(decimals) if decimals > MAX_DECIMALS => {
+                Err(metrics_config_error(
+                    "decimals",
+                    format!("Quantization keeps at most {MAX_DECIMALS} decimal places"),
+                ))
+            }
+            Quantization::Step(step) if !(step.is_finite() && step > 0.0) => Err(
+                metrics_config_error("step", "Quantization step must be positive and finite"),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Decorator that rounds gauge, histogram, and timer values before forwarding
+///
+/// Coarser values mean fewer distinct samples to store and steadier numbers
+/// on dashboards and in tests. `Single` values of gauges, histograms, and
+/// timers are snapped to the configured grid; counters and info metrics pass
+/// through unrounded so increments are never lost, as do already-bucketed
+/// histogram values. The caller's request is never modified. Timers started
+/// with `start_timer` are recorded by the inner adapter and are not rounded.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MetricRequest, MetricValue, MetricsManager, MockMetricsAdapter, QuantizeSettings, QuantizingMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = QuantizingMetricsAdapter::new(mock.clone(), QuantizeSettings::decimals(1));
+///
+/// metrics.record(&MetricRequest::gauge("cpu_usage", 0.4321)).await.unwrap();
+/// assert_eq!(mock.get_stored_metrics().await[0].value, MetricValue::Single(0.4));
+/// # });
+/// ```
+pub struct QuantizingMetricsAdapter<M> {
+    inner: M,
+    settings: QuantizeSettings,
+}
+
+impl<M: MetricsManager> QuantizingMetricsAdapter<M> {
+    /// Wrap an adapter, quantizing values according to `settings`
+    pub fn new(inner: M, settings: QuantizeSettings) -> Self {
+        Self { inner, settings }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the quantization settings
+    pub fn settings(&self) -> QuantizeSettings {
+        self.settings
+    }
+
+    /// Copy a request with its value quantized, or `None` if it is left alone
+    fn quantized(&self, request: &MetricRequest) -> Option<MetricRequest> {
+        if matches!(
+            request.metric_type(),
+            MetricType::Counter | MetricType::Info
+        ) {
+            return None;
+        }
+
+        match request.metric_value() {
+            MetricValue::Single(value) => {
+                let quantized = self.settings.quantize(*value);
+                if quantized == *value {
+                    return None;
+                }
+                let mut request = request.clone();
+                request.set_value(MetricValue::Single(quantized));
+                Some(request)
+            }
+            MetricValue::Histogram { .. } => None,
+        }
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for QuantizingMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, QuantizeSettings>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        config.settings.validate()?;
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        match self.quantized(request) {
+            Some(quantized) => self.inner.record(&quantized).await,
+            None => self.inner.record(request).await,
+        }
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        let quantized: Vec<MetricRequest> = requests
+            .iter()
+            .map(|r| self.quantized(r).unwrap_or_else(|| r.clone()))
+            .collect();
+        self.inner.record_batch(&quantized).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        match self.quantized(request.request()) {
+            // Quantizing keeps finite values finite, so the request stays valid
+            Some(quantized) => {
+                self.inner
+                    .record_validated(&ValidatedMetricRequest::assume_valid(quantized))
+                    .await
+            }
+            None => self.inner.record_validated(request).await,
+        }
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_quantizes_gauges_but_not_counters() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = QuantizingMetricsAdapter::new(mock.clone(), QuantizeSettings::decimals(2));
+
+        metrics
+            .record(&MetricRequest::gauge("temperature", 123.456))
+            .await
+            .unwrap();
+        metrics
+            .record_batch(&[
+                MetricRequest::counter("bytes_total", 123.456),
+                MetricRequest::histogram("latency_seconds", 0.12345),
+            ])
+            .await
+            .unwrap();
+
+        let values: Vec<MetricValue> = mock
+            .get_stored_metrics()
+            .await
+            .iter()
+            .map(|m| m.value.clone())
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                MetricValue::Single(123.46),
+                MetricValue::Single(123.456),
+                MetricValue::Single(0.12),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rounding_modes_and_steps() {
+        let two = QuantizeSettings::decimals(2);
+        assert_eq!(two.with_mode(RoundingMode::Floor).quantize(123.456), 123.45);
+        assert_eq!(two.with_mode(RoundingMode::Ceil).quantize(123.451), 123.46);
+        assert_eq!(two.quantize(-1.005e-3), -0.0);
+        assert!(two.quantize(f64::NAN).is_nan());
+        assert_eq!(two.quantize(f64::MAX), f64::MAX);
+
+        let step = QuantizeSettings::step(5.0);
+        assert_eq!(step.quantize(12.4), 10.0);
+        assert_eq!(step.with_mode(RoundingMode::Ceil).quantize(10.1), 15.0);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_settings_rejected() {
+        for settings in [
+            QuantizeSettings::step(0.0),
+            QuantizeSettings::step(f64::NAN),
+            QuantizeSettings::decimals(16),
+        ] {
+            let config = DecoratorConfig::new(MockMetricsConfig::default(), settings);
+            assert!(
+                <QuantizingMetricsAdapter<MockMetricsAdapter> as MetricsManager>::new(config)
+                    .await
+                    .is_err()
+            );
+        }
+    }
+}
@@ -0,0 +1,508 @@
+//! Rate limiting decorator for metrics adapters
+
+use super::*;
+use crate::clock::Instant;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// `metric` label value for drops of names beyond `max_tracked_names`
+const OVERFLOW_LABEL: &str = "_overflow";
+
+/// Maximum records per second, per metric name
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimits {
+    /// Limit for names without an override
+    pub default_per_second: f64,
+
+    /// Per-name limits overriding the default
+    pub per_name: HashMap<String, f64>,
+
+    /// Counter incremented on the inner adapter for dropped records
+    pub dropped_metric: String,
+
+    /// How often dropped counts are reported to the inner adapter
+    ///
+    /// Drops are counted in memory and reported by the first record after
+    /// the interval has passed, and by `flush`.
+    pub report_interval: Duration,
+
+    /// Most metric names tracked with their own token bucket
+    ///
+    /// Further names share one bucket at the default limit, and their drops
+    /// are reported with `metric="_overflow"`.
+    pub max_tracked_names: usize,
+}
+
+impl RateLimits {
+    /// Allow `per_second` records per second for every metric name
+    pub fn new(per_second: f64) -> Self {
+        Self {
+            default_per_second: per_second,
+            per_name: HashMap::new(),
+            dropped_metric: "metrics_dropped_total".to_string(),
+            report_interval: Duration::from_secs(10),
+            max_tracked_names: 10_000,
+        }
+    }
+
+    /// Override the limit for one metric name
+    pub fn with_limit(mut self, name: impl Into<String>, per_second: f64) -> Self {
+        self.per_name.insert(name.into(), per_second);
+        self
+    }
+
+    /// Set the counter incremented for dropped records
+    pub fn with_dropped_metric(mut self, name: impl Into<String>) -> Self {
+        self.dropped_metric = name.into();
+        self
+    }
+
+    /// Set how often dropped counts are reported
+    pub fn with_report_interval(mut self, interval: Duration) -> Self {
+        self.report_interval = interval;
+        self
+    }
+
+    /// Set the most metric names tracked with their own token bucket
+    pub fn with_max_tracked_names(mut self, max: usize) -> Self {
+        self.max_tracked_names = max;
+        self
+    }
+
+    /// Get the limit that applies to a metric name
+    pub fn limit_for(&self, name: &str) -> f64 {
+        self.per_name
+            .get(name)
+            .copied()
+            .unwrap_or(self.default_per_second)
+    }
+
+    /// Check that every limit is a positive number and the dropped counter name is valid
+    fn validate(&self) -> Result<()> {
+        let invalid = std::iter::once(&self.default_per_second)
+            .chain(self.per_name.values())
+            .any(|limit| !limit.is_finite() || *limit <= 0.0);
+        if invalid {
+            return Err(metrics_config_error(
+                "rate_limits",
+                "Rate limits must be positive numbers",
+            ));
+        }
+        if self.max_tracked_names == 0 {
+            return Err(metrics_config_error(
+                "max_tracked_names",
+                "At least one metric name must be tracked",
+            ));
+        }
+
+        validate_metric_name(&self.dropped_metric)
+    }
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self::new(1000.0)
+    }
+}
+
+/// Token bucket holding up to one second's worth of records (at least one)
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket for `limit` records per second
+    fn full(limit: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity(limit),
+            refilled_at: now,
+        }
+    }
+
+    /// Refill for the time elapsed since the last refill
+    fn refill(&mut self, limit: f64, now: Instant) {
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit).min(capacity(limit));
+        self.refilled_at = now;
+    }
+
+    /// Take a token, returning false if none is left
+    fn try_take(&mut self) -> bool {
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Bucket capacity; limits below one per second still need room for a whole token
+fn capacity(limit: f64) -> f64 {
+    limit.max(1.0)
+}
+
+/// Token buckets and drop counts, guarded together
+struct LimiterState {
+    /// Per-name buckets, at most `max_tracked_names`
+    buckets: HashMap<String, TokenBucket>,
+
+    /// Shared bucket for names beyond `max_tracked_names`
+    overflow: TokenBucket,
+
+    /// Drops not yet reported, by `metric` label value
+    dropped: HashMap<String, u64>,
+
+    /// When drops were last reported
+    reported_at: Instant,
+}
+
+/// Decorator that drops records exceeding a per-name rate
+///
+/// Each metric name gets a token bucket refilled at its limit and holding up
+/// to one second's worth of records, so short bursts pass. At most
+/// `max_tracked_names` names get their own bucket; idle buckets, which are
+/// full again, are reclaimed first, and names beyond the bound share one
+/// bucket at the default limit.
+///
+/// Over-limit records are dropped without error and counted in memory, so
+/// drops never add writes of their own to the backend. The counts are
+/// reported every `report_interval` (by the next record) and on `flush`, as
+/// one `counter_inc` per dropped name on the `dropped_metric` counter with
+/// the name in a `metric` label. Flush before shutdown to report the last
+/// counts. Timers, health checks, and snapshots are not limited.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter, RateLimitMetricsAdapter, RateLimits};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = RateLimitMetricsAdapter::new(mock.clone(), RateLimits::new(1.0)).unwrap();
+///
+/// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// assert_eq!(mock.find_metrics_by_name("requests").await.len(), 1);
+///
+/// metrics.flush().await.unwrap();
+/// assert_eq!(mock.counter_total("metrics_dropped_total", None).await, 1.0);
+/// # });
+/// ```
+pub struct RateLimitMetricsAdapter<M> {
+    inner: M,
+    limits: RateLimits,
+    state: Mutex<LimiterState>,
+}
+
+impl<M: MetricsManager> RateLimitMetricsAdapter<M> {
+    /// Wrap an adapter, limiting records according to `limits`
+    ///
+    /// # Errors
+    /// Returns a configuration error if a limit isn't a positive number, no
+    /// names may be tracked, or the dropped counter name is invalid.
+    pub fn new(inner: M, limits: RateLimits) -> Result<Self> {
+        limits.validate()?;
+        let now = Instant::now();
+        Ok(Self {
+            inner,
+            state: Mutex::new(LimiterState {
+                buckets: HashMap::new(),
+                overflow: TokenBucket::full(limits.default_per_second, now),
+                dropped: HashMap::new(),
+                reported_at: now,
+            }),
+            limits,
+        })
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the rate limits
+    pub fn limits(&self) -> &RateLimits {
+        &self.limits
+    }
+
+    /// Take a token for `name`, counting a drop if the rate is exceeded
+    fn try_acquire(&self, name: &str) -> bool {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        let state = &mut *state;
+
+        if !state.buckets.contains_key(name) && state.buckets.len() >= self.limits.max_tracked_names
+        {
+            // A bucket that has refilled completely is the same as a new one,
+            // so dropping it loses nothing
+            state.buckets.retain(|name, bucket| {
+                let limit = self.limits.limit_for(name);
+                let mut refilled = *bucket;
+                refilled.refill(limit, now);
+                refilled.tokens < capacity(limit)
+            });
+        }
+
+        let tracked =
+            state.buckets.contains_key(name) || state.buckets.len() < self.limits.max_tracked_names;
+        let (acquired, label) = if tracked {
+            let limit = self.limits.limit_for(name);
+            let bucket = state
+                .buckets
+                .entry(name.to_string())
+                .or_insert_with(|| TokenBucket::full(limit, now));
+            bucket.refill(limit, now);
+            (bucket.try_take(), name)
+        } else {
+            state.overflow.refill(self.limits.default_per_second, now);
+            (state.overflow.try_take(), OVERFLOW_LABEL)
+        };
+
+        if !acquired {
+            *state.dropped.entry(label.to_string()).or_insert(0) += 1;
+        }
+        acquired
+    }
+
+    /// Report dropped counts if `report_interval` has passed since the last report
+    async fn report_if_due(&self) -> Result<()> {
+        let due = {
+            let state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+            !state.dropped.is_empty() && state.reported_at.elapsed() >= self.limits.report_interval
+        };
+        if due {
+            self.report_dropped().await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Send every unreported drop count to the inner adapter in one batch
+    ///
+    /// Counts the inner adapter fails to record are kept for the next report.
+    async fn report_dropped(&self) -> Result<()> {
+        let dropped = {
+            let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+            state.reported_at = Instant::now();
+            std::mem::take(&mut state.dropped)
+        };
+        if dropped.is_empty() {
+            return Ok(());
+        }
+
+        let requests: Vec<MetricRequest> = dropped
+            .iter()
+            .map(|(name, count)| {
+                MetricRequest::counter_inc(self.limits.dropped_metric.as_str(), *count as f64)
+                    .with_label("metric", name.as_str())
+            })
+            .collect();
+        let result = self.inner.record_batch(&requests).await;
+        if result.is_err() {
+            let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+            for (name, count) in dropped {
+                *state.dropped.entry(name).or_insert(0) += count;
+            }
+        }
+        result
+    }
+
+    /// Combine the outcome of a record with the outcome of a due report
+    async fn and_report(&self, result: Result<()>) -> Result<()> {
+        let reported = self.report_if_due().await;
+        result.and(reported)
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for RateLimitMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, RateLimits>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        config.settings.validate()?;
+        let inner = M::new(config.inner).await?;
+        Self::new(inner, config.settings)
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        let result = if self.try_acquire(request.name()) {
+            self.inner.record(request).await
+        } else {
+            Ok(())
+        };
+        self.and_report(result).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        let allowed: Vec<MetricRequest> = requests
+            .iter()
+            .filter(|request| self.try_acquire(request.name()))
+            .cloned()
+            .collect();
+        let result = self.inner.record_batch(&allowed).await;
+        self.and_report(result).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        let result = if self.try_acquire(request.request().name()) {
+            self.inner.record_validated(request).await
+        } else {
+            Ok(())
+        };
+        self.and_report(result).await
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let reported = self.report_dropped().await;
+        self.inner.flush().await.and(reported)
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn limited(
+        limits: RateLimits,
+    ) -> (
+        Arc<MockMetricsAdapter>,
+        RateLimitMetricsAdapter<Arc<MockMetricsAdapter>>,
+    ) {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = RateLimitMetricsAdapter::new(mock.clone(), limits).unwrap();
+        (mock, metrics)
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_tight_loop() {
+        let (mock, metrics) = limited(RateLimits::new(10.0));
+
+        for _ in 0..100 {
+            metrics
+                .record(&MetricRequest::counter("hot_path_total", 1.0))
+                .await
+                .unwrap();
+        }
+
+        let passed = mock.find_metrics_by_name("hot_path_total").await.len();
+        assert!((10..=12).contains(&passed), "passed: {passed}");
+
+        // Drops cost the backend nothing until they are reported
+        assert_eq!(mock.record_calls(), passed as u64);
+        metrics.flush().await.unwrap();
+        assert_eq!(mock.record_calls(), passed as u64 + 1);
+
+        let labels: Labels = [("metric".to_string(), "hot_path_total".to_string())].into();
+        assert_eq!(
+            mock.counter_total("metrics_dropped_total", Some(&labels))
+                .await,
+            (100 - passed) as f64
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_per_name_override_and_batch() {
+        let limits = RateLimits::new(100.0)
+            .with_limit("noisy_total", 2.0)
+            .with_dropped_metric("rate_limited_total");
+        let (mock, metrics) = limited(limits);
+
+        let mut batch = vec![MetricRequest::counter("noisy_total", 1.0); 5];
+        batch.extend(vec![MetricRequest::counter("quiet_total", 1.0); 5]);
+        metrics.record_batch(&batch).await.unwrap();
+        metrics.flush().await.unwrap();
+
+        assert_eq!(mock.find_metrics_by_name("noisy_total").await.len(), 2);
+        assert_eq!(mock.find_metrics_by_name("quiet_total").await.len(), 5);
+        assert_eq!(mock.counter_total("rate_limited_total", None).await, 3.0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_reports_drops_after_interval() {
+        let (mock, metrics) = limited(RateLimits::new(1.0).with_report_interval(Duration::ZERO));
+        let request = MetricRequest::counter("requests", 1.0);
+
+        metrics.record(&request).await.unwrap();
+        metrics.record(&request).await.unwrap();
+        assert_eq!(mock.counter_total("metrics_dropped_total", None).await, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_bounds_tracked_names() {
+        let (mock, metrics) = limited(RateLimits::new(1.0).with_max_tracked_names(2));
+
+        for i in 0..10 {
+            metrics
+                .record(&MetricRequest::counter(format!("series_{i}_total"), 1.0))
+                .await
+                .unwrap();
+        }
+        assert!(metrics.state.lock().unwrap().buckets.len() <= 2);
+
+        // Untracked names share one bucket at the default limit
+        metrics.flush().await.unwrap();
+        let overflow: Labels = [("metric".to_string(), OVERFLOW_LABEL.to_string())].into();
+        assert_eq!(
+            mock.counter_total("metrics_dropped_total", Some(&overflow))
+                .await,
+            7.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_config_validation() {
+        let config = DecoratorConfig::new(MockMetricsConfig::default(), RateLimits::new(0.0));
+        assert!(
+            <RateLimitMetricsAdapter<MockMetricsAdapter> as MetricsManager>::new(config)
+                .await
+                .is_err()
+        );
+
+        let invalid = [
+            RateLimits::new(f64::NAN),
+            RateLimits::new(10.0).with_limit("x", f64::NAN),
+            RateLimits::new(10.0).with_max_tracked_names(0),
+            RateLimits::new(10.0).with_dropped_metric("bad name"),
+        ];
+        for limits in invalid {
+            assert!(RateLimitMetricsAdapter::new(MockMetricsAdapter::default(), limits).is_err());
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Label value redaction decorator for metrics adapters
+
+use super::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Number of hex characters kept from the SHA-256 digest in `RedactionMode::Hash`
+const HASH_PREFIX_LEN: usize = 16;
+
+/// Placeholder used by `RedactionMode::Mask`
+const MASK: &str = "***";
+
+/// How to rewrite the value of a sensitive label
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionMode {
+    /// Replace with a stable SHA-256 hex prefix, preserving distribution
+    Hash,
+
+    /// Replace with a fixed `"***"`
+    Mask,
+
+    /// Replace a numeric value with its bucket of the given width, e.g.
+    /// `1234` with `Bucket(1000)` becomes `"1000-2000"`; non-numeric values
+    /// are masked
+    Bucket(u64),
+}
+
+impl RedactionMode {
+    /// Redact a single label value
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::RedactionMode;
+    ///
+    /// assert_eq!(RedactionMode::Mask.redact("alice"), "***");
+    /// assert_eq!(RedactionMode::Bucket(100).redact("250"), "200-300");
+    /// assert_eq!(RedactionMode::Hash.redact("alice"), RedactionMode::Hash.redact("alice"));
+    /// ```
+    pub fn redact(&self, value: &str) -> String {
+        match self {
+            RedactionMode::Hash => {
+                let digest = Sha256::digest(value.as_bytes());
+                digest[..HASH_PREFIX_LEN / 2]
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect()
+            }
+            RedactionMode::Mask => MASK.to_string(),
+            RedactionMode::Bucket(width) => match value.trim().parse::<f64>() {
+                Ok(number) if number.is_finite() => {
+                    let width = (*width).max(1) as f64;
+                    let lower = (number / width).floor() * width;
+                    format!("{}-{}", lower, lower + width)
+                }
+                _ => MASK.to_string(),
+            },
+        }
+    }
+}
+
+/// Decorator that rewrites sensitive label values before forwarding
+///
+/// Labels whose key has a configured `RedactionMode` keep their key but have
+/// their value hashed, masked, or bucketed; other labels pass unchanged. The
+/// caller's request is never modified. Hashing is stable across processes, so
+/// the same input always yields the same redacted value.
+///
+/// # Example
+/// ```rust
+/// use std::collections::HashMap;
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter, RedactingMetricsAdapter, RedactionMode};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let rules = HashMap::from([("user_id".to_string(), RedactionMode::Mask)]);
+/// let metrics = RedactingMetricsAdapter::new(mock.clone(), rules);
+///
+/// let request = MetricRequest::counter("logins", 1.0).with_label("user_id", "42");
+/// metrics.record(&request).await.unwrap();
+/// assert_eq!(mock.get_stored_metrics().await[0].labels["user_id"], "***");
+/// # });
+/// ```
+pub struct RedactingMetricsAdapter<M> {
+    inner: M,
+    rules: HashMap<String, RedactionMode>,
+}
+
+impl<M: MetricsManager> RedactingMetricsAdapter<M> {
+    /// Wrap an adapter, redacting label values by key according to `rules`
+    pub fn new(inner: M, rules: HashMap<String, RedactionMode>) -> Self {
+        Self { inner, rules }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the redaction rules
+    pub fn rules(&self) -> &HashMap<String, RedactionMode> {
+        &self.rules
+    }
+
+    /// Redact matching label values in place
+    fn apply(&self, labels: &mut Labels) {
+        for (key, value) in labels.iter_mut() {
+            if let Some(mode) = self.rules.get(key) {
+                *value = mode.redact(value);
+            }
+        }
+    }
+
+    /// Copy a request with its label values redacted
+    fn redacted(&self, request: &MetricRequest) -> MetricRequest {
+        let mut redacted = request.clone();
+        self.apply(redacted.labels_mut());
+        redacted
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for RedactingMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, HashMap<String, RedactionMode>>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.inner.record(&self.redacted(request)).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        let redacted: Vec<MetricRequest> = requests.iter().map(|r| self.redacted(r)).collect();
+        self.inner.record_batch(&redacted).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        // Redacted values are short and printable, so the request stays valid
+        let redacted = ValidatedMetricRequest::assume_valid(self.redacted(request.request()));
+        self.inner.record_validated(&redacted).await
+    }
+
+    fn start_timer(&self, name: &str, mut labels: Labels) -> TimerGuard {
+        self.apply(&mut labels);
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, mut labels: Labels) -> CounterGuard {
+        self.apply(&mut labels);
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    async fn record_redacted(mode: RedactionMode, value: &str) -> MetricSnapshot {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let rules = HashMap::from([("field".to_string(), mode)]);
+        let metrics = RedactingMetricsAdapter::new(mock.clone(), rules);
+
+        let request = MetricRequest::counter("events", 1.0)
+            .with_label("field", value)
+            .with_label("method", "GET");
+        metrics.record(&request).await.unwrap();
+        mock.get_stored_metrics().await.remove(0)
+    }
+
+    #[tokio::test]
+    async fn test_redaction_hash_is_stable() {
+        let first = record_redacted(RedactionMode::Hash, "user-42").await;
+        let second = record_redacted(RedactionMode::Hash, "user-42").await;
+        let other = record_redacted(RedactionMode::Hash, "user-43").await;
+
+        assert_eq!(first.labels["field"], second.labels["field"]);
+        assert_ne!(first.labels["field"], other.labels["field"]);
+        assert_eq!(first.labels["field"].len(), HASH_PREFIX_LEN);
+        // Known SHA-256 prefix, so output can't drift between releases
+        assert_eq!(RedactionMode::Hash.redact("abc"), "ba7816bf8f01cfea");
+        assert_eq!(first.labels["method"], "GET");
+    }
+
+    #[tokio::test]
+    async fn test_redaction_mask() {
+        let stored = record_redacted(RedactionMode::Mask, "alice@example.com").await;
+        assert_eq!(stored.labels["field"], "***");
+    }
+
+    #[tokio::test]
+    async fn test_redaction_bucket() {
+        let stored = record_redacted(RedactionMode::Bucket(1000), "1234").await;
+        assert_eq!(stored.labels["field"], "1000-2000");
+
+        assert_eq!(RedactionMode::Bucket(10).redact("-5"), "-10-0");
+        assert_eq!(RedactionMode::Bucket(10).redact("not a number"), "***");
+    }
+}
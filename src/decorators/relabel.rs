@@ -0,0 +1,413 @@
+//! Prometheus-style relabeling decorator for metrics adapters
+
+use super::*;
+use crate::errors::metrics_config_error;
+use crate::utils::validate_labels_with;
+use regex::Regex;
+
+/// One relabeling step, applied to a request's labels
+///
+/// Regexes are anchored at both ends, as in Prometheus, so `5..` matches
+/// `503` but not `1503`. A label that is absent is matched as an empty value.
+#[derive(Debug, Clone)]
+pub enum RelabelRule {
+    /// Move the value of label `from` to label `to`, replacing any value `to` had
+    Rename {
+        /// Label key to rename
+        from: String,
+        /// New label key
+        to: String,
+    },
+
+    /// Drop the whole metric when the value of label `key` matches
+    Drop {
+        /// Label key to match
+        key: String,
+        /// Anchored pattern for the label value
+        value_regex: Regex,
+    },
+
+    /// Rewrite the value of label `key` when it matches
+    ///
+    /// `replacement` may refer to capture groups (`$1`, `${name}`). A
+    /// non-matching value is left unchanged; an empty result removes the
+    /// label.
+    Replace {
+        /// Label key to rewrite
+        key: String,
+        /// Anchored pattern for the label value
+        regex: Regex,
+        /// Replacement value, expanded with the regex captures
+        replacement: String,
+    },
+}
+
+impl RelabelRule {
+    /// Rename label `from` to `to`
+    pub fn rename(from: impl Into<String>, to: impl Into<String>) -> Self {
+        RelabelRule::Rename {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    /// Drop metrics whose `key` label matches `value_regex`
+    ///
+    /// Fails with a configuration error if the pattern is not a valid regex.
+    pub fn drop(key: impl Into<String>, value_regex: &str) -> Result<Self> {
+        Ok(RelabelRule::Drop {
+            key: key.into(),
+            value_regex: anchored(value_regex)?,
+        })
+    }
+
+    /// Rewrite the `key` label to `replacement` when it matches `regex`
+    ///
+    /// Fails with a configuration error if the pattern is not a valid regex.
+    pub fn replace(
+        key: impl Into<String>,
+        regex: &str,
+        replacement: impl Into<String>,
+    ) -> Result<Self> {
+        Ok(RelabelRule::Replace {
+            key: key.into(),
+            regex: anchored(regex)?,
+            replacement: replacement.into(),
+        })
+    }
+
+    /// Apply the rule to a label set; returns false if the metric is dropped
+    fn apply(&self, labels: &mut Labels) -> bool {
+        match self {
+            RelabelRule::Rename { from, to } => {
+                if let Some(value) = labels.remove(from) {
+                    labels.insert(to.clone(), value);
+                }
+                true
+            }
+            RelabelRule::Drop { key, value_regex } => {
+                !value_regex.is_match(labels.get(key).map_or("", String::as_str))
+            }
+            RelabelRule::Replace {
+                key,
+                regex,
+                replacement,
+            } => {
+                let value = labels.get(key).map_or("", String::as_str);
+                if let Some(captures) = regex.captures(value) {
+                    let mut replaced = String::new();
+                    captures.expand(replacement, &mut replaced);
+                    if replaced.is_empty() {
+                        labels.remove(key);
+                    } else {
+                        labels.insert(key.clone(), replaced);
+                    }
+                }
+                true
+            }
+        }
+    }
+}
+
+/// Compile a pattern that must match the whole value
+fn anchored(pattern: &str) -> Result<Regex> {
+    Regex::new(&format!("^(?:{pattern})$")).map_err(|error| {
+        metrics_config_error(
+            "relabel_rule",
+            format!("Invalid regex {pattern:?}: {error}"),
+        )
+    })
+}
+
+/// Decorator that rewrites labels with ordered relabeling rules
+///
+/// Every request is copied and its labels run through the rules in order,
+/// each seeing the previous one's output; the caller's request is never
+/// modified. Metrics matched by a `Drop` rule are discarded without error.
+/// The relabeled set must pass `validate_labels_with` against the adapter's
+/// limits (see `with_validation_limits`), so a rule producing an invalid key
+/// fails the record. Timer labels are relabeled when the timer
+/// starts; a dropped timer records nothing, and invalid timer labels are left
+/// to the inner adapter.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter, RelabelMetricsAdapter, RelabelRule};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = RelabelMetricsAdapter::new(
+///     mock.clone(),
+///     vec![
+///         RelabelRule::rename("statusCode", "status"),
+///         RelabelRule::replace("status", "([1-5])..", "${1}xx").unwrap(),
+///     ],
+/// );
+///
+/// let request = MetricRequest::counter("responses", 1.0).with_label("statusCode", "503");
+/// metrics.record(&request).await.unwrap();
+/// assert_eq!(mock.get_stored_metrics().await[0].labels["status"], "5xx");
+/// # });
+/// ```
+pub struct RelabelMetricsAdapter<M> {
+    inner: M,
+    rules: Vec<RelabelRule>,
+    limits: ValidationLimits,
+}
+
+impl<M: MetricsManager> RelabelMetricsAdapter<M> {
+    /// Wrap an adapter, relabeling with `rules` in order
+    pub fn new(inner: M, rules: Vec<RelabelRule>) -> Self {
+        Self {
+            inner,
+            rules,
+            limits: ValidationLimits::default(),
+        }
+    }
+
+    /// Check relabeled labels against custom size limits
+    pub fn with_validation_limits(mut self, limits: ValidationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the relabeling rules
+    pub fn rules(&self) -> &[RelabelRule] {
+        &self.rules
+    }
+
+    /// Run the rules over a label set; returns false if the metric is dropped
+    fn relabel_labels(&self, labels: &mut Labels) -> bool {
+        self.rules.iter().all(|rule| rule.apply(labels))
+    }
+
+    /// Copy a request with its labels relabeled, or `None` if it is dropped
+    fn relabeled(&self, request: &MetricRequest) -> Result<Option<MetricRequest>> {
+        let mut relabeled = request.clone();
+        if !self.relabel_labels(relabeled.labels_mut()) {
+            return Ok(None);
+        }
+
+        validate_labels_with(relabeled.labels(), &self.limits)?;
+        Ok(Some(relabeled))
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for RelabelMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, Vec<RelabelRule>>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        match self.relabeled(request)? {
+            Some(relabeled) => self.inner.record(&relabeled).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        // As with the default `record_batch`, invalid requests don't stop the
+        // rest; the first error is returned once the batch is recorded
+        let mut first_error = None;
+        let relabeled: Vec<_> = requests
+            .iter()
+            .filter_map(|r| match self.relabeled(r) {
+                Ok(relabeled) => relabeled,
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                    None
+                }
+            })
+            .collect();
+
+        let result = self.inner.record_batch(&relabeled).await;
+        match first_error {
+            Some(error) => Err(error),
+            None => result,
+        }
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        // The labels were revalidated; name and value are unchanged
+        match self.relabeled(request.request())? {
+            Some(relabeled) => {
+                self.inner
+                    .record_validated(&ValidatedMetricRequest::assume_valid(relabeled))
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn start_timer(&self, name: &str, mut labels: Labels) -> TimerGuard {
+        if !self.relabel_labels(&mut labels) {
+            return TimerGuard::new(name.to_string(), labels, |_| {});
+        }
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, mut labels: Labels) -> CounterGuard {
+        if !self.relabel_labels(&mut labels) {
+            return CounterGuard::new(name.to_string(), labels, |_| {});
+        }
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn relabeling(rules: Vec<RelabelRule>) -> (Arc<MockMetricsAdapter>, impl MetricsManager) {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = RelabelMetricsAdapter::new(mock.clone(), rules);
+        (mock, metrics)
+    }
+
+    #[tokio::test]
+    async fn test_relabel_rename() {
+        let (mock, metrics) = relabeling(vec![RelabelRule::rename("svc", "service")]);
+
+        let request = MetricRequest::counter("jobs_total", 1.0)
+            .with_label("svc", "billing")
+            .with_label("service", "stale");
+        metrics.record(&request).await.unwrap();
+
+        let stored = mock.get_stored_metrics().await.remove(0);
+        assert_eq!(stored.labels.len(), 1);
+        assert_eq!(stored.labels["service"], "billing");
+        // The caller's request is untouched
+        assert_eq!(request.labels()["svc"], "billing");
+    }
+
+    #[tokio::test]
+    async fn test_relabel_drop_on_match() {
+        let (mock, metrics) =
+            relabeling(vec![RelabelRule::drop("path", "/health|/ready").unwrap()]);
+
+        metrics
+            .record_batch(&[
+                MetricRequest::counter("requests_total", 1.0).with_label("path", "/health"),
+                MetricRequest::counter("requests_total", 1.0).with_label("path", "/api/health"),
+                MetricRequest::counter("requests_total", 1.0).with_label("path", "/ready"),
+            ])
+            .await
+            .unwrap();
+        drop(metrics.start_timer(
+            "request_duration",
+            [("path".to_string(), "/ready".to_string())].into(),
+        ));
+
+        let stored = mock.get_stored_metrics().await;
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].labels["path"], "/api/health");
+    }
+
+    #[tokio::test]
+    async fn test_relabel_regex_replacement() {
+        let (mock, metrics) = relabeling(vec![
+            RelabelRule::replace("status", "([1-5])[0-9]{2}", "${1}xx").unwrap(),
+            RelabelRule::replace("debug", ".*", "").unwrap(),
+        ]);
+
+        for status in ["200", "503", "unknown"] {
+            let request = MetricRequest::counter("responses_total", 1.0)
+                .with_label("status", status)
+                .with_label("debug", "on");
+            metrics.record(&request).await.unwrap();
+        }
+
+        let statuses: Vec<String> = mock
+            .get_stored_metrics()
+            .await
+            .into_iter()
+            .map(|m| {
+                assert!(!m.labels.contains_key("debug"));
+                m.labels["status"].clone()
+            })
+            .collect();
+        assert_eq!(statuses, vec!["2xx", "5xx", "unknown"]);
+    }
+
+    #[tokio::test]
+    async fn test_relabel_result_must_be_valid() {
+        let (mock, metrics) = relabeling(vec![RelabelRule::rename("region", "__region")]);
+
+        let request = MetricRequest::counter("jobs_total", 1.0).with_label("region", "eu");
+        assert!(metrics.record(&request).await.is_err());
+        assert_eq!(mock.get_metrics_count().await, 0);
+
+        assert!(RelabelRule::drop("path", "(unclosed").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_relabel_batch_records_valid_requests() {
+        let (mock, metrics) = relabeling(vec![RelabelRule::rename("region", "__region")]);
+
+        let result = metrics
+            .record_batch(&[
+                MetricRequest::counter("jobs_total", 1.0),
+                MetricRequest::counter("jobs_total", 1.0).with_label("region", "eu"),
+                MetricRequest::counter("jobs_total", 1.0).with_label("queue", "high"),
+            ])
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(mock.find_metrics_by_name("jobs_total").await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_relabel_custom_limits() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = RelabelMetricsAdapter::new(mock.clone(), Vec::new())
+            .with_validation_limits(ValidationLimits::default().with_max_labels(2));
+
+        let request = MetricRequest::counter("jobs_total", 1.0).with_labels([
+            ("a", "1"),
+            ("b", "2"),
+            ("c", "3"),
+        ]);
+        let error = metrics.record(&request).await.unwrap_err();
+        assert!(error.to_string().contains("Too many labels (max 2)"));
+        assert_eq!(mock.get_metrics_count().await, 0);
+    }
+}
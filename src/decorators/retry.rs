@@ -0,0 +1,216 @@
+//! Retry decorator for metrics adapters
+
+use super::*;
+use std::future::Future;
+use std::time::Duration;
+
+/// How many times to retry and how long to wait between attempts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    pub max_retries: u32,
+
+    /// Delay before the first retry; doubled for every further retry
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a retry policy
+    pub fn new(max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+        }
+    }
+
+    /// Backoff before the given retry (0-based): `base_delay * 2^retry`
+    fn delay_for(&self, retry: u32) -> Duration {
+        self.base_delay.saturating_mul(1 << retry.min(16))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(100))
+    }
+}
+
+/// Decorator that retries failed records with exponential backoff
+///
+/// Retries `record` and `record_validated` up to `max_retries` times, but
+/// only for errors classified as transient by `is_retryable`; validation and
+/// configuration errors fail immediately. Health checks and snapshots are
+/// passed through without retrying.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter, RetryMetricsAdapter, RetryPolicy};
+///
+/// # tokio_test::block_on(async {
+/// let mock = MockMetricsAdapter::default();
+/// mock.fail_next_records(1);
+///
+/// let metrics = RetryMetricsAdapter::new(mock, RetryPolicy::new(3, Duration::from_millis(1)));
+/// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// assert_eq!(metrics.inner().record_calls(), 2);
+/// # });
+/// ```
+pub struct RetryMetricsAdapter<M> {
+    inner: M,
+    policy: RetryPolicy,
+}
+
+impl<M: MetricsManager> RetryMetricsAdapter<M> {
+    /// Wrap an adapter, retrying failed records according to `policy`
+    pub fn new(inner: M, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the retry policy
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Run `attempt` until it succeeds, fails with a non-retryable error, or
+    /// runs out of retries, backing off between attempts
+    async fn retry<F, Fut>(&self, mut attempt: F) -> Result<()>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let mut retry = 0;
+        loop {
+            match attempt().await {
+                Ok(()) => return Ok(()),
+                Err(error) if !is_retryable(&error) || retry >= self.policy.max_retries => {
+                    return Err(error)
+                }
+                Err(_) => {}
+            }
+
+            tokio::time::sleep(self.policy.delay_for(retry)).await;
+            retry += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for RetryMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, RetryPolicy>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.retry(|| self.inner.record(request)).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        // Retrying a partially failed batch would duplicate the successes
+        self.inner.record_batch(requests).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        self.retry(|| self.inner.record_validated(request)).await
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy::new(max_retries, Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn test_retry_succeeds_after_transient_failures() {
+        let mock = MockMetricsAdapter::default();
+        mock.fail_next_records(2);
+        let metrics = RetryMetricsAdapter::new(mock, fast_policy(3));
+
+        metrics
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .unwrap();
+
+        assert_eq!(metrics.inner().record_calls(), 3);
+        assert_eq!(metrics.inner().get_metrics_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_retries() {
+        let mock = MockMetricsAdapter::default();
+        mock.fail_next_records(10);
+        let metrics = RetryMetricsAdapter::new(mock, fast_policy(2));
+
+        assert!(metrics
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .is_err());
+        assert_eq!(metrics.inner().record_calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_skips_validation_errors() {
+        let metrics = RetryMetricsAdapter::new(MockMetricsAdapter::default(), fast_policy(3));
+
+        assert!(metrics
+            .record(&MetricRequest::counter("invalid name", 1.0))
+            .await
+            .is_err());
+        assert_eq!(metrics.inner().record_calls(), 1);
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(10));
+        assert_eq!(policy.delay_for(0), Duration::from_millis(10));
+        assert_eq!(policy.delay_for(1), Duration::from_millis(20));
+        assert_eq!(policy.delay_for(2), Duration::from_millis(40));
+    }
+}
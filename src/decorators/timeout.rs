@@ -0,0 +1,202 @@
+//! Timeout decorator for metrics adapters
+
+use super::*;
+use std::future::Future;
+use std::time::Duration;
+
+/// Decorator that bounds how long adapter operations may take
+///
+/// Applies a timeout to `record`, `record_validated`, `health_check`,
+/// `health_check_detailed`, `flush`, and `get_snapshot`, failing with
+/// `metrics_timeout_error_after` when the inner adapter hangs. Timer guards are
+/// synchronous and pass straight through.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter, TimeoutMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let metrics = TimeoutMetricsAdapter::new(MockMetricsAdapter::default(), Duration::from_secs(1));
+/// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// assert_eq!(metrics.inner().get_metrics_count().await, 1);
+/// # });
+/// ```
+pub struct TimeoutMetricsAdapter<M> {
+    inner: M,
+    timeout: Duration,
+}
+
+impl<M: MetricsManager> TimeoutMetricsAdapter<M> {
+    /// Wrap an adapter, bounding each operation by `timeout`
+    pub fn new(inner: M, timeout: Duration) -> Self {
+        Self { inner, timeout }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Get the configured timeout
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Run an operation, mapping expiry to a timeout error
+    async fn bounded<T>(
+        &self,
+        operation: &str,
+        future: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        tokio::time::timeout(self.timeout, future)
+            .await
+            .unwrap_or_else(|_| Err(metrics_timeout_error_after(operation, self.timeout)))
+    }
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for TimeoutMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, Duration>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner, config.settings))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.bounded("record", self.inner.record(request)).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        self.bounded("record_batch", self.inner.record_batch(requests))
+            .await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        self.bounded("record", self.inner.record_validated(request))
+            .await
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.bounded("health_check", self.inner.health_check())
+            .await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.bounded("health_check", self.inner.health_check_detailed())
+            .await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.bounded("flush", self.inner.flush()).await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.bounded("get_snapshot", self.inner.get_snapshot())
+            .await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.bounded("get_snapshot", self.inner.get_snapshot_filtered(filter))
+            .await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.bounded("get_snapshot", self.inner.get_snapshot_paged(offset, limit))
+            .await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.bounded("get_snapshot", self.inner.snapshot_len())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Adapter whose operations take a fixed time to complete
+    struct SlowAdapter {
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl MetricsManager for SlowAdapter {
+        type Config = Duration;
+
+        async fn new(delay: Self::Config) -> Result<Self> {
+            Ok(Self { delay })
+        }
+
+        async fn record(&self, _request: &MetricRequest) -> Result<()> {
+            tokio::time::sleep(self.delay).await;
+            Ok(())
+        }
+
+        fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+            TimerGuard::new(name.to_string(), labels, |_| {})
+        }
+
+        async fn health_check(&self) -> Result<HealthStatus> {
+            tokio::time::sleep(self.delay).await;
+            Ok(HealthStatus::healthy())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_fires_on_slow_record() {
+        let slow = SlowAdapter {
+            delay: Duration::from_millis(200),
+        };
+        let metrics = TimeoutMetricsAdapter::new(slow, Duration::from_millis(20));
+
+        let error = metrics
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Metrics timeout error for record after 20ms"));
+
+        let error = metrics.health_check().await.unwrap_err();
+        assert!(error.to_string().contains("timeout error for health_check"));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_passes_fast_operations() {
+        let config = DecoratorConfig::new(Duration::from_millis(1), Duration::from_secs(1));
+        let metrics = <TimeoutMetricsAdapter<SlowAdapter> as MetricsManager>::new(config)
+            .await
+            .unwrap();
+
+        assert!(metrics
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .is_ok());
+        assert!(metrics.health_check().await.unwrap().is_healthy);
+        assert!(metrics.get_snapshot().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_with_slow_mock() {
+        let config = MockMetricsConfig::default().with_record_latency(Duration::from_millis(200));
+        let metrics =
+            TimeoutMetricsAdapter::new(MockMetricsAdapter::new(config), Duration::from_millis(20));
+
+        assert!(metrics
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .is_err());
+        assert_eq!(metrics.inner().get_metrics_count().await, 0);
+    }
+}
@@ -0,0 +1,311 @@
+//! Trace context label injection decorator for metrics adapters
+
+use super::*;
+use crate::utils::merge_labels_with;
+
+/// Trace and span IDs of the active span
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// Trace ID, if the source knows it
+    pub trace_id: Option<String>,
+
+    /// Span ID
+    pub span_id: String,
+}
+
+impl TraceContext {
+    /// Read the context of the current `tracing` span
+    ///
+    /// Returns `None` outside any span. `tracing` has no notion of traces, so
+    /// only `span_id` is set (the span's ID as 16 hex digits); use
+    /// `TraceContextMetricsAdapter::with_source` to read OpenTelemetry trace
+    /// IDs instead.
+    pub fn current_span() -> Option<Self> {
+        let id = tracing::Span::current().id()?;
+        Some(Self {
+            trace_id: None,
+            span_id: format!("{:016x}", id.into_u64()),
+        })
+    }
+
+    /// The labels this context adds, as `(key, value)` pairs
+    fn labels(&self) -> impl Iterator<Item = (&'static str, &str)> + Clone {
+        self.trace_id
+            .as_deref()
+            .map(|trace_id| ("trace_id", trace_id))
+            .into_iter()
+            .chain(std::iter::once(("span_id", self.span_id.as_str())))
+    }
+}
+
+/// Function reading the trace context at record time
+type ContextSource = Box<dyn Fn() -> Option<TraceContext> + Send + Sync>;
+
+/// Decorator that labels metrics with the active trace and span IDs
+///
+/// On every record the decorator reads the current `TraceContext` and adds
+/// `trace_id` and `span_id` labels, keeping any the call site already set.
+/// Outside a span, requests are forwarded unchanged. Timers get the context
+/// active when they start. Every span produces a new series, so wrap only
+/// adapters whose backend copes with that cardinality (e.g. one keeping
+/// exemplars or raw events).
+///
+/// Only available with the `tracing` feature.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter, TraceContext, TraceContextMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = TraceContextMetricsAdapter::with_source(mock.clone(), || {
+///     Some(TraceContext {
+///         trace_id: Some("4bf92f3577b34da6a3ce929d0e0e4736".to_string()),
+///         span_id: "00f067aa0ba902b7".to_string(),
+///     })
+/// });
+///
+/// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// assert_eq!(mock.get_stored_metrics().await[0].labels["span_id"], "00f067aa0ba902b7");
+/// # });
+/// ```
+pub struct TraceContextMetricsAdapter<M> {
+    inner: M,
+    source: ContextSource,
+    limits: ValidationLimits,
+}
+
+impl<M: MetricsManager> TraceContextMetricsAdapter<M> {
+    /// Wrap an adapter, labeling metrics with the current `tracing` span
+    pub fn new(inner: M) -> Self {
+        Self::with_source(inner, TraceContext::current_span)
+    }
+
+    /// Wrap an adapter, reading the trace context from `source`
+    ///
+    /// Use this to take IDs from OpenTelemetry, e.g. via
+    /// `tracing_opentelemetry::OpenTelemetrySpanExt::context`.
+    pub fn with_source<F>(inner: M, source: F) -> Self
+    where
+        F: Fn() -> Option<TraceContext> + Send + Sync + 'static,
+    {
+        Self {
+            inner,
+            source: Box::new(source),
+            limits: ValidationLimits::default(),
+        }
+    }
+
+    /// Check labeled requests against custom size limits
+    pub fn with_validation_limits(mut self, limits: ValidationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Read the current trace context
+    fn context(&self) -> Option<TraceContext> {
+        (self.source)()
+    }
+}
+
+/// Copy a request with the context's labels added under the call site's
+fn with_context(
+    request: &MetricRequest,
+    context: &TraceContext,
+    limits: &ValidationLimits,
+) -> Result<MetricRequest> {
+    let mut labeled = request.clone();
+    merge_labels_with(
+        labeled.labels_mut(),
+        context.labels(),
+        "trace context",
+        limits,
+    )?;
+    Ok(labeled)
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for TraceContextMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, ()>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        match self.context() {
+            Some(context) => {
+                self.inner
+                    .record(&with_context(request, &context, &self.limits)?)
+                    .await
+            }
+            None => self.inner.record(request).await,
+        }
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        let context = match self.context() {
+            Some(context) => context,
+            None => return self.inner.record_batch(requests).await,
+        };
+
+        let labeled = requests
+            .iter()
+            .map(|r| with_context(r, &context, &self.limits))
+            .collect::<Result<Vec<_>>>()?;
+        self.inner.record_batch(&labeled).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        match self.context() {
+            Some(context) => {
+                // IDs are plain label values; only the limit needed checking
+                let labeled = with_context(request.request(), &context, &self.limits)?;
+                self.inner
+                    .record_validated(&ValidatedMetricRequest::assume_valid(labeled))
+                    .await
+            }
+            None => self.inner.record_validated(request).await,
+        }
+    }
+
+    fn start_timer(&self, name: &str, mut labels: Labels) -> TimerGuard {
+        // Timers can't fail here; an over-limit set is left to the inner adapter
+        if let Some(context) = self.context() {
+            for (key, value) in context.labels() {
+                labels
+                    .entry(key.to_string())
+                    .or_insert_with(|| value.to_string());
+            }
+        }
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, mut labels: Labels) -> CounterGuard {
+        // Guards can't fail here; an over-limit set is left to the inner adapter
+        if let Some(context) = self.context() {
+            for (key, value) in context.labels() {
+                labels
+                    .entry(key.to_string())
+                    .or_insert_with(|| value.to_string());
+            }
+        }
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tracing::Instrument;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_trace_context_inside_and_outside_span() {
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry());
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = TraceContextMetricsAdapter::new(mock.clone());
+
+        let span = tracing::info_span!("checkout");
+        let span_id = format!("{:016x}", span.id().unwrap().into_u64());
+        metrics
+            .record(&MetricRequest::counter("inside_total", 1.0))
+            .instrument(span)
+            .await
+            .unwrap();
+        metrics
+            .record(&MetricRequest::counter("outside_total", 1.0))
+            .await
+            .unwrap();
+
+        let inside = mock.find_metrics_by_name("inside_total").await.remove(0);
+        assert_eq!(inside.labels.get("span_id"), Some(&span_id));
+        assert!(!inside.labels.contains_key("trace_id"));
+
+        let outside = mock.find_metrics_by_name("outside_total").await.remove(0);
+        assert!(outside.labels.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_trace_context_custom_source_keeps_call_site_labels() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = TraceContextMetricsAdapter::with_source(mock.clone(), || {
+            Some(TraceContext {
+                trace_id: Some("4bf92f3577b34da6a3ce929d0e0e4736".to_string()),
+                span_id: "00f067aa0ba902b7".to_string(),
+            })
+        });
+
+        metrics
+            .record_batch(&[
+                MetricRequest::counter("jobs_total", 1.0),
+                MetricRequest::counter("jobs_total", 1.0).with_label("span_id", "explicit"),
+            ])
+            .await
+            .unwrap();
+
+        let stored = mock.find_metrics_by_name("jobs_total").await;
+        assert_eq!(
+            stored[0].labels["trace_id"],
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        assert_eq!(stored[0].labels["span_id"], "00f067aa0ba902b7");
+        assert_eq!(stored[1].labels["span_id"], "explicit");
+        assert_eq!(
+            stored[1].labels["trace_id"],
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_trace_context_custom_limits() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = TraceContextMetricsAdapter::with_source(mock.clone(), || {
+            Some(TraceContext {
+                trace_id: None,
+                span_id: "00f067aa0ba902b7".to_string(),
+            })
+        })
+        .with_validation_limits(ValidationLimits::default().with_max_labels(1));
+
+        let request = MetricRequest::counter("jobs_total", 1.0).with_label("queue", "high");
+        let error = metrics.record(&request).await.unwrap_err();
+        assert!(error.to_string().contains("exceeds the limit of 1"));
+        assert_eq!(mock.get_metrics_count().await, 0);
+    }
+}
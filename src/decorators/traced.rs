@@ -0,0 +1,246 @@
+//! `tracing` integration decorator for metrics adapters
+
+use super::*;
+
+/// Decorator that emits a `tracing` event for every recorded metric
+///
+/// After each successful record the decorator emits a DEBUG event with the
+/// fields `metric.name`, `metric.type`, `metric.value`, and `metric.labels`.
+/// `tracing` fields must be known at compile time, so labels are flattened
+/// into one logfmt string (`method=GET status=200`, sorted by key). A batch emits one event per
+/// request, and only if the whole batch succeeded. Failed records emit
+/// nothing. Timers pass through untraced; their recordings reach the inner
+/// adapter directly when the guard drops.
+///
+/// Only available with the `tracing` feature.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter, TracingMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = TracingMetricsAdapter::new(mock.clone());
+///
+/// metrics.record(&MetricRequest::counter("requests_total", 1.0)).await.unwrap();
+/// assert_eq!(mock.get_stored_metrics().await.len(), 1);
+/// # });
+/// ```
+pub struct TracingMetricsAdapter<M> {
+    inner: M,
+}
+
+impl<M: MetricsManager> TracingMetricsAdapter<M> {
+    /// Wrap an adapter, tracing every successful record
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+}
+
+/// Emit the DEBUG event for one recorded metric
+fn trace_recorded(request: &MetricRequest) {
+    tracing::debug!(
+        metric.name = request.name(),
+        metric.r#type = %request.metric_type(),
+        metric.value = request.value(),
+        metric.labels = %format_labels_with(request.labels(), &FormatOptions::logfmt()),
+        "metric recorded"
+    );
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for TracingMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, ()>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.inner.record(request).await?;
+        trace_recorded(request);
+        Ok(())
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        self.inner.record_batch(requests).await?;
+        requests.iter().for_each(trace_recorded);
+        Ok(())
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        self.inner.record_validated(request).await?;
+        trace_recorded(request.request());
+        Ok(())
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Fields of one captured event, as `(name, debug-formatted value)`
+    type CapturedEvent = Vec<(String, String)>;
+
+    /// Minimal subscriber recording the fields of every event
+    #[derive(Clone, Default)]
+    struct CaptureSubscriber {
+        events: Arc<Mutex<Vec<CapturedEvent>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut CapturedEvent);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    impl Subscriber for CaptureSubscriber {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Vec::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.events.lock().unwrap().push(fields);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    fn field<'a>(event: &'a CapturedEvent, name: &str) -> Option<&'a str> {
+        event
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_tracing_emits_one_event_per_record() {
+        let subscriber = CaptureSubscriber::default();
+        let events = subscriber.events.clone();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = TracingMetricsAdapter::new(mock.clone());
+
+        metrics
+            .record(
+                &MetricRequest::counter("requests_total", 2.0)
+                    .with_label("status", "200")
+                    .with_label("method", "GET"),
+            )
+            .await
+            .unwrap();
+        metrics
+            .record_batch(&[
+                MetricRequest::gauge("queue_depth", 4.0),
+                MetricRequest::histogram("payload_bytes", 512.0),
+            ])
+            .await
+            .unwrap();
+        let validated = MetricRequest::counter("jobs_total", 1.0)
+            .validate()
+            .unwrap();
+        metrics.record_validated(&validated).await.unwrap();
+
+        assert_eq!(mock.get_stored_metrics().await.len(), 4);
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 4);
+
+        let first = &events[0];
+        assert_eq!(field(first, "metric.name"), Some("requests_total"));
+        assert_eq!(field(first, "metric.type"), Some("counter"));
+        assert_eq!(field(first, "metric.value"), Some("2.0"));
+        assert_eq!(field(first, "metric.labels"), Some("method=GET status=200"));
+        assert_eq!(field(first, "message"), Some("metric recorded"));
+        assert_eq!(field(&events[3], "metric.name"), Some("jobs_total"));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_tracing_skips_failed_records() {
+        let subscriber = CaptureSubscriber::default();
+        let events = subscriber.events.clone();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = TracingMetricsAdapter::new(mock.clone());
+
+        mock.fail_next_records(1);
+        assert!(metrics
+            .record(&MetricRequest::counter("requests_total", 1.0))
+            .await
+            .is_err());
+        assert!(metrics
+            .record(&MetricRequest::counter("bad name", 1.0))
+            .await
+            .is_err());
+
+        assert!(events.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+//! Unicode normalization decorator for metrics adapters
+
+use super::*;
+use crate::utils::{normalize_unicode, validate_labels};
+use unicode_normalization::is_nfc;
+
+/// Decorator that normalizes label values to Unicode NFC before forwarding
+///
+/// Visually identical strings can arrive in different normalization forms,
+/// e.g. `café` as a precomposed `é` or as `e` plus a combining accent, and
+/// would otherwise split into separate series. Every label value is rewritten
+/// with `normalize_unicode`; requests that are already NFC are forwarded
+/// without copying, and the caller's request is never modified. Names and
+/// label keys are ASCII by validation, so they are left alone. Timer labels
+/// are normalized when the timer starts.
+///
+/// Only available with the `unicode` feature.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter, UnicodeNormalizingMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let metrics = UnicodeNormalizingMetricsAdapter::new(mock.clone());
+///
+/// let decomposed = MetricRequest::counter("orders", 1.0).with_label("shop", "cafe\u{301}");
+/// metrics.record(&decomposed).await.unwrap();
+/// assert_eq!(mock.get_stored_metrics().await[0].labels["shop"], "caf\u{e9}");
+/// # });
+/// ```
+pub struct UnicodeNormalizingMetricsAdapter<M> {
+    inner: M,
+}
+
+impl<M: MetricsManager> UnicodeNormalizingMetricsAdapter<M> {
+    /// Wrap an adapter, normalizing label values to NFC
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+
+    /// Get the wrapped adapter
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+}
+
+/// Rewrite label values that aren't NFC in place
+fn normalize_labels(labels: &mut Labels) {
+    for value in labels.values_mut() {
+        if !is_nfc(value) {
+            *value = normalize_unicode(value);
+        }
+    }
+}
+
+/// Copy a request with NFC label values, or `None` if it already is NFC
+fn normalized(request: &MetricRequest) -> Option<MetricRequest> {
+    if request.labels().values().all(|value| is_nfc(value)) {
+        return None;
+    }
+
+    let mut normalized = request.clone();
+    normalize_labels(normalized.labels_mut());
+    Some(normalized)
+}
+
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for UnicodeNormalizingMetricsAdapter<M> {
+    type Config = DecoratorConfig<M::Config, ()>;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        let inner = M::new(config.inner).await?;
+        Ok(Self::new(inner))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        match normalized(request) {
+            Some(normalized) => self.inner.record(&normalized).await,
+            None => self.inner.record(request).await,
+        }
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        let normalized: Vec<MetricRequest> = requests
+            .iter()
+            .map(|r| normalized(r).unwrap_or_else(|| r.clone()))
+            .collect();
+        self.inner.record_batch(&normalized).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        match normalized(request.request()) {
+            Some(normalized) => {
+                // Normalization can lengthen a value, so recheck the labels
+                validate_labels(normalized.labels())?;
+                self.inner
+                    .record_validated(&ValidatedMetricRequest::assume_valid(normalized))
+                    .await
+            }
+            None => self.inner.record_validated(request).await,
+        }
+    }
+
+    fn start_timer(&self, name: &str, mut labels: Labels) -> TimerGuard {
+        normalize_labels(&mut labels);
+        self.inner.start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, mut labels: Labels) -> CounterGuard {
+        normalize_labels(&mut labels);
+        self.inner.start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        self.inner.health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        self.inner.get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        self.inner.snapshot_len().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_normalization_collapses_series() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let metrics = UnicodeNormalizingMetricsAdapter::new(mock.clone());
+
+        let precomposed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+        assert_ne!(precomposed, decomposed);
+
+        for shop in [precomposed, decomposed] {
+            metrics
+                .record(&MetricRequest::counter("orders", 1.0).with_label("shop", shop))
+                .await
+                .unwrap();
+        }
+
+        let state = mock.current_state().await;
+        assert_eq!(state.len(), 1);
+        assert_eq!(state[0].labels["shop"], precomposed);
+        assert_eq!(state[0].value, MetricValue::Single(2.0));
+    }
+}
@@ -0,0 +1,83 @@
+//! Environment-aware adapter selection
+//!
+//! Services tend to repeat the same mapping from deployment environment to
+//! metrics adapter. `adapter_for_environment` codifies it for the adapters
+//! this crate ships, returning them behind the object-safe
+//! `DynMetricsManager` shim so the caller holds one type for all of them.
+
+use super::*;
+use crate::errors::metrics_config_error;
+use std::sync::Arc;
+
+/// Pick the bundled adapter for a deployment environment
+///
+/// - `Development` gets a default `MockMetricsAdapter`, so metrics can be
+///   inspected locally (e.g. served with `metrics_handler`).
+/// - `Production` fails with a configuration error: this crate is the port
+///   and ships no production backend, so construct a real adapter (such as
+///   a Prometheus or OTLP implementation) and wrap it in `Arc` instead.
+/// - `Test` gets a `NoopMetricsAdapter`, keeping metrics out of the way.
+///
+/// Every variant is matched explicitly, so a new `Environment` variant needs
+/// a deliberate mapping here rather than silently getting the no-op adapter.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{adapter_for_environment, Environment, MetricRequest};
+///
+/// # tokio_test::block_on(async {
+/// let metrics = adapter_for_environment(Environment::Development).unwrap();
+/// metrics.record_dyn(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+///
+/// assert!(adapter_for_environment(Environment::Production).is_err());
+/// # });
+/// ```
+pub fn adapter_for_environment(environment: Environment) -> Result<Arc<dyn DynMetricsManager>> {
+    match environment {
+        Environment::Development => Ok(Arc::new(MockMetricsAdapter::default())),
+        Environment::Test => Ok(Arc::new(NoopMetricsAdapter::new())),
+        Environment::Production => Err(metrics_config_error(
+            "environment",
+            "No production metrics adapter is bundled with the port; \
+             construct a backend adapter and wrap it in Arc<dyn DynMetricsManager>",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_development_gets_mock_adapter() {
+        let metrics = adapter_for_environment(Environment::Development).unwrap();
+        metrics
+            .record_dyn(&MetricRequest::counter("requests", 1.0))
+            .await
+            .unwrap();
+
+        // Only the mock keeps what it records
+        assert_eq!(metrics.get_snapshot_dyn().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_test_gets_noop_adapter() {
+        let metrics = adapter_for_environment(Environment::Test).unwrap();
+        metrics
+            .record_dyn(&MetricRequest::counter("requests", 1.0))
+            .await
+            .unwrap();
+
+        // The no-op adapter accepts records but keeps nothing
+        assert!(metrics.get_snapshot_dyn().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_production_requires_real_adapter() {
+        let error = adapter_for_environment(Environment::Production)
+            .err()
+            .unwrap();
+        assert_eq!(metrics_error_category(&error), "configuration");
+        assert!(error.to_string().contains("No production metrics adapter"));
+    }
+}
@@ -5,6 +5,102 @@
 //! pattern of providing semantic error constructors rather than custom error types.
 
 use super::*;
+use std::time::Duration;
+
+/// Category tag threaded through every helper in this module
+///
+/// `TylError` has no slot for metrics-specific metadata, so each helper embeds
+/// a `[metrics:<category>]` tag at the start of its message. Classification
+/// then keys off the tag rather than free-form message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorCategory {
+    Validation,
+    Configuration,
+    Connection,
+    Recording,
+    Adapter,
+    Health,
+    Serialization,
+    Timeout,
+    Capacity,
+}
+
+impl ErrorCategory {
+    const ALL: [ErrorCategory; 9] = [
+        ErrorCategory::Validation,
+        ErrorCategory::Configuration,
+        ErrorCategory::Connection,
+        ErrorCategory::Recording,
+        ErrorCategory::Adapter,
+        ErrorCategory::Health,
+        ErrorCategory::Serialization,
+        ErrorCategory::Timeout,
+        ErrorCategory::Capacity,
+    ];
+
+    /// Tag embedded in error messages
+    fn tag(self) -> &'static str {
+        match self {
+            ErrorCategory::Validation => "[metrics:validation]",
+            ErrorCategory::Configuration => "[metrics:configuration]",
+            ErrorCategory::Connection => "[metrics:connection]",
+            ErrorCategory::Recording => "[metrics:recording]",
+            ErrorCategory::Adapter => "[metrics:adapter]",
+            ErrorCategory::Health => "[metrics:health]",
+            ErrorCategory::Serialization => "[metrics:serialization]",
+            ErrorCategory::Timeout => "[metrics:timeout]",
+            ErrorCategory::Capacity => "[metrics:capacity]",
+        }
+    }
+
+    /// Stable machine-readable code for this category
+    fn code(self) -> &'static str {
+        match self {
+            ErrorCategory::Validation => "METRICS_VALIDATION_FAILED",
+            ErrorCategory::Configuration => "METRICS_CONFIG_INVALID",
+            ErrorCategory::Connection => "METRICS_CONNECTION_FAILED",
+            ErrorCategory::Recording => "METRICS_RECORDING_FAILED",
+            ErrorCategory::Adapter => "METRICS_ADAPTER_FAILED",
+            ErrorCategory::Health => "METRICS_HEALTH_CHECK_FAILED",
+            ErrorCategory::Serialization => "METRICS_SERIALIZATION_FAILED",
+            ErrorCategory::Timeout => "METRICS_TIMEOUT",
+            ErrorCategory::Capacity => "METRICS_CAPACITY_EXCEEDED",
+        }
+    }
+
+    /// The `TylError` category the helpers construct this category under
+    fn tyl_category(self) -> &'static str {
+        match self {
+            ErrorCategory::Validation => "validation",
+            ErrorCategory::Configuration => "configuration",
+            ErrorCategory::Connection => "network",
+            ErrorCategory::Recording
+            | ErrorCategory::Adapter
+            | ErrorCategory::Health
+            | ErrorCategory::Serialization
+            | ErrorCategory::Timeout
+            | ErrorCategory::Capacity => "internal",
+        }
+    }
+
+    /// Prefix a message with this category's tag
+    fn tagged(self, message: impl std::fmt::Display) -> String {
+        format!("{} {}", self.tag(), message)
+    }
+
+    /// Find the category of an error built by one of the helpers
+    ///
+    /// The first tag wins, so an error keeps its category when wrapped with
+    /// `MetricsErrorExt` context.
+    fn of(error: &TylError) -> Option<ErrorCategory> {
+        let text = error.to_string();
+        Self::ALL
+            .into_iter()
+            .filter_map(|category| text.find(category.tag()).map(|index| (index, category)))
+            .min_by_key(|(index, _)| *index)
+            .map(|(_, category)| category)
+    }
+}
 
 /// Create a metrics validation error
 ///
@@ -24,7 +120,10 @@ use super::*;
 /// let error = metrics_error("metric_name", "Names cannot contain spaces");
 /// ```
 pub fn metrics_error(field: impl Into<String>, message: impl Into<String>) -> TylError {
-    TylError::validation(field.into(), message.into())
+    TylError::validation(
+        field.into(),
+        ErrorCategory::Validation.tagged(message.into()),
+    )
 }
 
 /// Create a metrics configuration error
@@ -45,11 +144,11 @@ pub fn metrics_error(field: impl Into<String>, message: impl Into<String>) -> Ty
 /// let error = metrics_config_error("prometheus.port", "Port must be between 1024 and 65535");
 /// ```
 pub fn metrics_config_error(config_key: impl Into<String>, message: impl Into<String>) -> TylError {
-    TylError::configuration(format!(
+    TylError::configuration(ErrorCategory::Configuration.tagged(format!(
         "Metrics config error for {}: {}",
         config_key.into(),
         message.into()
-    ))
+    )))
 }
 
 /// Create a metrics connection error
@@ -73,11 +172,11 @@ pub fn metrics_connection_error(
     endpoint: impl Into<String>,
     message: impl Into<String>,
 ) -> TylError {
-    TylError::network(format!(
+    TylError::network(ErrorCategory::Connection.tagged(format!(
         "Metrics connection error to {}: {}",
         endpoint.into(),
         message.into()
-    ))
+    )))
 }
 
 /// Create a metrics recording error
@@ -101,11 +200,11 @@ pub fn metrics_recording_error(
     metric_name: impl Into<String>,
     message: impl Into<String>,
 ) -> TylError {
-    TylError::internal(format!(
+    TylError::internal(ErrorCategory::Recording.tagged(format!(
         "Metrics recording error for {}: {}",
         metric_name.into(),
         message.into()
-    ))
+    )))
 }
 
 /// Create a metrics adapter initialization error
@@ -129,11 +228,11 @@ pub fn metrics_adapter_error(
     adapter_type: impl Into<String>,
     message: impl Into<String>,
 ) -> TylError {
-    TylError::internal(format!(
+    TylError::internal(ErrorCategory::Adapter.tagged(format!(
         "Metrics adapter error for {}: {}",
         adapter_type.into(),
         message.into()
-    ))
+    )))
 }
 
 /// Create a metrics health check error
@@ -157,11 +256,11 @@ pub fn metrics_health_error(
     adapter_type: impl Into<String>,
     message: impl Into<String>,
 ) -> TylError {
-    TylError::internal(format!(
+    TylError::internal(ErrorCategory::Health.tagged(format!(
         "Metrics health check error for {}: {}",
         adapter_type.into(),
         message.into()
-    ))
+    )))
 }
 
 /// Create a metrics serialization error
@@ -185,11 +284,11 @@ pub fn metrics_serialization_error(
     format: impl Into<String>,
     message: impl Into<String>,
 ) -> TylError {
-    TylError::internal(format!(
+    TylError::internal(ErrorCategory::Serialization.tagged(format!(
         "Metrics serialization error for {}: {}",
         format.into(),
         message.into()
-    ))
+    )))
 }
 
 /// Create a metrics timeout error
@@ -210,11 +309,171 @@ pub fn metrics_serialization_error(
 /// let error = metrics_timeout_error("record_batch", 5);
 /// ```
 pub fn metrics_timeout_error(operation: impl Into<String>, timeout_secs: u64) -> TylError {
-    TylError::internal(format!(
+    TylError::internal(ErrorCategory::Timeout.tagged(format!(
         "Metrics timeout error for {} after {}s",
         operation.into(),
         timeout_secs
-    ))
+    )))
+}
+
+/// Create a metrics timeout error for a timeout given as a `Duration`
+///
+/// Like `metrics_timeout_error`, but reports the timeout in milliseconds so
+/// sub-second timeouts don't read as "after 0s".
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use tyl_metrics_port::metrics_timeout_error_after;
+///
+/// let error = metrics_timeout_error_after("record", Duration::from_millis(10));
+/// assert!(error.to_string().contains("after 10ms"));
+/// ```
+pub fn metrics_timeout_error_after(operation: impl Into<String>, timeout: Duration) -> TylError {
+    TylError::internal(ErrorCategory::Timeout.tagged(format!(
+        "Metrics timeout error for {} after {}ms",
+        operation.into(),
+        timeout.as_millis()
+    )))
+}
+
+/// Create a metrics timeout error carrying the underlying failure's message
+///
+/// For timeouts reported by something else (an I/O error, an HTTP status),
+/// where the timeout that expired isn't known, so no duration is stated.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{is_retryable, metrics_timeout_error_with_message};
+///
+/// let error = metrics_timeout_error_with_message("io_operation", "connection timed out");
+/// assert!(error.to_string().contains("connection timed out"));
+/// assert!(is_retryable(&error));
+/// ```
+pub fn metrics_timeout_error_with_message(
+    operation: impl Into<String>,
+    message: impl Into<String>,
+) -> TylError {
+    TylError::internal(ErrorCategory::Timeout.tagged(format!(
+        "Metrics timeout error for {}: {}",
+        operation.into(),
+        message.into()
+    )))
+}
+
+/// Create a metrics capacity error
+///
+/// Used when a record is rejected because a bounded store or queue is full.
+/// Unlike `metrics_recording_error` this is not retryable: retrying at once
+/// hits the same limit.
+///
+/// # Arguments
+/// * `metric_name` - The metric that was rejected
+/// * `message` - Description of the limit that was reached
+///
+/// # Returns
+/// * `TylError` - Structured error with capacity context
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{is_retryable, metrics_capacity_error};
+///
+/// let error = metrics_capacity_error("http_requests_total", "Metric storage is full");
+/// assert!(!is_retryable(&error));
+/// ```
+pub fn metrics_capacity_error(
+    metric_name: impl Into<String>,
+    message: impl Into<String>,
+) -> TylError {
+    TylError::internal(ErrorCategory::Capacity.tagged(format!(
+        "Metrics capacity error for {}: {}",
+        metric_name.into(),
+        message.into()
+    )))
+}
+
+/// Get the stable error code of an error built by the metrics helpers
+///
+/// Lets callers branch on a code instead of parsing messages:
+///
+/// | Helper | Code |
+/// |--------|------|
+/// | `metrics_error` | `METRICS_VALIDATION_FAILED` |
+/// | `metrics_config_error` | `METRICS_CONFIG_INVALID` |
+/// | `metrics_connection_error` | `METRICS_CONNECTION_FAILED` |
+/// | `metrics_recording_error` | `METRICS_RECORDING_FAILED` |
+/// | `metrics_adapter_error` | `METRICS_ADAPTER_FAILED` |
+/// | `metrics_health_error` | `METRICS_HEALTH_CHECK_FAILED` |
+/// | `metrics_serialization_error` | `METRICS_SERIALIZATION_FAILED` |
+/// | `metrics_timeout_error`, `metrics_timeout_error_after`, `metrics_timeout_error_with_message` | `METRICS_TIMEOUT` |
+/// | `metrics_capacity_error` | `METRICS_CAPACITY_EXCEEDED` |
+///
+/// Returns `None` for errors not built by these helpers.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{metrics_error_code, metrics_timeout_error, TylError};
+///
+/// assert_eq!(metrics_error_code(&metrics_timeout_error("record", 5)), Some("METRICS_TIMEOUT"));
+/// assert_eq!(metrics_error_code(&TylError::internal("other")), None);
+/// ```
+pub fn metrics_error_code(error: &TylError) -> Option<&'static str> {
+    ErrorCategory::of(error).map(ErrorCategory::code)
+}
+
+/// Get the TYL error category an error was built under by the metrics helpers
+///
+/// Returns `"validation"` (`metrics_error`), `"configuration"`
+/// (`metrics_config_error`), `"network"` (`metrics_connection_error`), or
+/// `"internal"` (every other helper), read from the helper's category tag
+/// rather than the message text. Errors wrapped with `MetricsErrorExt` keep
+/// the category of the original error. Returns `"unknown"` for errors not
+/// built by these helpers.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{metrics_connection_error, metrics_error_category, TylError};
+///
+/// let error = metrics_connection_error("localhost:9090", "Connection refused");
+/// assert_eq!(metrics_error_category(&error), "network");
+/// assert_eq!(metrics_error_category(&TylError::internal("other")), "unknown");
+/// ```
+pub fn metrics_error_category(error: &TylError) -> &'static str {
+    ErrorCategory::of(error).map_or("unknown", ErrorCategory::tyl_category)
+}
+
+/// Whether an operation that failed with this error is worth retrying
+///
+/// Classification uses the category tag attached by the helpers in this
+/// module, so it is reliable for errors built with them:
+///
+/// | Helper | Retryable |
+/// |--------|-----------|
+/// | `metrics_connection_error` | yes |
+/// | `metrics_timeout_error`, `metrics_timeout_error_after` | yes |
+/// | `metrics_recording_error` | yes |
+/// | `metrics_error` (validation) | no |
+/// | `metrics_config_error` | no |
+/// | `metrics_adapter_error` | no |
+/// | `metrics_health_error` | no |
+/// | `metrics_serialization_error` | no |
+/// | `metrics_capacity_error` | no |
+///
+/// Errors not built by these helpers are treated as permanent. Wrapping with
+/// `MetricsErrorExt` keeps the original classification.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{is_retryable, metrics_connection_error, metrics_error};
+///
+/// assert!(is_retryable(&metrics_connection_error("localhost:9090", "Connection refused")));
+/// assert!(!is_retryable(&metrics_error("metric_name", "Invalid characters")));
+/// ```
+pub fn is_retryable(error: &TylError) -> bool {
+    matches!(
+        ErrorCategory::of(error),
+        Some(ErrorCategory::Connection | ErrorCategory::Timeout | ErrorCategory::Recording)
+    )
 }
 
 /// Helper trait for adding metrics context to existing errors
@@ -258,11 +517,46 @@ pub fn from_io_error(error: std::io::Error) -> TylError {
         std::io::ErrorKind::ConnectionRefused => {
             metrics_connection_error("unknown", error.to_string())
         }
-        std::io::ErrorKind::TimedOut => metrics_timeout_error("io_operation", 0),
-        _ => TylError::internal(format!("Metrics IO error: {error}")),
+        std::io::ErrorKind::TimedOut => {
+            metrics_timeout_error_with_message("io_operation", error.to_string())
+        }
+        _ => {
+            TylError::internal(ErrorCategory::Adapter.tagged(format!("Metrics IO error: {error}")))
+        }
     }
 }
 
+/// Convert an HTTP response status from a push endpoint into a metrics error
+///
+/// Mapping:
+/// - `408` and `429` become timeout errors (retryable: the endpoint is busy)
+/// - other `4xx` become configuration errors (the request itself is wrong)
+/// - `5xx` become connection errors (retryable: the endpoint is failing)
+/// - anything else becomes an adapter error for an unexpected status
+///
+/// The response body, if any, is appended to the message.
+pub fn from_http_status(url: &str, status: u16, body: Option<&str>) -> TylError {
+    let mut message = format!("HTTP {status}");
+    if let Some(body) = body.filter(|body| !body.is_empty()) {
+        message.push_str(&format!(": {body}"));
+    }
+
+    match status {
+        408 | 429 => metrics_timeout_error_with_message(url, message),
+        400..=499 => metrics_config_error(url, message),
+        500..=599 => metrics_connection_error(url, message),
+        _ => metrics_adapter_error(url, format!("Unexpected {message}")),
+    }
+}
+
+/// Convert an HTTP transport failure (DNS, TLS, connection reset, ...) into a metrics error
+///
+/// Transport failures never reached the endpoint, so they are reported as
+/// retryable connection errors.
+pub fn from_transport_error(url: &str, message: &str) -> TylError {
+    metrics_connection_error(url, message)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,6 +641,158 @@ mod tests {
     fn test_timeout_io_error_conversion() {
         let io_error = std::io::Error::new(std::io::ErrorKind::TimedOut, "Operation timed out");
         let tyl_error = from_io_error(io_error);
-        assert!(tyl_error.to_string().contains("timeout"));
+        assert_eq!(metrics_error_code(&tyl_error), Some("METRICS_TIMEOUT"));
+        assert!(tyl_error.to_string().contains("Operation timed out"));
+        assert!(!tyl_error.to_string().contains("after"));
+    }
+
+    #[test]
+    fn test_is_retryable_per_helper() {
+        assert!(is_retryable(&metrics_connection_error(
+            "localhost",
+            "refused"
+        )));
+        assert!(is_retryable(&metrics_timeout_error("record", 5)));
+        assert!(is_retryable(&metrics_recording_error("cpu_usage", "busy")));
+
+        assert!(!is_retryable(&metrics_error("metric_name", "invalid")));
+        assert!(!is_retryable(&metrics_config_error("port", "out of range")));
+        assert!(!is_retryable(&metrics_adapter_error("prometheus", "init")));
+        assert!(!is_retryable(&metrics_health_error("otel", "down")));
+        assert!(!is_retryable(&metrics_serialization_error("json", "bad")));
+        assert!(!is_retryable(&metrics_capacity_error("cpu_usage", "full")));
+    }
+
+    #[test]
+    fn test_is_retryable_edge_cases() {
+        // Untagged errors are permanent
+        assert!(!is_retryable(&TylError::network("connection reset")));
+
+        // Context wrapping keeps the original category
+        let wrapped = metrics_connection_error("localhost", "refused")
+            .with_metrics_context("flush")
+            .with_adapter_type("prometheus");
+        assert!(is_retryable(&wrapped));
+
+        // The outermost helper decides when one error embeds another
+        let nested = metrics_error("request", metrics_timeout_error("record", 1).to_string());
+        assert!(!is_retryable(&nested));
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out");
+        assert!(is_retryable(&from_io_error(io_error)));
+    }
+
+    #[test]
+    fn test_metrics_error_codes() {
+        let cases = [
+            (metrics_error("name", "bad"), "METRICS_VALIDATION_FAILED"),
+            (
+                metrics_config_error("port", "bad"),
+                "METRICS_CONFIG_INVALID",
+            ),
+            (
+                metrics_connection_error("host", "bad"),
+                "METRICS_CONNECTION_FAILED",
+            ),
+            (
+                metrics_recording_error("cpu", "bad"),
+                "METRICS_RECORDING_FAILED",
+            ),
+            (
+                metrics_adapter_error("prometheus", "bad"),
+                "METRICS_ADAPTER_FAILED",
+            ),
+            (
+                metrics_health_error("otel", "bad"),
+                "METRICS_HEALTH_CHECK_FAILED",
+            ),
+            (
+                metrics_serialization_error("json", "bad"),
+                "METRICS_SERIALIZATION_FAILED",
+            ),
+            (metrics_timeout_error("record", 1), "METRICS_TIMEOUT"),
+            (
+                metrics_capacity_error("cpu", "full"),
+                "METRICS_CAPACITY_EXCEEDED",
+            ),
+        ];
+
+        for (error, code) in cases {
+            assert_eq!(metrics_error_code(&error), Some(code));
+        }
+        assert_eq!(metrics_error_code(&TylError::network("untagged")), None);
+    }
+
+    #[test]
+    fn test_metrics_error_category() {
+        let cases = [
+            (metrics_error("name", "bad"), "validation"),
+            (metrics_config_error("key", "bad"), "configuration"),
+            (metrics_connection_error("host", "refused"), "network"),
+            (metrics_recording_error("name", "full"), "internal"),
+            (metrics_timeout_error("record", 1), "internal"),
+            (metrics_capacity_error("name", "full"), "internal"),
+            (
+                from_io_error(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "denied",
+                )),
+                "internal",
+            ),
+        ];
+
+        for (error, category) in cases {
+            assert_eq!(metrics_error_category(&error), category);
+        }
+        let wrapped = metrics_error("name", "bad").with_metric_name("requests");
+        assert_eq!(metrics_error_category(&wrapped), "validation");
+        assert_eq!(
+            metrics_error_category(&TylError::validation("name", "untagged")),
+            "unknown"
+        );
+    }
+
+    #[test]
+    fn test_http_status_conversion() {
+        let url = "http://pushgateway:9091";
+
+        let server_error = from_http_status(url, 500, Some("internal error"));
+        assert_eq!(
+            metrics_error_code(&server_error),
+            Some("METRICS_CONNECTION_FAILED")
+        );
+        assert!(is_retryable(&server_error));
+        assert!(server_error
+            .to_string()
+            .contains("HTTP 500: internal error"));
+
+        let throttled = from_http_status(url, 429, None);
+        assert_eq!(metrics_error_code(&throttled), Some("METRICS_TIMEOUT"));
+        assert!(is_retryable(&throttled));
+        assert!(throttled.to_string().contains("HTTP 429"));
+
+        let bad_request = from_http_status(url, 400, Some(""));
+        assert_eq!(
+            metrics_error_code(&bad_request),
+            Some("METRICS_CONFIG_INVALID")
+        );
+        assert!(!is_retryable(&bad_request));
+        assert!(bad_request.to_string().contains("HTTP 400"));
+
+        let redirect = from_http_status(url, 302, None);
+        assert_eq!(
+            metrics_error_code(&redirect),
+            Some("METRICS_ADAPTER_FAILED")
+        );
+    }
+
+    #[test]
+    fn test_transport_error_conversion() {
+        let error = from_transport_error("http://collector:4318", "connection reset");
+        assert_eq!(
+            metrics_error_code(&error),
+            Some("METRICS_CONNECTION_FAILED")
+        );
+        assert!(error.to_string().contains("connection reset"));
     }
 }
@@ -0,0 +1,1411 @@
+//! Snapshot exporters for the metrics port
+//!
+//! This module turns `MetricSnapshot`s into wire formats that downstream
+//! pipelines can consume. Exporters are pure functions over snapshots, so
+//! they work with any adapter that implements `get_snapshot`. Parsers for
+//! the same formats turn scraped output back into snapshots.
+
+use super::*;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+/// Export snapshots as JSON Lines
+///
+/// Each snapshot is serialized as one compact JSON object terminated by `\n`,
+/// which is the framing expected by log-based metrics pipelines. Labels are
+/// serialized as an object sorted by key so output is deterministic, and
+/// histogram values keep their `sum`/`count`/`buckets` fields in a single
+/// object so every line parses back into a `MetricSnapshot`.
+///
+/// # Arguments
+/// * `snapshots` - The snapshots to export
+///
+/// # Returns
+/// * `Result<String>` - One JSON object per line, or a serialization error
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{export_jsonl, MetricRequest, MetricSnapshot};
+///
+/// let request = MetricRequest::counter("http_requests_total", 1.0).with_label("method", "GET");
+/// let output = export_jsonl(&[MetricSnapshot::from(&request)]).unwrap();
+/// assert_eq!(output.lines().count(), 1);
+/// assert!(output.ends_with('\n'));
+/// ```
+pub fn export_jsonl(snapshots: &[MetricSnapshot]) -> Result<String> {
+    let mut output = String::new();
+
+    for snapshot in snapshots {
+        let line = serde_json::to_string(snapshot).map_err(from_serde_json_error)?;
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Load snapshots saved with `MockMetricsAdapter::save_snapshots`
+///
+/// Reads a JSON array of snapshots, e.g. a metrics dump attached to a bug
+/// report, so it can be replayed or compared with `diff_snapshots`.
+///
+/// # Arguments
+/// * `path` - The file to read
+///
+/// # Returns
+/// * `Result<Vec<MetricSnapshot>>` - The saved snapshots in their original
+///   order, or an I/O or serialization error
+///
+/// Not available on `wasm32`, where Tokio has no filesystem support.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn load_snapshots(path: &Path) -> Result<Vec<MetricSnapshot>> {
+    let contents = tokio::fs::read(path).await.map_err(from_io_error)?;
+    serde_json::from_slice(&contents).map_err(from_serde_json_error)
+}
+
+/// Write snapshots to `path` as a pretty-printed JSON array
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn save_snapshots(path: &Path, snapshots: &[MetricSnapshot]) -> Result<()> {
+    let contents = serde_json::to_vec_pretty(snapshots).map_err(from_serde_json_error)?;
+    tokio::fs::write(path, contents)
+        .await
+        .map_err(from_io_error)
+}
+
+/// Export snapshots in the Prometheus text exposition format
+///
+/// Snapshots are grouped into metric families by name, in order of first
+/// appearance, each preceded by `# HELP` (when any snapshot has help text)
+/// and `# TYPE` lines. Labels are sorted by key and escaped, and every sample
+/// carries the snapshot timestamp converted to milliseconds, so output is
+/// byte-stable for fixed inputs.
+///
+/// Counters and gauges map directly. `Histogram` values expand into
+/// `_bucket`, `_sum`, and `_count` samples; histogram or timer snapshots that
+/// hold a single observation are exported as `untyped`. Text format 0.0.4
+/// has no info type, so info metrics are exported as gauges under their full
+/// name (e.g. `build_info`); use `export_openmetrics_text` for the
+/// OpenMetrics `info` type.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{export_prometheus_text, MetricRequest, MetricSnapshot};
+///
+/// let request = MetricRequest::counter("http_requests_total", 3.0).with_label("method", "GET");
+/// let snapshot = MetricSnapshot::from(&request).with_timestamp(1_700_000_000_000_000_000);
+///
+/// assert_eq!(
+///     export_prometheus_text(&[snapshot]),
+///     "# TYPE http_requests_total counter\n\
+///      http_requests_total{method=\"GET\"} 3 1700000000000\n"
+/// );
+/// ```
+pub fn export_prometheus_text(snapshots: &[MetricSnapshot]) -> String {
+    render_exposition(snapshots, TextFormat::Prometheus, true)
+}
+
+/// Export snapshots in the OpenMetrics text format
+///
+/// Served under `Content-Type: application/openmetrics-text; version=1.0.0;
+/// charset=utf-8`. Families are grouped and ordered as in
+/// `export_prometheus_text`, with the differences OpenMetrics requires:
+///
+/// - Counter families drop the `_total` suffix, which every counter sample
+///   carries: `http_requests_total` is `# TYPE http_requests counter` with
+///   an `http_requests_total` sample.
+/// - Info metrics use the `info` type the same way: `build_info` is
+///   `# TYPE build info` with a `build_info` sample.
+/// - Single-observation histograms and timers are `unknown` rather than
+///   `untyped`.
+/// - Timestamps are in seconds with millisecond precision.
+/// - Output ends with `# EOF`.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{export_openmetrics_text, MetricRequest, MetricSnapshot};
+///
+/// let request = MetricRequest::info("build_info", [("version", "1.4.2")]);
+/// let snapshot = MetricSnapshot::from(&request).with_timestamp(1_700_000_000_250_000_000);
+///
+/// let text = export_openmetrics_text(&[snapshot]);
+/// assert!(text.starts_with(
+///     "# TYPE build info\n\
+///      build_info{version=\"1.4.2\"} 1 1700000000.250\n"
+/// ));
+/// assert!(text.ends_with("# EOF\n"));
+/// ```
+pub fn export_openmetrics_text(snapshots: &[MetricSnapshot]) -> String {
+    let mut output = render_exposition(snapshots, TextFormat::OpenMetrics, true);
+    output.push_str("# EOF\n");
+    output
+}
+
+/// Text exposition flavors sharing one renderer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextFormat {
+    /// Prometheus text format 0.0.4
+    Prometheus,
+    /// OpenMetrics 1.0 text format
+    OpenMetrics,
+}
+
+/// Render snapshot families in a text exposition format, without a trailer
+///
+/// With `timestamps` off, samples carry no timestamp field.
+fn render_exposition(snapshots: &[MetricSnapshot], format: TextFormat, timestamps: bool) -> String {
+    // Group into families, keeping first-seen order
+    let mut families: Vec<(&str, Vec<&MetricSnapshot>)> = Vec::new();
+    for snapshot in snapshots {
+        match families.iter_mut().find(|(name, _)| *name == snapshot.name) {
+            Some((_, members)) => members.push(snapshot),
+            None => families.push((&snapshot.name, vec![snapshot])),
+        }
+    }
+
+    let mut output = String::new();
+    for (name, members) in families {
+        // OpenMetrics names counter and info families without the suffix
+        // their samples carry
+        let suffix = match (format, members[0].metric_type) {
+            (TextFormat::OpenMetrics, MetricType::Counter) => Some("_total"),
+            (TextFormat::OpenMetrics, MetricType::Info) => Some("_info"),
+            _ => None,
+        };
+        let (family, sample_name) = match suffix {
+            Some(suffix) => {
+                let family = name.strip_suffix(suffix).unwrap_or(name);
+                (family, format!("{family}{suffix}"))
+            }
+            None => (name, name.to_string()),
+        };
+
+        if let Some(help) = members.iter().find_map(|m| m.help.as_deref()) {
+            let help = match format {
+                TextFormat::Prometheus => escape_help(help),
+                TextFormat::OpenMetrics => escape_label_value(help),
+            };
+            output.push_str(&format!("# HELP {} {}\n", family, help));
+        }
+        output.push_str(&format!(
+            "# TYPE {} {}\n",
+            family,
+            exposition_type(members[0], format)
+        ));
+
+        for snapshot in members {
+            let timestamp = if timestamps {
+                format!(
+                    " {}",
+                    format_exposition_timestamp(snapshot.timestamp, format)
+                )
+            } else {
+                String::new()
+            };
+            match &snapshot.value {
+                MetricValue::Single(value) => {
+                    output.push_str(&format!(
+                        "{}{} {}{}\n",
+                        sample_name,
+                        prometheus_labels(&snapshot.labels, None),
+                        format_sample_value(*value),
+                        timestamp
+                    ));
+                }
+                MetricValue::Histogram {
+                    sum,
+                    count,
+                    buckets,
+                } => {
+                    for bucket in buckets {
+                        let le = format_sample_value(bucket.upper_bound);
+                        output.push_str(&format!(
+                            "{}_bucket{} {}{}\n",
+                            name,
+                            prometheus_labels(&snapshot.labels, Some(&le)),
+                            bucket.count,
+                            timestamp
+                        ));
+                    }
+                    let labels = prometheus_labels(&snapshot.labels, None);
+                    output.push_str(&format!(
+                        "{}_sum{} {}{}\n",
+                        name,
+                        labels,
+                        format_sample_value(*sum),
+                        timestamp
+                    ));
+                    output.push_str(&format!(
+                        "{}_count{} {}{}\n",
+                        name, labels, count, timestamp
+                    ));
+                }
+            }
+        }
+    }
+
+    output
+}
+
+/// Build a Pushgateway push for snapshots under a grouping key
+///
+/// Returns the URL path (`/metrics/job/<job>/<label>/<value>...`) to `PUT`
+/// or `POST` to, and the body in the Prometheus text format. Unlike
+/// `export_prometheus_text`, samples carry no timestamps, since the
+/// Pushgateway rejects pushes that include them.
+/// `grouping` must contain a `job` entry, which goes first in the path; the
+/// other pairs follow in order. Keys are validated with
+/// `validate_label_key` and may not repeat.
+///
+/// Values are percent-encoded. Values containing `/`, which servers decode
+/// before routing, and empty values use the Pushgateway's base64 form
+/// instead (`<label>@base64/<base64url value>`).
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{export_pushgateway, MetricRequest, MetricSnapshot};
+///
+/// let snapshots = vec![MetricSnapshot::from(&MetricRequest::gauge("backup_last_success", 1.0))];
+/// let grouping = vec![
+///     ("job".to_string(), "backup".to_string()),
+///     ("instance".to_string(), "db 1".to_string()),
+/// ];
+///
+/// let (path, body) = export_pushgateway(&snapshots, &grouping).unwrap();
+/// assert_eq!(path, "/metrics/job/backup/instance/db%201");
+/// assert!(body.contains("backup_last_success 1"));
+/// ```
+pub fn export_pushgateway(
+    snapshots: &[MetricSnapshot],
+    grouping: &[(String, String)],
+) -> Result<(String, String)> {
+    let job = grouping
+        .iter()
+        .find(|(key, _)| key == "job")
+        .ok_or_else(|| metrics_error("grouping", "Pushgateway grouping key needs a job"))?;
+
+    let mut seen = std::collections::HashSet::new();
+    for (key, _) in grouping {
+        validate_label_key(key)?;
+        if !seen.insert(key.as_str()) {
+            return Err(metrics_error(
+                "grouping",
+                format!("Grouping key {key} appears more than once"),
+            ));
+        }
+    }
+
+    let mut path = String::from("/metrics");
+    let others = grouping.iter().filter(|(key, _)| key != "job");
+    for (key, value) in std::iter::once(job).chain(others) {
+        if value.is_empty() || value.contains('/') {
+            path.push_str(&format!("/{}@base64/{}", key, base64_url(value.as_bytes())));
+        } else {
+            path.push_str(&format!("/{}/{}", key, percent_encode(value)));
+        }
+    }
+
+    Ok((
+        path,
+        render_exposition(snapshots, TextFormat::Prometheus, false),
+    ))
+}
+
+/// Parse the Prometheus text exposition format back into snapshots
+///
+/// This is the inverse of `export_prometheus_text` and
+/// `export_openmetrics_text`, meant for contract tests against a scraped
+/// `/metrics` endpoint. `# TYPE` and `# HELP` lines set the family type and
+/// help text; other comments (including `# EOF`) and blank lines are skipped.
+///
+/// Histogram families are reassembled from their `_bucket`, `_sum`, and
+/// `_count` samples into one `MetricValue::Histogram` per label set. Every
+/// other sample becomes its own snapshot: counters as cumulative `Counter`s,
+/// `_info` samples of an OpenMetrics `info` family as `Info`, everything else
+/// (gauges, untyped, unknown, summaries) as `Gauge`. Samples keep their full
+/// name, so OpenMetrics `_total` and `_info` suffixes are preserved. Integer
+/// sample timestamps are read as milliseconds and decimal ones as
+/// OpenMetrics seconds; samples without one are stamped with the current
+/// time.
+///
+/// # Errors
+/// Returns a serialization error naming the line number for malformed
+/// samples, unknown `# TYPE` values, and buckets without an `le` label.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{parse_prometheus_text, MetricType, MetricValue};
+///
+/// let snapshots = parse_prometheus_text(
+///     "# TYPE http_requests_total counter\n\
+///      http_requests_total{method=\"GET\"} 3 1700000000000\n",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(snapshots[0].metric_type, MetricType::Counter);
+/// assert_eq!(snapshots[0].value, MetricValue::Single(3.0));
+/// assert_eq!(snapshots[0].labels["method"], "GET");
+/// ```
+pub fn parse_prometheus_text(input: &str) -> Result<Vec<MetricSnapshot>> {
+    let mut types: HashMap<String, MetricType> = HashMap::new();
+    let mut helps: HashMap<String, String> = HashMap::new();
+    let mut snapshots: Vec<MetricSnapshot> = Vec::new();
+    // Histogram series (family name, sorted labels) -> index into `snapshots`
+    let mut histograms: HashMap<(String, Vec<(String, String)>), usize> = HashMap::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            let comment = comment.trim_start();
+            if let Some(rest) = comment.strip_prefix("HELP ") {
+                let (name, help) = rest.split_once(' ').unwrap_or((rest, ""));
+                helps.insert(name.to_string(), unescape_help(help));
+            } else if let Some(rest) = comment.strip_prefix("TYPE ") {
+                let (name, kind) = rest
+                    .split_once(' ')
+                    .ok_or_else(|| malformed(line_number, "TYPE line without a type"))?;
+                let metric_type = match kind.trim() {
+                    "counter" => MetricType::Counter,
+                    "histogram" => MetricType::Histogram,
+                    "info" => MetricType::Info,
+                    "gauge" | "untyped" | "unknown" | "summary" => MetricType::Gauge,
+                    other => {
+                        return Err(malformed(
+                            line_number,
+                            format!("unknown metric type '{other}'"),
+                        ))
+                    }
+                };
+                types.insert(name.to_string(), metric_type);
+            }
+            continue;
+        }
+
+        let sample = parse_sample(line).map_err(|message| malformed(line_number, message))?;
+
+        let histogram_part = ["_bucket", "_sum", "_count"].iter().find_map(|suffix| {
+            let family = sample.name.strip_suffix(suffix)?;
+            (types.get(family) == Some(&MetricType::Histogram)).then_some((family, *suffix))
+        });
+
+        let Some((family, suffix)) = histogram_part else {
+            // OpenMetrics counter and info samples carry a suffix their family lacks
+            let suffixed_family = |suffix: &str| {
+                sample
+                    .name
+                    .strip_suffix(suffix)
+                    .and_then(|family| types.get(family))
+                    .copied()
+            };
+            let metric_type = match types.get(&sample.name) {
+                Some(MetricType::Counter) => MetricType::Counter,
+                _ if suffixed_family("_total") == Some(MetricType::Counter) => MetricType::Counter,
+                _ if suffixed_family("_info") == Some(MetricType::Info) => MetricType::Info,
+                _ => MetricType::Gauge,
+            };
+            let mut snapshot = MetricSnapshot::new(
+                sample.name,
+                metric_type,
+                MetricValue::Single(sample.value),
+                sample.labels,
+            );
+            if let Some(timestamp) = sample.timestamp {
+                snapshot = snapshot.with_timestamp(timestamp);
+            }
+            if metric_type == MetricType::Counter {
+                // Prometheus counters are always cumulative
+                snapshot.temporality = Some(AggregationTemporality::Cumulative);
+            }
+            snapshots.push(snapshot);
+            continue;
+        };
+
+        let mut labels = sample.labels;
+        let le = labels.remove("le");
+        let mut key: Vec<(String, String)> = labels.clone().into_iter().collect();
+        key.sort();
+
+        let position = *histograms
+            .entry((family.to_string(), key))
+            .or_insert_with(|| {
+                snapshots.push(MetricSnapshot::new(
+                    family.to_string(),
+                    MetricType::Histogram,
+                    MetricValue::Histogram {
+                        sum: 0.0,
+                        count: 0,
+                        buckets: Vec::new(),
+                    },
+                    labels,
+                ));
+                snapshots.len() - 1
+            });
+
+        let snapshot = &mut snapshots[position];
+        if let Some(timestamp) = sample.timestamp {
+            snapshot.timestamp = timestamp;
+        }
+
+        // Histogram series always hold histogram values
+        if let MetricValue::Histogram {
+            sum,
+            count,
+            buckets,
+        } = &mut snapshot.value
+        {
+            match suffix {
+                "_bucket" => {
+                    let le =
+                        le.ok_or_else(|| malformed(line_number, "bucket without an 'le' label"))?;
+                    let upper_bound = parse_sample_value(&le)
+                        .map_err(|message| malformed(line_number, message))?;
+                    buckets.push(HistogramBucket {
+                        upper_bound,
+                        count: sample.value as u64,
+                    });
+                }
+                "_sum" => *sum = sample.value,
+                _ => *count = sample.value as u64,
+            }
+        }
+    }
+
+    for snapshot in &mut snapshots {
+        let suffix = match snapshot.metric_type {
+            MetricType::Counter => "_total",
+            MetricType::Info => "_info",
+            _ => "",
+        };
+        let family = snapshot.name.strip_suffix(suffix).unwrap_or(&snapshot.name);
+        snapshot.help = helps
+            .get(&snapshot.name)
+            .or_else(|| helps.get(family))
+            .cloned();
+    }
+
+    Ok(snapshots)
+}
+
+/// Format snapshots as an aligned, human-readable table
+///
+/// Produces one row per series (name, type, and labels), grouped by metric
+/// name in first-seen order, with the series' records aggregated:
+///
+/// - Counters show the sum of their increments
+/// - Gauges show the value with the highest timestamp (last stored on ties)
+/// - Histograms and timers show `count=<n> sum=<s>` over all observations
+///
+/// Labels are shown in logfmt form sorted by key, or `-` if there are none.
+/// Empty input yields the single line `(no metrics)`. Intended for test
+/// output and CLI tools, not for machine consumption.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{format_snapshot_table, MetricRequest, MetricSnapshot};
+///
+/// let snapshots = vec![
+///     MetricSnapshot::from(&MetricRequest::counter("requests_total", 2.0)),
+///     MetricSnapshot::from(&MetricRequest::counter("requests_total", 3.0)),
+/// ];
+/// let table = format_snapshot_table(&snapshots);
+/// assert!(table.lines().nth(1).unwrap().ends_with("5"));
+/// ```
+pub fn format_snapshot_table(snapshots: &[MetricSnapshot]) -> String {
+    if snapshots.is_empty() {
+        return "(no metrics)\n".to_string();
+    }
+
+    let label_options = FormatOptions::logfmt().with_empty("-");
+    let mut names: Vec<&str> = Vec::new();
+    let mut series: Vec<TableRow> = Vec::new();
+    for snapshot in snapshots {
+        let labels = format_labels_with(&snapshot.labels, &label_options);
+        let row = series.iter_mut().find(|row| {
+            row.name == snapshot.name
+                && row.metric_type == snapshot.metric_type
+                && row.labels == labels
+        });
+        match row {
+            Some(row) => row.add(snapshot),
+            None => {
+                if !names.contains(&snapshot.name.as_str()) {
+                    names.push(&snapshot.name);
+                }
+                let mut row = TableRow {
+                    name: snapshot.name.clone(),
+                    metric_type: snapshot.metric_type,
+                    labels,
+                    total: TableValue::Empty,
+                };
+                row.add(snapshot);
+                series.push(row);
+            }
+        }
+    }
+
+    let mut rows = vec![[
+        "NAME".to_string(),
+        "TYPE".to_string(),
+        "LABELS".to_string(),
+        "VALUE".to_string(),
+    ]];
+    for name in names {
+        rows.extend(series.iter().filter(|row| row.name == name).map(|row| {
+            [
+                row.name.clone(),
+                row.metric_type.to_string(),
+                row.labels.clone(),
+                row.total.to_string(),
+            ]
+        }));
+    }
+
+    let mut widths = [0; 4];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    for row in &rows {
+        let line = format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        );
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Export timer snapshots as folded stacks for flamegraph tools
+///
+/// Timer durations are summed per distinct value of `stack_label` and
+/// emitted as `<stack> <microseconds>` lines, sorted by stack, the input
+/// format of `flamegraph.pl` and `inferno-flamegraph`. Histogram-valued
+/// timers contribute their `sum`. A label value containing `;` becomes
+/// nested frames (e.g. `checkout;db`); whitespace, which would end the stack,
+/// is replaced with `_`. Non-timer snapshots and timers without the label are
+/// ignored.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use tyl_metrics_port::{export_folded_stacks, MetricRequest, MetricSnapshot};
+///
+/// let snapshots = vec![
+///     MetricSnapshot::from(
+///         &MetricRequest::timer("span_duration", Duration::from_millis(3)).with_label("operation", "checkout;db"),
+///     ),
+///     MetricSnapshot::from(
+///         &MetricRequest::timer("span_duration", Duration::from_millis(1)).with_label("operation", "checkout"),
+///     ),
+/// ];
+///
+/// assert_eq!(
+///     export_folded_stacks(&snapshots, "operation"),
+///     "checkout 1000\ncheckout;db 3000\n"
+/// );
+/// ```
+pub fn export_folded_stacks(snapshots: &[MetricSnapshot], stack_label: &str) -> String {
+    let mut stacks: BTreeMap<String, f64> = BTreeMap::new();
+    for snapshot in snapshots {
+        if snapshot.metric_type != MetricType::Timer {
+            continue;
+        }
+        let Some(stack) = snapshot.labels.get(stack_label) else {
+            continue;
+        };
+        let seconds = match &snapshot.value {
+            MetricValue::Single(value) => *value,
+            MetricValue::Histogram { sum, .. } => *sum,
+        };
+        let stack: String = stack
+            .chars()
+            .map(|c| if c.is_whitespace() { '_' } else { c })
+            .collect();
+        *stacks.entry(stack).or_insert(0.0) += seconds;
+    }
+
+    stacks
+        .into_iter()
+        .map(|(stack, seconds)| format!("{} {}\n", stack, (seconds * 1e6).round() as u64))
+        .collect()
+}
+
+/// One series of the snapshot table
+struct TableRow {
+    name: String,
+    metric_type: MetricType,
+    labels: String,
+    total: TableValue,
+}
+
+impl TableRow {
+    /// Fold another record of the series into the row
+    fn add(&mut self, snapshot: &MetricSnapshot) {
+        let (count, sum) = match snapshot.value {
+            MetricValue::Single(value) => (1, value),
+            MetricValue::Histogram { sum, count, .. } => (count, sum),
+        };
+
+        self.total = match (self.metric_type, &self.total) {
+            (MetricType::Counter, TableValue::Scalar { value, .. }) => TableValue::Scalar {
+                value: value + sum,
+                timestamp: snapshot.timestamp,
+            },
+            (MetricType::Gauge | MetricType::Info, TableValue::Scalar { timestamp, .. })
+                if *timestamp > snapshot.timestamp =>
+            {
+                return;
+            }
+            (MetricType::Counter | MetricType::Gauge | MetricType::Info, _) => TableValue::Scalar {
+                value: sum,
+                timestamp: snapshot.timestamp,
+            },
+            (
+                _,
+                TableValue::Distribution {
+                    count: total,
+                    sum: total_sum,
+                },
+            ) => TableValue::Distribution {
+                count: total + count,
+                sum: total_sum + sum,
+            },
+            (_, _) => TableValue::Distribution { count, sum },
+        };
+    }
+}
+
+/// Aggregated value of a table row
+enum TableValue {
+    Empty,
+    Scalar { value: f64, timestamp: u64 },
+    Distribution { count: u64, sum: f64 },
+}
+
+impl std::fmt::Display for TableValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableValue::Empty => Ok(()),
+            TableValue::Scalar { value, .. } => write!(f, "{}", format_sample_value(*value)),
+            TableValue::Distribution { count, sum } => {
+                write!(f, "count={} sum={}", count, format_sample_value(*sum))
+            }
+        }
+    }
+}
+
+/// One parsed sample line
+struct Sample {
+    name: String,
+    labels: Labels,
+    value: f64,
+    /// Timestamp in Unix epoch nanoseconds
+    timestamp: Option<u64>,
+}
+
+/// Error for a malformed line of Prometheus text
+fn malformed(line_number: usize, message: impl Into<String>) -> TylError {
+    metrics_serialization_error(
+        "prometheus_text",
+        format!("line {}: {}", line_number, message.into()),
+    )
+}
+
+/// Parse `name{labels} value [timestamp_ms]`
+fn parse_sample(line: &str) -> std::result::Result<Sample, String> {
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .ok_or("sample without a value")?;
+    let name = &line[..name_end];
+    if name.is_empty() {
+        return Err("sample without a metric name".to_string());
+    }
+
+    let mut rest = &line[name_end..];
+    let mut labels = Labels::new();
+    if let Some(after_brace) = rest.strip_prefix('{') {
+        let (parsed, remaining) = parse_labels(after_brace)?;
+        labels = parsed;
+        rest = remaining;
+    }
+
+    let mut fields = rest.split_whitespace();
+    let value = parse_sample_value(fields.next().ok_or("sample without a value")?)?;
+    let timestamp = match fields.next() {
+        Some(text) => Some(parse_timestamp(text)?),
+        None => None,
+    };
+    if fields.next().is_some() {
+        return Err("unexpected trailing fields".to_string());
+    }
+
+    Ok(Sample {
+        name: name.to_string(),
+        labels,
+        value,
+        timestamp,
+    })
+}
+
+/// Parse a sample timestamp into Unix epoch nanoseconds
+///
+/// Integers are Prometheus milliseconds; decimals are OpenMetrics seconds.
+fn parse_timestamp(text: &str) -> std::result::Result<u64, String> {
+    let invalid = || format!("invalid timestamp '{text}'");
+    let nanos = match text.split_once('.') {
+        Some((seconds, fraction)) => {
+            if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            let seconds: u64 = seconds.parse().map_err(|_| invalid())?;
+            // Keep nanosecond precision, dropping any finer digits
+            let digits = &fraction[..fraction.len().min(9)];
+            let fraction_nanos = format!("{digits:0<9}")
+                .parse::<u64>()
+                .map_err(|_| invalid())?;
+            seconds
+                .checked_mul(1_000_000_000)
+                .and_then(|nanos| nanos.checked_add(fraction_nanos))
+        }
+        None => {
+            let millis: u64 = text.parse().map_err(|_| invalid())?;
+            millis.checked_mul(1_000_000)
+        }
+    };
+    nanos.ok_or_else(|| "timestamp out of range".to_string())
+}
+
+/// Parse `k="v",...}` (the opening brace already consumed), returning the rest of the line
+fn parse_labels(input: &str) -> std::result::Result<(Labels, &str), String> {
+    let mut labels = Labels::new();
+    let mut rest = input.trim_start();
+
+    loop {
+        if let Some(after) = rest.strip_prefix('}') {
+            return Ok((labels, after));
+        }
+
+        let (key, after_key) = rest.split_once('=').ok_or("label without a value")?;
+        let after_quote = after_key
+            .trim_start()
+            .strip_prefix('"')
+            .ok_or("label value must be quoted")?;
+
+        let mut value = String::new();
+        let mut chars = after_quote.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((i, '"')) => break i,
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, c)) => value.push(c),
+                    None => return Err("unterminated label value".to_string()),
+                },
+                Some((_, c)) => value.push(c),
+                None => return Err("unterminated label value".to_string()),
+            }
+        };
+        labels.insert(key.trim().to_string(), value);
+
+        rest = after_quote[end + 1..].trim_start();
+        if let Some(after_comma) = rest.strip_prefix(',') {
+            rest = after_comma.trim_start();
+        } else if !rest.starts_with('}') {
+            return Err("expected ',' or '}' after label".to_string());
+        }
+    }
+}
+
+/// Parse a sample value, accepting the Prometheus spellings of infinities and NaN
+fn parse_sample_value(text: &str) -> std::result::Result<f64, String> {
+    match text {
+        "+Inf" | "Inf" => Ok(f64::INFINITY),
+        "-Inf" => Ok(f64::NEG_INFINITY),
+        "NaN" => Ok(f64::NAN),
+        _ => text
+            .parse()
+            .map_err(|_| format!("invalid sample value '{text}'")),
+    }
+}
+
+/// Undo `escape_help`, plus the `\"` escape OpenMetrics help text may use
+fn unescape_help(help: &str) -> String {
+    let mut output = String::with_capacity(help.len());
+    let mut chars = help.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                output.push('\n');
+                chars.next();
+            }
+            ('\\', Some(escaped @ ('\\' | '"'))) => {
+                output.push(escaped);
+                chars.next();
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+/// Exposition family type for a snapshot
+fn exposition_type(snapshot: &MetricSnapshot, format: TextFormat) -> &'static str {
+    match (snapshot.metric_type, &snapshot.value, format) {
+        (MetricType::Counter, _, _) => "counter",
+        (MetricType::Gauge, _, _) => "gauge",
+        (MetricType::Info, _, TextFormat::Prometheus) => "gauge",
+        (MetricType::Info, _, TextFormat::OpenMetrics) => "info",
+        (_, MetricValue::Histogram { .. }, _) => "histogram",
+        (_, MetricValue::Single(_), TextFormat::Prometheus) => "untyped",
+        (_, MetricValue::Single(_), TextFormat::OpenMetrics) => "unknown",
+    }
+}
+
+/// Format a sample timestamp: milliseconds for Prometheus, seconds for OpenMetrics
+fn format_exposition_timestamp(timestamp_ns: u64, format: TextFormat) -> String {
+    let millis = timestamp_ns / 1_000_000;
+    match format {
+        TextFormat::Prometheus => millis.to_string(),
+        TextFormat::OpenMetrics => format!("{}.{:03}", millis / 1000, millis % 1000),
+    }
+}
+
+/// Render a label set as `{k="v",...}` sorted by key, with an optional `le` label
+fn prometheus_labels(labels: &Labels, le: Option<&str>) -> String {
+    let mut pairs: Vec<(&str, &str)> = labels
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    pairs.sort();
+    if let Some(le) = le {
+        pairs.push(("le", le));
+    }
+
+    if pairs.is_empty() {
+        return String::new();
+    }
+
+    let rendered: Vec<String> = pairs
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, escape_label_value(v)))
+        .collect();
+    format!("{{{}}}", rendered.join(","))
+}
+
+/// Format a sample value, spelling infinities and NaN the Prometheus way
+fn format_sample_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value.is_sign_positive() {
+            "+Inf".to_string()
+        } else {
+            "-Inf".to_string()
+        }
+    } else {
+        value.to_string()
+    }
+}
+
+/// Escape a label value: backslash, double quote, and newline
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Escape help text: backslash and newline
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Base64 with the URL-safe alphabet and `=` padding
+///
+/// An empty input encodes as `=`, which the Pushgateway reads as an empty
+/// value.
+fn base64_url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    if bytes.is_empty() {
+        return "=".to_string();
+    }
+
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let triple = chunk.iter().enumerate().fold(0u32, |acc, (i, &byte)| {
+            acc | (u32::from(byte) << (16 - 8 * i))
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::HistogramBucket;
+    use std::time::Duration;
+
+    fn sample_snapshots() -> Vec<MetricSnapshot> {
+        let counter = MetricRequest::counter("http_requests_total", 3.0)
+            .with_label("method", "GET")
+            .with_label("status", "200")
+            .with_help("Total HTTP requests");
+
+        let histogram = MetricSnapshot::new(
+            "request_duration_seconds".to_string(),
+            MetricType::Histogram,
+            MetricValue::Histogram {
+                sum: 1.5,
+                count: 3,
+                buckets: vec![
+                    HistogramBucket {
+                        upper_bound: 0.5,
+                        count: 2,
+                    },
+                    HistogramBucket {
+                        upper_bound: f64::INFINITY,
+                        count: 3,
+                    },
+                ],
+            },
+            Labels::new(),
+        );
+
+        vec![MetricSnapshot::from(&counter), histogram]
+    }
+
+    #[test]
+    fn test_export_jsonl_line_framing() {
+        let output = export_jsonl(&sample_snapshots()).unwrap();
+
+        assert!(output.ends_with('\n'));
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.lines().all(|line| !line.contains('\n')));
+    }
+
+    #[test]
+    fn test_export_jsonl_sorted_labels() {
+        let request = MetricRequest::counter("requests", 1.0)
+            .with_label("zone", "eu")
+            .with_label("app", "api")
+            .with_label("method", "GET");
+
+        let output = export_jsonl(&[MetricSnapshot::from(&request)]).unwrap();
+        assert!(output.contains(r#""labels":{"app":"api","method":"GET","zone":"eu"}"#));
+    }
+
+    #[test]
+    fn test_export_jsonl_round_trip() {
+        let snapshots = sample_snapshots();
+        let output = export_jsonl(&snapshots).unwrap();
+
+        let parsed: Vec<MetricSnapshot> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(parsed, snapshots);
+    }
+
+    #[test]
+    fn test_export_jsonl_empty() {
+        assert_eq!(export_jsonl(&[]).unwrap(), "");
+    }
+
+    #[test]
+    fn test_export_prometheus_text_fixed_timestamps() {
+        let first = MetricSnapshot::from(
+            &MetricRequest::counter("http_requests_total", 3.0)
+                .with_label("status", "200")
+                .with_label("method", "GET")
+                .with_help("Total HTTP requests"),
+        )
+        .with_timestamp(1_700_000_000_000_000_000);
+        let second = MetricSnapshot::from(&MetricRequest::gauge("memory_bytes", 512.5))
+            .with_timestamp(1_700_000_000_123_456_789);
+
+        let expected = "# HELP http_requests_total Total HTTP requests\n\
+                        # TYPE http_requests_total counter\n\
+                        http_requests_total{method=\"GET\",status=\"200\"} 3 1700000000000\n\
+                        # TYPE memory_bytes gauge\n\
+                        memory_bytes 512.5 1700000000123\n";
+
+        let snapshots = vec![first, second];
+        assert_eq!(export_prometheus_text(&snapshots), expected);
+        assert_eq!(
+            export_prometheus_text(&snapshots),
+            export_prometheus_text(&snapshots.clone())
+        );
+    }
+
+    #[test]
+    fn test_export_prometheus_text_histogram() {
+        let snapshots: Vec<MetricSnapshot> = sample_snapshots()
+            .into_iter()
+            .map(|s| s.with_timestamp(2_000_000))
+            .collect();
+
+        let output = export_prometheus_text(&snapshots[1..]);
+        assert_eq!(
+            output,
+            "# TYPE request_duration_seconds histogram\n\
+             request_duration_seconds_bucket{le=\"0.5\"} 2 2\n\
+             request_duration_seconds_bucket{le=\"+Inf\"} 3 2\n\
+             request_duration_seconds_sum 1.5 2\n\
+             request_duration_seconds_count 3 2\n"
+        );
+    }
+
+    /// Zero timestamps, which don't survive the millisecond round trip
+    fn without_timestamps(snapshots: Vec<MetricSnapshot>) -> Vec<MetricSnapshot> {
+        snapshots.into_iter().map(|s| s.with_timestamp(0)).collect()
+    }
+
+    #[test]
+    fn test_parse_prometheus_text_round_trip() {
+        let snapshots = vec![
+            MetricSnapshot::from(
+                &MetricRequest::counter("http_requests_total", 3.0)
+                    .with_label("method", "GET")
+                    .with_label("path", "/a\"b\\c\nd")
+                    .with_help("Total HTTP requests\nby method"),
+            ),
+            MetricSnapshot::from(
+                &MetricRequest::counter("http_requests_total", 1.0)
+                    .with_label("method", "POST")
+                    .with_help("Total HTTP requests\nby method"),
+            ),
+            MetricSnapshot::from(&MetricRequest::gauge("memory_bytes", -512.5)),
+            sample_snapshots().remove(1),
+        ];
+
+        let parsed = parse_prometheus_text(&export_prometheus_text(&snapshots)).unwrap();
+        assert_eq!(without_timestamps(parsed), without_timestamps(snapshots));
+    }
+
+    #[tokio::test]
+    async fn test_export_build_info_metric() {
+        let metrics = MockMetricsAdapter::default();
+        let request =
+            MetricRequest::info("build_info", [("version", "1.4.2"), ("commit", "9f3c2a1")])
+                .with_help("Build metadata");
+        metrics.record(&request).await.unwrap();
+        assert!(metrics
+            .record(&MetricRequest::info("build_info", Labels::new()))
+            .await
+            .is_err());
+
+        let snapshots = without_timestamps(metrics.get_snapshot().await.unwrap());
+
+        // Text format 0.0.4 has no info type, so scrapes see a plain gauge
+        assert_eq!(
+            export_prometheus_text(&snapshots),
+            "# HELP build_info Build metadata\n\
+             # TYPE build_info gauge\n\
+             build_info{commit=\"9f3c2a1\",version=\"1.4.2\"} 1 0\n"
+        );
+
+        let exported = export_openmetrics_text(&snapshots);
+        assert_eq!(
+            exported,
+            "# HELP build Build metadata\n\
+             # TYPE build info\n\
+             build_info{commit=\"9f3c2a1\",version=\"1.4.2\"} 1 0.000\n\
+             # EOF\n"
+        );
+
+        let parsed = parse_prometheus_text(&exported).unwrap();
+        assert_eq!(without_timestamps(parsed), snapshots);
+    }
+
+    #[test]
+    fn test_openmetrics_round_trip() {
+        let snapshots = vec![
+            MetricSnapshot::from(
+                &MetricRequest::counter("http_requests_total", 3.0)
+                    .with_label("method", "GET")
+                    .with_help("Requests \"served\""),
+            )
+            .with_timestamp(1_700_000_000_250_000_000),
+            MetricSnapshot::from(&MetricRequest::gauge("queue_depth", 7.0))
+                .with_timestamp(1_700_000_000_000_000_000),
+            MetricSnapshot::from(&MetricRequest::timer_millis("request_duration", 5.0))
+                .with_timestamp(1_700_000_000_000_000_000),
+        ];
+
+        let exported = export_openmetrics_text(&snapshots);
+        assert!(exported.starts_with(
+            "# HELP http_requests Requests \\\"served\\\"\n\
+             # TYPE http_requests counter\n\
+             http_requests_total{method=\"GET\"} 3 1700000000.250\n"
+        ));
+        assert!(exported.contains("# TYPE request_duration unknown\n"));
+        assert!(exported.ends_with("# EOF\n"));
+
+        let mut expected = snapshots;
+        expected[0].temporality = Some(AggregationTemporality::Cumulative);
+        expected[2].metric_type = MetricType::Gauge;
+        assert_eq!(parse_prometheus_text(&exported).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_prometheus_text_timestamps_and_labelled_histograms() {
+        let input = "# TYPE latency histogram\n\
+                     latency_bucket{route=\"a\",le=\"0.1\"} 1 5\n\
+                     latency_bucket{route=\"b\",le=\"0.1\"} 0 5\n\
+                     latency_bucket{le=\"+Inf\",route=\"a\"} 2 5\n\
+                     latency_sum{route=\"a\"} 0.3 5\n\
+                     latency_count{route=\"a\"} 2 5\n\
+                     # some comment\n\
+                     \n\
+                     untyped_metric 7\n";
+
+        let parsed = parse_prometheus_text(input).unwrap();
+        assert_eq!(parsed.len(), 3);
+
+        assert_eq!(parsed[0].labels["route"], "a");
+        assert_eq!(parsed[0].timestamp, 5_000_000);
+        assert_eq!(
+            parsed[0].value,
+            MetricValue::Histogram {
+                sum: 0.3,
+                count: 2,
+                buckets: vec![
+                    HistogramBucket {
+                        upper_bound: 0.1,
+                        count: 1
+                    },
+                    HistogramBucket {
+                        upper_bound: f64::INFINITY,
+                        count: 2
+                    },
+                ],
+            }
+        );
+        assert_eq!(parsed[1].labels["route"], "b");
+        assert_eq!(parsed[2].metric_type, MetricType::Gauge);
+        assert_eq!(parsed[2].value, MetricValue::Single(7.0));
+    }
+
+    #[test]
+    fn test_parse_prometheus_text_malformed_lines() {
+        let cases = [
+            ("ok 1\nbroken\n", "line 2"),
+            ("# TYPE x sometype\n", "line 1"),
+            ("x{a=\"1\" 1\n", "line 1"),
+            ("x{a=1} 1\n", "line 1"),
+            ("x one\n", "line 1"),
+            ("\n\nx 1 soon\n", "line 3"),
+            ("# TYPE h histogram\nh_bucket 1\n", "line 2"),
+        ];
+
+        for (input, expected) in cases {
+            let error = parse_prometheus_text(input).unwrap_err().to_string();
+            assert!(error.contains(expected), "{input:?}: {error}");
+            assert!(error.contains("prometheus_text"), "{input:?}: {error}");
+        }
+    }
+
+    #[test]
+    fn test_parse_prometheus_text_timestamp_overflow() {
+        let error = parse_prometheus_text("foo 1 99999999999999999\n")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("line 1"), "{error}");
+        assert!(error.contains("timestamp out of range"), "{error}");
+    }
+
+    #[test]
+    fn test_export_prometheus_text_groups_and_escapes() {
+        let snapshots = vec![
+            MetricSnapshot::from(&MetricRequest::gauge("temp", 1.0).with_label("room", "a\"b"))
+                .with_timestamp(0),
+            MetricSnapshot::from(&MetricRequest::gauge("other", 2.0)).with_timestamp(0),
+            MetricSnapshot::from(&MetricRequest::gauge("temp", 3.0).with_label("room", "c\\d"))
+                .with_timestamp(0),
+            MetricSnapshot::from(&MetricRequest::histogram("latency", 0.2)).with_timestamp(0),
+        ];
+
+        assert_eq!(
+            export_prometheus_text(&snapshots),
+            "# TYPE temp gauge\n\
+             temp{room=\"a\\\"b\"} 1 0\n\
+             temp{room=\"c\\\\d\"} 3 0\n\
+             # TYPE other gauge\n\
+             other 2 0\n\
+             # TYPE latency untyped\n\
+             latency 0.2 0\n"
+        );
+    }
+
+    #[test]
+    fn test_export_pushgateway_path() {
+        let snapshots = vec![MetricSnapshot::from(&MetricRequest::counter(
+            "jobs_total",
+            3.0,
+        ))];
+        let grouping: Vec<(String, String)> = [
+            ("instance", "10.0.0.1:9100"),
+            ("job", "batch/nightly"),
+            ("stage", ""),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let (path, body) = export_pushgateway(&snapshots, &grouping).unwrap();
+        assert_eq!(
+            path,
+            "/metrics/job@base64/YmF0Y2gvbmlnaHRseQ==/instance/10.0.0.1%3A9100/stage@base64/="
+        );
+        assert_eq!(body, "# TYPE jobs_total counter\njobs_total 3\n");
+    }
+
+    #[test]
+    fn test_export_pushgateway_omits_timestamps() {
+        let snapshots = vec![
+            MetricSnapshot::from(
+                &MetricRequest::gauge("queue_depth", 4.0).with_label("queue", "a b"),
+            )
+            .with_timestamp(1_700_000_000_000_000_000),
+            MetricSnapshot::from(&MetricRequest::counter("jobs_total", 3.0))
+                .with_timestamp(1_700_000_000_000_000_000),
+        ];
+        let grouping = vec![("job".to_string(), "batch".to_string())];
+
+        let (_, body) = export_pushgateway(&snapshots, &grouping).unwrap();
+        let samples: Vec<&str> = body.lines().filter(|line| !line.starts_with('#')).collect();
+        assert_eq!(samples.len(), 2);
+        for sample in samples {
+            // Skip the label set, whose quoted values may contain spaces
+            let fields = match sample.rsplit_once('}') {
+                Some((series, value)) => vec![series, value.trim()],
+                None => sample.split_whitespace().collect(),
+            };
+            assert_eq!(fields.len(), 2, "sample has a timestamp: {sample}");
+        }
+    }
+
+    #[test]
+    fn test_export_pushgateway_rejects_bad_grouping() {
+        let pairs = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+
+        assert!(export_pushgateway(&[], &pairs(&[("instance", "a")])).is_err());
+        assert!(export_pushgateway(&[], &pairs(&[("job", "a"), ("bad-key", "b")])).is_err());
+        assert!(export_pushgateway(&[], &pairs(&[("job", "a"), ("job", "b")])).is_err());
+    }
+
+    #[test]
+    fn test_base64_url_padding() {
+        assert_eq!(base64_url(b"a"), "YQ==");
+        assert_eq!(base64_url(b"ab"), "YWI=");
+        assert_eq!(base64_url(b"abc"), "YWJj");
+        assert_eq!(base64_url(&[0xfb, 0xff]), "-_8=");
+    }
+
+    #[test]
+    fn test_format_snapshot_table() {
+        let snapshots = vec![
+            MetricSnapshot::from(
+                &MetricRequest::counter("http_requests_total", 2.0).with_label("method", "GET"),
+            ),
+            MetricSnapshot::from(&MetricRequest::gauge("queue_depth", 7.0)).with_timestamp(2),
+            MetricSnapshot::from(
+                &MetricRequest::counter("http_requests_total", 3.0).with_label("method", "GET"),
+            ),
+            MetricSnapshot::from(
+                &MetricRequest::counter("http_requests_total", 1.0).with_label("method", "POST"),
+            ),
+            MetricSnapshot::from(&MetricRequest::gauge("queue_depth", 4.0)).with_timestamp(1),
+            MetricSnapshot::from(&MetricRequest::histogram("latency", 0.25)),
+        ]
+        .into_iter()
+        .chain(sample_snapshots().into_iter().skip(1))
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            format_snapshot_table(&snapshots),
+            "NAME                      TYPE       LABELS       VALUE\n\
+             http_requests_total       counter    method=GET   5\n\
+             http_requests_total       counter    method=POST  1\n\
+             queue_depth               gauge      -            7\n\
+             latency                   histogram  -            count=1 sum=0.25\n\
+             request_duration_seconds  histogram  -            count=3 sum=1.5\n"
+        );
+    }
+
+    #[test]
+    fn test_format_snapshot_table_empty() {
+        assert_eq!(format_snapshot_table(&[]), "(no metrics)\n");
+    }
+
+    #[test]
+    fn test_export_folded_stacks() {
+        let timer = |operation: &str, millis: u64| {
+            MetricSnapshot::from(
+                &MetricRequest::timer("span_duration", Duration::from_millis(millis))
+                    .with_label("operation", operation),
+            )
+        };
+        let snapshots = vec![
+            timer("checkout", 10),
+            timer("checkout;load cart", 4),
+            timer("checkout", 5),
+            timer("search", 2),
+            MetricSnapshot::from(
+                &MetricRequest::gauge("queue_depth", 7.0).with_label("operation", "checkout"),
+            ),
+            MetricSnapshot::from(&MetricRequest::timer(
+                "unlabeled",
+                Duration::from_millis(100),
+            )),
+        ];
+
+        assert_eq!(
+            export_folded_stacks(&snapshots, "operation"),
+            "checkout 15000\ncheckout;load_cart 4000\nsearch 2000\n"
+        );
+        assert_eq!(export_folded_stacks(&snapshots, "missing"), "");
+    }
+}
@@ -0,0 +1,225 @@
+//! Convenience recording methods for every metrics adapter
+//!
+//! `MetricsManagerExt` is implemented for all `MetricsManager`s, so simple
+//! increments and observations don't need a hand-built `MetricRequest`.
+
+use super::*;
+
+/// Ergonomic shortcuts over `MetricsManager::record`
+///
+/// Each method builds the matching `MetricRequest` and records it, so
+/// validation and error handling are exactly those of the adapter.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{MetricsManagerExt, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let metrics = MockMetricsAdapter::default();
+/// metrics.incr("http_requests_total").await.unwrap();
+/// metrics.set_gauge("memory_bytes", 512.0).await.unwrap();
+/// metrics.observe("request_duration_seconds", 0.25).await.unwrap();
+/// assert_eq!(metrics.get_metrics_count().await, 3);
+/// # });
+/// ```
+#[async_trait]
+pub trait MetricsManagerExt: MetricsManager {
+    /// Increment a counter by one
+    async fn incr(&self, name: &str) -> Result<()> {
+        self.incr_by_with(name, 1.0, Labels::new()).await
+    }
+
+    /// Increment a counter by `value`
+    async fn incr_by(&self, name: &str, value: f64) -> Result<()> {
+        self.incr_by_with(name, value, Labels::new()).await
+    }
+
+    /// Set a gauge to `value`
+    async fn set_gauge(&self, name: &str, value: f64) -> Result<()> {
+        self.set_gauge_with(name, value, Labels::new()).await
+    }
+
+    /// Observe `value` in a histogram
+    async fn observe(&self, name: &str, value: f64) -> Result<()> {
+        self.observe_with(name, value, Labels::new()).await
+    }
+
+    /// Record a validated template with `value`
+    ///
+    /// Only the value is checked; the name and labels were validated once
+    /// when the template was built with `MetricRequest::template`.
+    async fn record_template(&self, template: &ValidatedMetricRequest, value: f64) -> Result<()> {
+        self.record_validated(&template.clone().with_value(value)?)
+            .await
+    }
+
+    /// Increment a labeled counter by one
+    async fn incr_with(&self, name: &str, labels: Labels) -> Result<()> {
+        self.incr_by_with(name, 1.0, labels).await
+    }
+
+    /// Increment a labeled counter by `value`
+    async fn incr_by_with(&self, name: &str, value: f64, labels: Labels) -> Result<()> {
+        self.record(&MetricRequest::counter_inc(name, value).with_labels(labels))
+            .await
+    }
+
+    /// Set a labeled gauge to `value`
+    async fn set_gauge_with(&self, name: &str, value: f64, labels: Labels) -> Result<()> {
+        self.record(&MetricRequest::gauge(name, value).with_labels(labels))
+            .await
+    }
+
+    /// Observe `value` in a labeled histogram
+    async fn observe_with(&self, name: &str, value: f64, labels: Labels) -> Result<()> {
+        self.record(&MetricRequest::histogram(name, value).with_labels(labels))
+            .await
+    }
+
+    /// Start a guard that increments a labeled counter when it drops
+    ///
+    /// Call `on_panic_only` on the guard to count only scopes that panic.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{Labels, MetricsManagerExt, MockMetricsAdapter};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let metrics = MockMetricsAdapter::default();
+    /// {
+    ///     let _attempts = metrics.start_scope_counter("job_attempts_total", Labels::new());
+    ///     let _failures = metrics
+    ///         .start_scope_counter("job_panics_total", Labels::new())
+    ///         .on_panic_only();
+    /// }
+    /// assert_eq!(metrics.counter_total("job_attempts_total", None).await, 1.0);
+    /// assert_eq!(metrics.counter_total("job_panics_total", None).await, 0.0);
+    /// # });
+    /// ```
+    fn start_scope_counter(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.start_counter_guard(name, labels)
+    }
+}
+
+impl<M: MetricsManager + ?Sized> MetricsManagerExt for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method_label() -> Labels {
+        [("method".to_string(), "GET".to_string())].into()
+    }
+
+    #[tokio::test]
+    async fn test_ext_counters() {
+        let metrics = MockMetricsAdapter::default();
+        metrics.incr("requests").await.unwrap();
+        metrics.incr_by("bytes_sent", 512.0).await.unwrap();
+        metrics.incr_with("requests", method_label()).await.unwrap();
+
+        let stored = metrics.get_stored_metrics().await;
+        assert!(stored.iter().all(|m| m.metric_type == MetricType::Counter));
+        assert_eq!(stored[0].value, MetricValue::Single(1.0));
+        assert_eq!(stored[1].value, MetricValue::Single(512.0));
+        assert_eq!(stored[2].labels, method_label());
+        assert!(metrics.incr_by("requests", -1.0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scope_counter_increments_on_drop() {
+        let metrics = MockMetricsAdapter::default();
+        for _ in 0..3 {
+            let _guard = metrics.start_scope_counter("jobs_total", method_label());
+        }
+
+        let stored = metrics.get_stored_metrics().await;
+        assert!(stored.iter().all(|m| m.metric_type == MetricType::Counter));
+        assert_eq!(stored[0].labels, method_label());
+        assert_eq!(
+            metrics
+                .counter_total("jobs_total", Some(&method_label()))
+                .await,
+            3.0
+        );
+        assert!(metrics
+            .find_metrics_by_type(MetricType::Timer)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scope_counter_on_panic_only() {
+        let metrics = MockMetricsAdapter::default();
+        {
+            let _guard = metrics
+                .start_scope_counter("job_panics_total", Labels::new())
+                .on_panic_only();
+        }
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = metrics
+                .start_scope_counter("job_panics_total", Labels::new())
+                .on_panic_only();
+            panic!("job failed");
+        }));
+
+        assert!(panicked.is_err());
+        assert_eq!(metrics.counter_total("job_panics_total", None).await, 1.0);
+        assert_eq!(metrics.get_metrics_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_ext_gauges_and_histograms() {
+        let metrics = MockMetricsAdapter::default();
+        metrics.set_gauge("memory_bytes", 256.0).await.unwrap();
+        metrics
+            .set_gauge_with("queue_depth", 3.0, method_label())
+            .await
+            .unwrap();
+        metrics.observe("latency", 0.5).await.unwrap();
+        metrics
+            .observe_with("latency", 0.7, method_label())
+            .await
+            .unwrap();
+
+        let stored = metrics.get_stored_metrics().await;
+        let kinds: Vec<(MetricType, MetricValue)> = stored
+            .iter()
+            .map(|m| (m.metric_type, m.value.clone()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (MetricType::Gauge, MetricValue::Single(256.0)),
+                (MetricType::Gauge, MetricValue::Single(3.0)),
+                (MetricType::Histogram, MetricValue::Single(0.5)),
+                (MetricType::Histogram, MetricValue::Single(0.7)),
+            ]
+        );
+        assert_eq!(stored[3].labels, method_label());
+    }
+
+    #[tokio::test]
+    async fn test_ext_record_template_validates_once() {
+        let metrics = MockMetricsAdapter::default();
+        let template = MetricRequest::template("http_requests_total", MetricType::Counter)
+            .with_label("endpoint", "/users")
+            .with_label("method", "GET")
+            .validate()
+            .unwrap();
+
+        for i in 0..1000 {
+            metrics.record_template(&template, i as f64).await.unwrap();
+        }
+        assert!(metrics.record_template(&template, -1.0).await.is_err());
+
+        let stored = metrics.get_stored_metrics().await;
+        assert_eq!(stored.len(), 1000);
+        assert!(stored
+            .iter()
+            .all(|m| m.labels == template.request().labels().clone()));
+        assert_eq!(stored[999].value, MetricValue::Single(999.0));
+        // The template's own `validate` was the only full validation
+        assert_eq!(metrics.validation_count(), 0);
+    }
+}
@@ -0,0 +1,169 @@
+//! Process-wide default recorder
+//!
+//! Passing an adapter through every function is not always practical. This
+//! module holds one optional recorder for the whole process, set once with
+//! `set_global_recorder`, and the `counter!`, `gauge!`, `histogram!`, and
+//! `timer!` macros that record against it. Until a recorder is set, every
+//! macro is a no-op.
+//!
+//! The recording macros return a future resolving to `Result<()>`, so call
+//! sites await them like any other record:
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use tyl_metrics_port::{counter, global, MockMetricsAdapter};
+//!
+//! # tokio_test::block_on(async {
+//! let metrics = Arc::new(MockMetricsAdapter::default());
+//! global::set_global_recorder(metrics.clone()).unwrap();
+//!
+//! counter!("http_requests_total", 1.0, "method" => "GET").await.unwrap();
+//! assert_eq!(metrics.get_metrics_count().await, 1);
+//! # });
+//! ```
+
+use super::*;
+use std::sync::{Arc, OnceLock};
+
+static GLOBAL_RECORDER: OnceLock<Arc<dyn DynMetricsManager>> = OnceLock::new();
+
+/// Install the process-wide recorder
+///
+/// The recorder can only be set once; later calls fail with a configuration
+/// error and leave the original in place.
+pub fn set_global_recorder(recorder: Arc<dyn DynMetricsManager>) -> Result<()> {
+    GLOBAL_RECORDER
+        .set(recorder)
+        .map_err(|_| metrics_config_error("global_recorder", "Global recorder is already set"))
+}
+
+/// Get the process-wide recorder, if one was set
+pub fn global_recorder() -> Option<Arc<dyn DynMetricsManager>> {
+    GLOBAL_RECORDER.get().cloned()
+}
+
+/// Record a request against the global recorder, or do nothing if none is set
+///
+/// This is what the recording macros expand to.
+pub async fn record(request: MetricRequest) -> Result<()> {
+    match GLOBAL_RECORDER.get() {
+        Some(recorder) => recorder.record_dyn(&request).await,
+        None => Ok(()),
+    }
+}
+
+/// Start a timer on the global recorder, or a no-op timer if none is set
+///
+/// This is what the `timer!` macro expands to.
+pub fn start_timer(name: &str, labels: Labels) -> TimerGuard {
+    match GLOBAL_RECORDER.get() {
+        Some(recorder) => recorder.start_timer_dyn(name, labels),
+        None => TimerGuard::new(name.to_string(), labels, |_| {}),
+    }
+}
+
+/// Start a counter guard on the global recorder, or a no-op guard if none is set
+pub fn start_counter_guard(name: &str, labels: Labels) -> CounterGuard {
+    match GLOBAL_RECORDER.get() {
+        Some(recorder) => recorder.start_counter_guard_dyn(name, labels),
+        None => CounterGuard::new(name.to_string(), labels, |_| {}),
+    }
+}
+
+/// Increment a counter on the global recorder
+///
+/// `counter!(name, value)` or `counter!(name, value, "key" => "value", ...)`.
+/// Returns a future resolving to `Result<()>`.
+#[macro_export]
+macro_rules! counter {
+    ($name:expr, $value:expr $(, $key:expr => $label:expr)* $(,)?) => {
+        $crate::global::record(
+            $crate::MetricRequest::counter_inc($name, $value) $(.with_label($key, $label))*,
+        )
+    };
+}
+
+/// Set a gauge on the global recorder
+///
+/// `gauge!(name, value)` or `gauge!(name, value, "key" => "value", ...)`.
+/// Returns a future resolving to `Result<()>`.
+#[macro_export]
+macro_rules! gauge {
+    ($name:expr, $value:expr $(, $key:expr => $label:expr)* $(,)?) => {
+        $crate::global::record(
+            $crate::MetricRequest::gauge($name, $value) $(.with_label($key, $label))*,
+        )
+    };
+}
+
+/// Observe a histogram value on the global recorder
+///
+/// `histogram!(name, value)` or `histogram!(name, value, "key" => "value", ...)`.
+/// Returns a future resolving to `Result<()>`.
+#[macro_export]
+macro_rules! histogram {
+    ($name:expr, $value:expr $(, $key:expr => $label:expr)* $(,)?) => {
+        $crate::global::record(
+            $crate::MetricRequest::histogram($name, $value) $(.with_label($key, $label))*,
+        )
+    };
+}
+
+/// Start a timer on the global recorder
+///
+/// `timer!(name)` or `timer!(name, "key" => "value", ...)`. Returns a
+/// `TimerGuard` that records when dropped.
+#[macro_export]
+macro_rules! timer {
+    ($name:expr $(, $key:expr => $label:expr)* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut labels = $crate::Labels::new();
+        $(labels.insert(::std::string::String::from($key), ::std::string::String::from($label));)*
+        $crate::global::start_timer($name, labels)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The recorder is process-wide and can only be set once, so everything
+    // runs in a single test
+    #[tokio::test]
+    async fn test_global_recorder_macros() {
+        // Without a recorder the macros are no-ops
+        assert!(global_recorder().is_none());
+        counter!("ignored_total", 1.0).await.unwrap();
+        drop(timer!("ignored_timer"));
+
+        let metrics = Arc::new(MockMetricsAdapter::default());
+        set_global_recorder(metrics.clone()).unwrap();
+        assert!(set_global_recorder(Arc::new(MockMetricsAdapter::default())).is_err());
+
+        counter!("requests_total", 1.0, "method" => "GET")
+            .await
+            .unwrap();
+        gauge!("memory_bytes", 512.0).await.unwrap();
+        histogram!("latency_seconds", 0.25, "route" => "/", "status" => "200")
+            .await
+            .unwrap();
+        drop(timer!("job_duration", "job" => "sync"));
+        assert!(counter!("invalid name", 1.0).await.is_err());
+
+        let stored = metrics.get_stored_metrics().await;
+        let recorded: Vec<(&str, MetricType, usize)> = stored
+            .iter()
+            .map(|m| (m.name.as_str(), m.metric_type, m.labels.len()))
+            .collect();
+        assert_eq!(
+            recorded,
+            // Counter increments are kept as running totals, after the records
+            vec![
+                ("memory_bytes", MetricType::Gauge, 0),
+                ("latency_seconds", MetricType::Histogram, 2),
+                ("job_duration", MetricType::Timer, 1),
+                ("requests_total", MetricType::Counter, 1),
+            ]
+        );
+    }
+}
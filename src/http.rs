@@ -0,0 +1,207 @@
+//! Prometheus scrape and health endpoints for axum services
+//!
+//! `metrics_handler` serves the current state of any adapter in the
+//! Prometheus text exposition format, e.g. to make the mock scrapable in
+//! local development, and `health_handler` serves its health check for
+//! readiness and liveness probes:
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use axum::{routing::get, Router};
+//! use tyl_metrics_port::{health_handler, metrics_handler, MockMetricsAdapter};
+//!
+//! let metrics = Arc::new(MockMetricsAdapter::default());
+//! let health = metrics.clone();
+//! let app: Router = Router::new()
+//!     .route("/metrics", get(move || metrics_handler(metrics.clone())))
+//!     .route("/health", get(move || health_handler(health.clone())));
+//! ```
+
+use super::*;
+use crate::export::{export_openmetrics_text, export_prometheus_text};
+use crate::snapshots::aggregate_snapshots;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+
+/// Content type of the Prometheus text exposition format
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Content type of the OpenMetrics text format
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Content type of health responses
+const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// Serve an adapter's snapshot as Prometheus text
+///
+/// Takes the adapter's snapshot, folds it into one series per name and
+/// labels (as `MockMetricsAdapter::get_aggregated_snapshot` does, so the
+/// mock's raw records scrape like a real backend), and renders it with
+/// `export_prometheus_text` under `Content-Type: text/plain; version=0.0.4`.
+/// If the snapshot can't be read or aggregated, responds `500` with the
+/// error message.
+///
+/// Only available with the `http` feature.
+pub async fn metrics_handler<M: MetricsManager>(adapter: Arc<M>) -> Response {
+    exposition_response(adapter, PROMETHEUS_CONTENT_TYPE, export_prometheus_text).await
+}
+
+/// Serve an adapter's snapshot as OpenMetrics text
+///
+/// Like `metrics_handler`, but renders with `export_openmetrics_text` under
+/// `Content-Type: application/openmetrics-text; version=1.0.0;
+/// charset=utf-8`, so info metrics keep the OpenMetrics `info` type.
+///
+/// Only available with the `http` feature.
+pub async fn openmetrics_handler<M: MetricsManager>(adapter: Arc<M>) -> Response {
+    exposition_response(adapter, OPENMETRICS_CONTENT_TYPE, export_openmetrics_text).await
+}
+
+/// Aggregate an adapter's snapshot and render it as an exposition response
+async fn exposition_response<M: MetricsManager>(
+    adapter: Arc<M>,
+    content_type: &'static str,
+    render: fn(&[MetricSnapshot]) -> String,
+) -> Response {
+    let snapshot = match adapter.get_snapshot().await.and_then(aggregate_snapshots) {
+        Ok(snapshot) => snapshot,
+        Err(error) => {
+            return (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+        }
+    };
+
+    ([(header::CONTENT_TYPE, content_type)], render(&snapshot)).into_response()
+}
+
+/// Serve an adapter's health check as JSON
+///
+/// Responds `200 OK` with the serialized `HealthStatus` when the adapter is
+/// healthy and `503 Service Unavailable` with the same body when it is not.
+/// If the health check itself fails, responds `503` with the error message,
+/// since a probe should treat an adapter it can't check as down.
+///
+/// Only available with the `http` feature.
+pub async fn health_handler<M: MetricsManager>(adapter: Arc<M>) -> Response {
+    let health = match adapter.health_check().await {
+        Ok(health) => health,
+        Err(error) => return (StatusCode::SERVICE_UNAVAILABLE, error.to_string()).into_response(),
+    };
+
+    let body = match serde_json::to_string(&health) {
+        Ok(body) => body,
+        Err(error) => {
+            return (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+        }
+    };
+    let status = if health.is_healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, [(header::CONTENT_TYPE, JSON_CONTENT_TYPE)], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    #[tokio::test]
+    async fn test_metrics_handler_serves_exposition_text() {
+        let metrics = Arc::new(MockMetricsAdapter::default());
+        for _ in 0..2 {
+            metrics
+                .record(
+                    &MetricRequest::counter("http_requests_total", 1.0).with_label("method", "GET"),
+                )
+                .await
+                .unwrap();
+        }
+
+        let response = metrics_handler(metrics).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/plain; version=0.0.4"
+        );
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("# TYPE http_requests_total counter\n"));
+        assert!(body.contains("http_requests_total{method=\"GET\"} 2 "));
+        assert_eq!(body.matches("http_requests_total{").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_openmetrics_handler_serves_info_type() {
+        let metrics = Arc::new(MockMetricsAdapter::default());
+        metrics
+            .record(&MetricRequest::info("build_info", [("version", "1.4.2")]))
+            .await
+            .unwrap();
+
+        let response = openmetrics_handler(metrics.clone()).await;
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/openmetrics-text; version=1.0.0; charset=utf-8"
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("# TYPE build info\n"));
+        assert!(body.ends_with("# EOF\n"));
+
+        let response = metrics_handler(metrics).await;
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("# TYPE build_info gauge\n"));
+    }
+
+    /// Status and JSON body of a health response
+    async fn health_response(metrics: Arc<MockMetricsAdapter>) -> (StatusCode, HealthStatus) {
+        let response = health_handler(metrics).await;
+        let status = response.status();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_health_handler_healthy() {
+        let metrics = Arc::new(MockMetricsAdapter::default());
+
+        let (status, health) = health_response(metrics).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(health.is_healthy);
+    }
+
+    #[tokio::test]
+    async fn test_health_handler_unhealthy() {
+        let metrics = Arc::new(MockMetricsAdapter::default());
+        metrics
+            .set_health_status(HealthStatus::unhealthy("storage unavailable"))
+            .await;
+
+        let (status, health) = health_response(metrics).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!health.is_healthy);
+        assert_eq!(health.message, "storage unavailable");
+    }
+
+    #[tokio::test]
+    async fn test_metrics_handler_reports_unmergeable_snapshot() {
+        let metrics = Arc::new(MockMetricsAdapter::default());
+        metrics
+            .record(&MetricRequest::counter("jobs", 1.0))
+            .await
+            .unwrap();
+        metrics
+            .record(&MetricRequest::gauge("jobs", 1.0))
+            .await
+            .unwrap();
+
+        let response = metrics_handler(metrics).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -53,27 +53,129 @@ pub use tyl_logging::Environment;
 
 // Core port interface
 mod port;
-pub use port::{HealthStatus, MetricsManager};
+pub use port::{ComponentHealth, DynMetricsManager, HealthStatus, MetricsManager};
+
+// Convenience extension methods for every adapter
+mod ext;
+pub use ext::MetricsManagerExt;
 
 // Domain types (port concern)
 mod types;
-pub use types::{Labels, MetricRequest, MetricSnapshot, MetricType, MetricValue, TimerGuard};
+pub use types::{
+    AggregationTemporality, CounterGuard, HistogramBucket, HistogramBuckets, Labels, MetricName,
+    MetricRequest, MetricSnapshot, MetricStats, MetricType, MetricValue, SnapshotFilter, TimeUnit,
+    TimerGuard, TimerOutcome, ValidatedMetricRequest,
+};
 
 // Error helpers for metrics domain
 mod errors;
 pub use errors::{
-    from_io_error, from_serde_json_error, metrics_adapter_error, metrics_config_error,
-    metrics_connection_error, metrics_error, metrics_health_error, metrics_recording_error,
-    metrics_serialization_error, metrics_timeout_error, MetricsErrorExt,
+    from_http_status, from_io_error, from_serde_json_error, from_transport_error, is_retryable,
+    metrics_adapter_error, metrics_capacity_error, metrics_config_error, metrics_connection_error,
+    metrics_error, metrics_error_category, metrics_error_code, metrics_health_error,
+    metrics_recording_error, metrics_serialization_error, metrics_timeout_error,
+    metrics_timeout_error_after, metrics_timeout_error_with_message, MetricsErrorExt,
 };
 
 // Utilities and validation (port concern)
 mod utils;
-pub use utils::{format_labels, normalize_metric_name, validate_metric_name};
+pub use utils::{
+    bucket_index, format_labels, format_labels_with, normalize_label_key, normalize_metric_name,
+    sanitize_metric_name, series_hash, validate_histogram_buckets, validate_label_key,
+    validate_label_key_with, validate_label_value, validate_label_value_for,
+    validate_label_value_with, validate_labels, validate_labels_with, validate_metric_name,
+    validate_metric_name_for, validate_metric_name_with, validate_metric_request,
+    validate_metric_request_with, FormatOptions, LabelValueCharset, NamingConvention,
+    ValidationLimits,
+};
+
+// Snapshot exporters (port concern)
+mod export;
+#[cfg(not(target_arch = "wasm32"))]
+pub use export::load_snapshots;
+pub use export::{
+    export_folded_stacks, export_jsonl, export_openmetrics_text, export_prometheus_text,
+    export_pushgateway, format_snapshot_table, parse_prometheus_text,
+};
+
+// Snapshot comparison helpers (port concern)
+mod snapshots;
+pub use snapshots::{
+    dedup_latest, diff_snapshots, fingerprint, replay_snapshots, snapshots_eq_ignoring_timestamps,
+    ReplayReport, SnapshotChange, SnapshotDiff,
+};
+
+// OTLP-shaped data points for OpenTelemetry adapters (port concern)
+mod otlp;
+pub use otlp::{to_otlp_datapoint, OtlpDataPoint, OtlpMetricKind, OtlpValue};
+
+// Up-front metric declarations and typed handles
+mod registry;
+pub use registry::{CounterHandle, GaugeHandle, HistogramHandle, MetricDefinition, MetricRegistry};
+
+// Composable adapter decorators
+mod decorators;
+#[cfg(feature = "unicode")]
+pub use decorators::UnicodeNormalizingMetricsAdapter;
+pub use decorators::{
+    BufferSettings, BufferingMetricsAdapter, CircuitBreakerMetricsAdapter, CircuitBreakerSettings,
+    CircuitState, ConcurrencyLimitMetricsAdapter, ConcurrencyLimits, ConditionalMetricsAdapter,
+    DecoratorConfig, DefaultLabelsMetricsAdapter, FilteringMetricsAdapter, LabelFilter,
+    PrefixMetricsAdapter, Quantization, QuantizeSettings, QuantizingMetricsAdapter,
+    RateLimitMetricsAdapter, RateLimits, RedactingMetricsAdapter, RedactionMode,
+    RelabelMetricsAdapter, RelabelRule, RetryMetricsAdapter, RetryPolicy, RoundingMode,
+    TimeoutMetricsAdapter,
+};
+#[cfg(feature = "tracing")]
+pub use decorators::{TraceContext, TraceContextMetricsAdapter, TracingMetricsAdapter};
+#[cfg(feature = "unicode")]
+pub use utils::normalize_unicode;
+
+// Process-wide default recorder and macros (optional)
+#[cfg(feature = "global")]
+pub mod global;
+
+// Prometheus scrape and health endpoints for axum (optional)
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+pub use http::{health_handler, metrics_handler, openmetrics_handler};
+
+// Recorder for the `metrics` facade crate (optional)
+#[cfg(feature = "metrics-compat")]
+mod metrics_compat;
+#[cfg(feature = "metrics-compat")]
+pub use metrics_compat::TylMetricsRecorder;
+
+// Throughput probe for performance regression checks (optional)
+#[cfg(feature = "bench")]
+mod bench;
+#[cfg(feature = "bench")]
+pub use bench::throughput_probe;
+
+// Label-building macros (`labels!`, `metric_labels!`)
+mod macros;
+#[doc(hidden)]
+pub use macros::validated_labels;
+
+// Pluggable time source
+mod clock;
+pub use clock::{Clock, MockClock, SystemClock};
+
+// No-op adapter for switching metrics off
+mod noop;
+pub use noop::{NoopMetricsAdapter, NoopMetricsConfig};
 
 // Mock adapter for testing and examples (always available)
 mod mock;
-pub use mock::{MockMetricsAdapter, MockMetricsConfig};
+pub use mock::{
+    DropStrategy, GaugeAgg, MetricSubscription, MockAdapterBuilder, MockMetricsAdapter,
+    MockMetricsConfig,
+};
+
+// Environment-to-adapter mapping
+mod environment;
+pub use environment::adapter_for_environment;
 
 /// Result type for metrics operations using TYL error handling
 pub type Result<T> = TylResult<T>;
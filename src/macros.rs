@@ -0,0 +1,110 @@
+//! Label-building macros
+//!
+//! `labels!` builds a `Labels` map from `key => value` pairs, and
+//! `metric_labels!` also validates it, naming the offending key on failure:
+//!
+//! ```rust
+//! use tyl_metrics_port::{labels, metric_labels, MetricRequest};
+//!
+//! let request = MetricRequest::counter("http_requests_total", 1.0)
+//!     .with_labels(labels! { method => "GET", status => 200 });
+//! assert_eq!(request.labels()["status"], "200");
+//!
+//! assert!(metric_labels! { "status-code" => 200 }.is_err());
+//! ```
+
+use super::*;
+use crate::utils::validate_label_key;
+
+/// Build a `Labels` map from `key => value` pairs
+///
+/// Keys are identifiers (`method => "GET"`), so a misspelled key reads as
+/// one, or string literals for keys that aren't identifiers. Values are
+/// anything implementing `ToString`, so numbers need no quoting. Later pairs
+/// replace earlier ones with the same key. Nothing is validated; use
+/// `metric_labels!` for that.
+#[macro_export]
+macro_rules! labels {
+    ($($key:tt => $value:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut labels = $crate::Labels::new();
+        $(
+            labels.insert(
+                ::std::string::String::from($crate::__label_key!($key)),
+                ::std::string::ToString::to_string(&$value),
+            );
+        )*
+        labels
+    }};
+}
+
+/// Build a `Labels` map like `labels!`, validating it
+///
+/// Evaluates to `Result<Labels>`. Each key is checked with
+/// `validate_label_key`, and an invalid one fails with a validation error
+/// naming it; the finished map is then checked with `validate_labels`.
+#[macro_export]
+macro_rules! metric_labels {
+    ($($key:tt => $value:expr),* $(,)?) => {
+        $crate::validated_labels([
+            $((
+                $crate::__label_key!($key),
+                ::std::string::ToString::to_string(&$value),
+            )),*
+        ])
+    };
+}
+
+/// Turn an identifier or string literal label key into a `&str`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __label_key {
+    ($key:ident) => {
+        stringify!($key)
+    };
+    ($key:literal) => {
+        $key
+    };
+}
+
+/// Validate and collect the pairs built by `metric_labels!`
+#[doc(hidden)]
+pub fn validated_labels<const N: usize>(pairs: [(&str, String); N]) -> Result<Labels> {
+    let mut labels = Labels::new();
+    for (key, value) in pairs {
+        validate_label_key(key)
+            .map_err(|error| error.with_metrics_context(format!("label key {key:?}")))?;
+        labels.insert(key.to_string(), value);
+    }
+
+    validate_labels(&labels)?;
+    Ok(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_macro_matches_manual_insertion() {
+        let mut manual = Labels::new();
+        manual.insert("method".to_string(), "GET".to_string());
+        manual.insert("status".to_string(), "200".to_string());
+        manual.insert("content-type".to_string(), "json".to_string());
+
+        let built = labels! { method => "GET", status => 200, "content-type" => "json" };
+        assert_eq!(built, manual);
+        assert!(labels! {}.is_empty());
+    }
+
+    #[test]
+    fn test_metric_labels_macro_validates_keys() {
+        let labels = metric_labels! { method => "GET", status => 200 }.unwrap();
+        assert_eq!(labels, labels! { method => "GET", status => "200" });
+
+        let error = metric_labels! { method => "GET", "content-type" => "json" }.unwrap_err();
+        assert!(error.to_string().contains("label key \"content-type\""));
+        assert!(metric_labels! { __reserved => "x" }.is_err());
+        assert!(metric_labels! {}.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+//! Bridge from the `metrics` facade crate
+//!
+//! Many libraries are instrumented with the `metrics` crate macros
+//! (`metrics::counter!`, `metrics::gauge!`, `metrics::histogram!`), which
+//! record through whichever `metrics::Recorder` is installed. Installing a
+//! `TylMetricsRecorder` routes those calls into any `MetricsManager`.
+//!
+//! `metrics` handles record synchronously, while adapters record
+//! asynchronously, so the recorder enqueues every call on a channel drained
+//! by a background task in order.
+
+use super::*;
+use crate::utils::{normalize_label_key, sanitize_metric_name};
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, oneshot};
+
+/// Work queued for the background task
+enum Command {
+    Record(MetricRequest),
+    Flush(oneshot::Sender<()>),
+}
+
+/// `metrics::Recorder` forwarding to a wrapped adapter
+///
+/// Keys are translated on registration:
+/// - Names go through `sanitize_metric_name`, so `http.requests` becomes
+///   `http_requests`
+/// - Label keys go through `normalize_label_key`; label values are kept
+/// - Counter increments become `counter_inc` requests; `absolute` totals are
+///   turned into increments against the last total seen
+/// - Gauge `increment`/`decrement` are applied to the last value set through
+///   the recorder and recorded as the new level
+/// - Histogram values become histogram requests
+///
+/// Descriptions and units are dropped, since `MetricRequest` has nowhere to
+/// carry them. Records are queued without bound and recorded in call order;
+/// call `flush` to wait for the queue to drain. Failed records can't be
+/// reported to `metrics` call sites, so they are only counted
+/// (`failed_records`).
+///
+/// Only available with the `metrics-compat` feature.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MockMetricsAdapter, TylMetricsRecorder};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let recorder = TylMetricsRecorder::new(mock.clone()).unwrap();
+///
+/// metrics::with_local_recorder(&recorder, || {
+///     metrics::counter!("http.requests", "method" => "GET").increment(1);
+/// });
+/// recorder.flush().await.unwrap();
+///
+/// assert_eq!(mock.counter_total("http_requests", None).await, 1.0);
+/// # });
+/// ```
+///
+/// In an application, install it process-wide with
+/// `metrics::set_global_recorder(recorder)` instead.
+pub struct TylMetricsRecorder {
+    manager: Arc<dyn DynMetricsManager>,
+    sender: mpsc::UnboundedSender<Command>,
+    failed: Arc<AtomicU64>,
+    series: Mutex<HashMap<(MetricType, Key), Arc<Series>>>,
+}
+
+impl TylMetricsRecorder {
+    /// Create a recorder forwarding to `manager`
+    ///
+    /// Spawns the task draining the queue, so this must be called inside a
+    /// Tokio runtime; otherwise it fails with a configuration error.
+    pub fn new(manager: Arc<dyn DynMetricsManager>) -> Result<Self> {
+        let runtime = tokio::runtime::Handle::try_current().map_err(|_| {
+            metrics_config_error(
+                "runtime",
+                "TylMetricsRecorder must be created inside a Tokio runtime",
+            )
+        })?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let failed = Arc::new(AtomicU64::new(0));
+        runtime.spawn(drain(manager.clone(), receiver, failed.clone()));
+
+        Ok(Self {
+            manager,
+            sender,
+            failed,
+            series: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Wait until everything queued so far is recorded, then flush the adapter
+    pub async fn flush(&self) -> Result<()> {
+        let stopped = || metrics_connection_error("metrics-compat", "Recording task has stopped");
+
+        let (done, flushed) = oneshot::channel();
+        self.sender
+            .send(Command::Flush(done))
+            .map_err(|_| stopped())?;
+        flushed.await.map_err(|_| stopped())?;
+        self.manager.flush_dyn().await
+    }
+
+    /// Number of queued records the adapter rejected
+    pub fn failed_records(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    /// Get the shared handle state for a key, translating it on first use
+    fn series(&self, metric_type: MetricType, key: &Key) -> Arc<Series> {
+        let mut series = self.series.lock().unwrap();
+        series
+            .entry((metric_type, key.clone()))
+            .or_insert_with(|| {
+                Arc::new(Series {
+                    name: sanitize_metric_name(key.name()),
+                    labels: key
+                        .labels()
+                        .map(|label| (normalize_label_key(label.key()), label.value().to_string()))
+                        .collect(),
+                    value: AtomicU64::new(0),
+                    sender: self.sender.clone(),
+                })
+            })
+            .clone()
+    }
+}
+
+/// Record queued requests in order until every sender is gone
+async fn drain(
+    manager: Arc<dyn DynMetricsManager>,
+    mut receiver: mpsc::UnboundedReceiver<Command>,
+    failed: Arc<AtomicU64>,
+) {
+    while let Some(command) = receiver.recv().await {
+        match command {
+            Command::Record(request) => {
+                if manager.record_dyn(&request).await.is_err() {
+                    failed.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Command::Flush(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+/// State behind one registered `metrics` handle
+///
+/// Shared by every registration of the same key, since the `metrics` macros
+/// register again on each call.
+struct Series {
+    name: String,
+    labels: Labels,
+
+    /// Last counter total seen by `absolute`, or the gauge level as `f64` bits
+    value: AtomicU64,
+
+    sender: mpsc::UnboundedSender<Command>,
+}
+
+impl Series {
+    /// Queue a request carrying this series' labels
+    fn send(&self, mut request: MetricRequest) {
+        *request.labels_mut() = self.labels.clone();
+        // Only fails once the runtime running the task has shut down
+        let _ = self.sender.send(Command::Record(request));
+    }
+
+    /// Apply `update` to the gauge level and record the result
+    fn update_gauge(&self, update: impl Fn(f64) -> f64) {
+        let previous = self
+            .value
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                Some(update(f64::from_bits(bits)).to_bits())
+            })
+            .unwrap_or_else(|bits| bits);
+        self.send(MetricRequest::gauge(
+            self.name.clone(),
+            update(f64::from_bits(previous)),
+        ));
+    }
+}
+
+impl CounterFn for Series {
+    fn increment(&self, value: u64) {
+        self.send(MetricRequest::counter_inc(self.name.clone(), value as f64));
+    }
+
+    fn absolute(&self, value: u64) {
+        let previous = self.value.swap(value, Ordering::Relaxed);
+        // A total below the last one means the source restarted from zero
+        let increase = if value >= previous {
+            value - previous
+        } else {
+            value
+        };
+        if increase > 0 {
+            CounterFn::increment(self, increase);
+        }
+    }
+}
+
+impl GaugeFn for Series {
+    fn increment(&self, value: f64) {
+        self.update_gauge(|level| level + value);
+    }
+
+    fn decrement(&self, value: f64) {
+        self.update_gauge(|level| level - value);
+    }
+
+    fn set(&self, value: f64) {
+        self.update_gauge(|_| value);
+    }
+}
+
+impl HistogramFn for Series {
+    fn record(&self, value: f64) {
+        self.send(MetricRequest::histogram(self.name.clone(), value));
+    }
+}
+
+impl Recorder for TylMetricsRecorder {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.series(MetricType::Counter, key))
+    }
+
+    fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        Gauge::from_arc(self.series(MetricType::Gauge, key))
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(self.series(MetricType::Histogram, key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metrics_macros_reach_adapter() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let recorder = TylMetricsRecorder::new(mock.clone()).unwrap();
+
+        metrics::with_local_recorder(&recorder, || {
+            metrics::counter!("http.requests", "statusCode" => "200").increment(2);
+            metrics::counter!("http.requests", "statusCode" => "200").increment(1);
+            metrics::gauge!("queue.depth").set(5.0);
+            metrics::gauge!("queue.depth").increment(3.0);
+            metrics::gauge!("queue.depth").decrement(1.0);
+            metrics::histogram!("request.duration").record(0.25);
+        });
+        recorder.flush().await.unwrap();
+
+        let labels: Labels = [("status_code".to_string(), "200".to_string())].into();
+        assert_eq!(
+            mock.counter_total("http_requests", Some(&labels)).await,
+            3.0
+        );
+        assert_eq!(mock.latest_gauge("queue_depth", None).await, Some(7.0));
+        let observed = mock.find_metrics_by_name("request_duration").await;
+        assert_eq!(observed[0].metric_type, MetricType::Histogram);
+        assert_eq!(observed[0].value, MetricValue::Single(0.25));
+        assert_eq!(recorder.failed_records(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_absolute_counters_and_failed_records() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let recorder = TylMetricsRecorder::new(mock.clone()).unwrap();
+
+        metrics::with_local_recorder(&recorder, || {
+            let total = metrics::counter!("bytes.sent");
+            total.absolute(10);
+            total.absolute(25);
+            total.absolute(25);
+            total.absolute(4); // restarted
+            metrics::counter!("bad.value", "path" => "a\0b").increment(1);
+        });
+        recorder.flush().await.unwrap();
+
+        assert_eq!(mock.counter_total("bytes_sent", None).await, 29.0);
+        assert_eq!(mock.find_metrics_by_name("bytes_sent").await.len(), 1);
+        assert_eq!(recorder.failed_records(), 1);
+    }
+
+    #[test]
+    fn test_new_requires_runtime() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        assert!(TylMetricsRecorder::new(mock).is_err());
+    }
+}
@@ -5,12 +5,85 @@
 //! development environments where you don't need actual metrics collection.
 
 use super::*;
-use crate::errors::{metrics_config_error, metrics_recording_error};
-use crate::utils::{
-    validate_counter_value, validate_labels, validate_metric_name, validate_metric_value,
+use crate::errors::{
+    metrics_capacity_error, metrics_config_error, metrics_connection_error, metrics_recording_error,
 };
+#[cfg(not(target_arch = "wasm32"))]
+use crate::export::save_snapshots;
+use crate::snapshots::aggregate_snapshots;
+use crate::utils::{series_hash, validate_metric_request_with};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::Stream;
+
+/// Label used to tell apart instances reporting the same series
+const INSTANCE_LABEL: &str = "instance";
+
+/// Snapshots buffered per `subscribe` stream before a slow reader lags
+const SUBSCRIPTION_CAPACITY: usize = 1024;
+
+/// Aggregation applied when several instances report the same gauge
+///
+/// Instances are distinguished by their `instance` label. Which aggregation
+/// is "right" depends on the gauge: memory is summed, queue depth is maxed,
+/// CPU percentage is averaged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GaugeAgg {
+    /// Keep each instance's latest value as its own series
+    #[default]
+    Last,
+
+    /// Sum the latest value of every instance
+    Sum,
+
+    /// Take the largest latest value across instances
+    Max,
+
+    /// Take the smallest latest value across instances
+    Min,
+
+    /// Average the latest values across instances
+    Avg,
+}
+
+impl GaugeAgg {
+    /// Combine the per-instance values into one
+    fn combine(self, values: &[f64]) -> f64 {
+        match self {
+            GaugeAgg::Last => values.last().copied().unwrap_or_default(),
+            GaugeAgg::Sum => values.iter().sum(),
+            GaugeAgg::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            GaugeAgg::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            GaugeAgg::Avg => values.iter().sum::<f64>() / values.len() as f64,
+        }
+    }
+}
+
+/// What the mock does with a new metric once its storage limit is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropStrategy {
+    /// Evict the oldest stored metric to make room (FIFO)
+    #[default]
+    DropOldest,
+
+    /// Keep the stored metrics and silently discard the new one
+    DropNewest,
+
+    /// Keep the stored metrics and fail the `record` call with a
+    /// non-retryable `metrics_capacity_error`; a `histogram_many` batch that
+    /// doesn't fit whole is rejected whole
+    RejectNew,
+}
 
 /// Configuration for the mock metrics adapter
 ///
@@ -24,14 +97,83 @@ pub struct MockMetricsConfig {
     /// Whether to store metrics in memory for inspection
     pub store_metrics: bool,
 
+    /// Whether stored metrics keep their values
+    ///
+    /// When false, the store only tracks which series (name, type, and
+    /// labels) were recorded, in a set that grows with distinct series
+    /// rather than records. Reads return one placeholder snapshot per series
+    /// with value `0.0` and timestamp `0`; `max_stored_metrics`,
+    /// `drop_strategy`, and `ttl` don't apply. Use this for cardinality soak
+    /// tests together with `MockMetricsAdapter::distinct_series`.
+    pub store_values: bool,
+
     /// Maximum number of metrics to store (prevents memory leaks in tests)
+    ///
+    /// With multiple shards the limit is split evenly, so each shard holds at
+    /// most `max_stored_metrics / shards` (rounded up) metrics.
     pub max_stored_metrics: usize,
 
+    /// Number of independently locked storage shards
+    ///
+    /// With more than one shard, metrics are placed by hashing their name so
+    /// concurrent writers to different names don't contend on one lock.
+    pub shards: usize,
+
+    /// Behavior once `max_stored_metrics` is reached
+    pub drop_strategy: DropStrategy,
+
+    /// How long a stored metric lives, measured from its `timestamp`
+    ///
+    /// Metrics timestamped before `now - ttl`, reading `now` from the
+    /// adapter's clock (see `MockMetricsAdapter::with_clock`), are removed on
+    /// the next store in their shard and before every read. Backfilled
+    /// `record_at` calls older than that expire at once. A counter total
+    /// carries the timestamp of its latest increment, so it expires once it
+    /// hasn't been incremented for `ttl`.
+    pub ttl: Option<Duration>,
+
+    /// Whether `record` enforces the name, label, and value rules
+    ///
+    /// When false, any metric is stored as-is, which is handy for building
+    /// fixtures from malformed real-world data.
+    pub strict_validation: bool,
+
+    /// Size limits `record` validates against when `strict_validation` is on
+    pub validation_limits: ValidationLimits,
+
     /// Whether to simulate recording failures for testing
     pub simulate_failures: bool,
 
     /// Failure probability (0.0 to 1.0) when simulate_failures is true
     pub failure_rate: f64,
+
+    /// Simulated latency applied to every `record` call before storing
+    pub record_latency: Option<Duration>,
+
+    /// Extra random latency, up to this much, added on top of `record_latency`
+    pub record_latency_jitter: Option<Duration>,
+
+    /// Simulated latency applied to every `health_check` call
+    pub health_latency: Option<Duration>,
+
+    /// Shortest timer duration accepted; shorter timers are rejected
+    ///
+    /// An operation that was optimized away, or a timer started and stopped
+    /// around nothing, records zero or a few nanoseconds. `None` (the
+    /// default) accepts any duration.
+    pub min_timer_duration: Option<Duration>,
+
+    /// Longest timer duration accepted; longer timers are rejected
+    ///
+    /// Catches timers that were never stopped or mixed up units. `None`
+    /// (the default) accepts any duration.
+    pub max_timer_duration: Option<Duration>,
+
+    /// Gauge aggregations applied by `current_state`, as (name pattern, aggregation)
+    ///
+    /// Patterns match metric names exactly or with `*` wildcards. The first
+    /// matching pattern wins; unmatched gauges use `GaugeAgg::Last`.
+    pub gauge_aggregations: Vec<(String, GaugeAgg)>,
 }
 
 impl Default for MockMetricsConfig {
@@ -39,9 +181,21 @@ impl Default for MockMetricsConfig {
         Self {
             service_name: "test-service".to_string(),
             store_metrics: true,
+            store_values: true,
             max_stored_metrics: 1000,
+            shards: 1,
+            drop_strategy: DropStrategy::default(),
+            ttl: None,
+            strict_validation: true,
+            validation_limits: ValidationLimits::default(),
             simulate_failures: false,
             failure_rate: 0.0,
+            record_latency: None,
+            record_latency_jitter: None,
+            health_latency: None,
+            min_timer_duration: None,
+            max_timer_duration: None,
+            gauge_aggregations: Vec::new(),
         }
     }
 }
@@ -61,354 +215,2948 @@ impl MockMetricsConfig {
         self
     }
 
+    /// Keep only which series were recorded, not their values
+    pub fn with_store_values(mut self, store_values: bool) -> Self {
+        self.store_values = store_values;
+        self
+    }
+
     /// Set maximum number of stored metrics
     pub fn with_max_stored(mut self, max: usize) -> Self {
         self.max_stored_metrics = max;
         self
     }
 
+    /// Split storage across `shards` independently locked shards
+    ///
+    /// Useful for high-concurrency load tests. Stored metrics keep their
+    /// insertion order within a shard, but global order across shards is lost.
+    pub fn with_shards(mut self, shards: usize) -> Self {
+        self.shards = shards;
+        self
+    }
+
+    /// Set what happens to new metrics once storage is full
+    pub fn with_drop_strategy(mut self, strategy: DropStrategy) -> Self {
+        self.drop_strategy = strategy;
+        self
+    }
+
+    /// Expire stored metrics older than `ttl`
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Validate recorded metrics against custom size limits
+    pub fn with_validation_limits(mut self, limits: ValidationLimits) -> Self {
+        self.validation_limits = limits;
+        self
+    }
+
+    /// Enforce (`true`, the default) or skip validation in `record`
+    pub fn with_strict_validation(mut self, strict: bool) -> Self {
+        self.strict_validation = strict;
+        self
+    }
+
     /// Enable failure simulation for error handling tests
     pub fn with_failures(mut self, failure_rate: f64) -> Self {
         self.simulate_failures = failure_rate > 0.0;
         self.failure_rate = failure_rate.clamp(0.0, 1.0);
         self
     }
+
+    /// Make every `record` call take at least `latency`
+    ///
+    /// Useful for exercising timeout and retry logic against a slow backend.
+    pub fn with_record_latency(mut self, latency: Duration) -> Self {
+        self.record_latency = Some(latency);
+        self
+    }
+
+    /// Add a random extra delay of up to `jitter` to every `record` call
+    pub fn with_record_latency_jitter(mut self, jitter: Duration) -> Self {
+        self.record_latency_jitter = Some(jitter);
+        self
+    }
+
+    /// Make every `health_check` call take at least `latency`
+    pub fn with_health_latency(mut self, latency: Duration) -> Self {
+        self.health_latency = Some(latency);
+        self
+    }
+
+    /// Reject timers shorter than `min` or longer than `max` as likely bugs
+    ///
+    /// `record` fails with a validation error for out-of-range timers;
+    /// `start_timer` recordings out of range are discarded.
+    pub fn with_timer_bounds(mut self, min: Duration, max: Duration) -> Self {
+        self.min_timer_duration = Some(min);
+        self.max_timer_duration = Some(max);
+        self
+    }
+
+    /// Aggregate gauges matching `name_pattern` across instances in `current_state`
+    ///
+    /// The pattern is an exact metric name or may use `*` as a wildcard,
+    /// e.g. `"memory_*"`.
+    pub fn with_gauge_aggregation(
+        mut self,
+        name_pattern: impl Into<String>,
+        agg: GaugeAgg,
+    ) -> Self {
+        self.gauge_aggregations.push((name_pattern.into(), agg));
+        self
+    }
+
+    /// Check the configuration for invalid values and conflicting fields
+    ///
+    /// Storage limits only matter while `store_metrics` is on. Each shard
+    /// holds `max_stored_metrics / shards` metrics rounded up, so at least
+    /// one; more shards than `max_stored_metrics` would exceed the limit and
+    /// is rejected.
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.failure_rate) {
+            return Err(metrics_config_error(
+                "failure_rate",
+                "Failure rate must be between 0.0 and 1.0",
+            ));
+        }
+
+        if self.shards == 0 {
+            return Err(metrics_config_error(
+                "shards",
+                "Number of shards must be greater than 0",
+            ));
+        }
+
+        if self.store_metrics && self.max_stored_metrics == 0 {
+            return Err(metrics_config_error(
+                "max_stored_metrics",
+                format!(
+                    "Maximum stored metrics must be greater than 0 when storing; \
+                     with {:?} every record would be lost",
+                    self.drop_strategy
+                ),
+            ));
+        }
+
+        if self.store_metrics && self.shards > self.max_stored_metrics {
+            return Err(metrics_config_error(
+                "shards",
+                format!(
+                    "{} shards can't share a limit of {} stored metrics; \
+                     every shard holds at least one",
+                    self.shards, self.max_stored_metrics
+                ),
+            ));
+        }
+
+        if let (Some(min), Some(max)) = (self.min_timer_duration, self.max_timer_duration) {
+            if min > max {
+                return Err(metrics_config_error(
+                    "min_timer_duration",
+                    format!(
+                        "Minimum timer duration {min:?} exceeds the maximum {max:?}; \
+                         every timer would be rejected"
+                    ),
+                ));
+            }
+        }
+
+        if self.ttl == Some(Duration::ZERO) {
+            return Err(metrics_config_error(
+                "ttl",
+                "TTL must be greater than zero; every stored metric would expire at once",
+            ));
+        }
+
+        if let Some((pattern, _)) = self
+            .gauge_aggregations
+            .iter()
+            .find(|(pattern, _)| pattern.is_empty())
+        {
+            return Err(metrics_config_error(
+                "gauge_aggregations",
+                format!("Gauge aggregation pattern '{pattern}' must not be empty"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Look up the gauge aggregation configured for a metric name
+    fn gauge_aggregation_for(&self, name: &str) -> GaugeAgg {
+        self.gauge_aggregations
+            .iter()
+            .find(|(pattern, _)| matches_pattern(pattern, name))
+            .map(|(_, agg)| *agg)
+            .unwrap_or_default()
+    }
 }
 
-/// Mock metrics adapter that stores metrics in memory
-///
-/// This adapter provides a complete implementation of MetricsManager for
-/// testing purposes. It stores all recorded metrics in memory and allows
-/// inspection of what was recorded.
+/// Validated builder for `MockMetricsAdapter`
 ///
-/// ## Features
-/// - In-memory storage of all recorded metrics
-/// - Configurable failure simulation for error handling tests
-/// - Thread-safe concurrent access
-/// - Health checking simulation
-/// - Timer guard support with callback pattern
+/// Has a setter for every `MockMetricsConfig` field; `build` checks the
+/// combination with `MockMetricsConfig::validate` and reports conflicts as
+/// configuration errors, instead of the mock misbehaving at runtime.
 ///
-/// ## Example Usage
+/// # Example
 /// ```rust
-/// use tyl_metrics_port::{MockMetricsAdapter, MockMetricsConfig, MetricRequest, MetricsManager};
-///
-/// # tokio_test::block_on(async {
-/// let config = MockMetricsConfig::new("test-app");
-/// let metrics = MockMetricsAdapter::new(config);
+/// use tyl_metrics_port::{DropStrategy, MockAdapterBuilder};
 ///
-/// // Record some metrics
-/// let request = MetricRequest::counter("test_counter", 1.0);
-/// metrics.record(&request).await.unwrap();
+/// let adapter = MockAdapterBuilder::new()
+///     .max_stored_metrics(100)
+///     .shards(4)
+///     .drop_strategy(DropStrategy::RejectNew)
+///     .build()
+///     .unwrap();
+/// assert_eq!(adapter.config().shards, 4);
 ///
-/// // Inspect what was recorded
-/// let stored = metrics.get_stored_metrics().await;
-/// assert_eq!(stored.len(), 1);
-/// # });
+/// assert!(MockAdapterBuilder::new().max_stored_metrics(0).build().is_err());
 /// ```
-pub struct MockMetricsAdapter {
-    /// Configuration for this adapter
+#[derive(Debug, Clone, Default)]
+pub struct MockAdapterBuilder {
     config: MockMetricsConfig,
+    clock: Option<Arc<dyn Clock>>,
+}
 
-    /// Stored metrics for inspection (behind RwLock for thread safety)
-    stored_metrics: Arc<RwLock<Vec<MetricSnapshot>>>,
-
-    /// Health status tracking
-    health_status: Arc<RwLock<HealthStatus>>,
+impl MockAdapterBuilder {
+    /// Start from the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    /// Random number generator for failure simulation
-    rng: Arc<RwLock<fastrand::Rng>>,
-}
+    /// Set the service name
+    pub fn service_name(mut self, service_name: impl Into<String>) -> Self {
+        self.config.service_name = service_name.into();
+        self
+    }
 
-impl MockMetricsAdapter {
-    /// Create a new mock metrics adapter
-    ///
-    /// This is a convenience constructor that doesn't require async.
-    /// Use `new_async` if you need async initialization.
-    pub fn new(config: MockMetricsConfig) -> Self {
-        Self {
-            config,
-            stored_metrics: Arc::new(RwLock::new(Vec::new())),
-            health_status: Arc::new(RwLock::new(HealthStatus::healthy())),
-            rng: Arc::new(RwLock::new(fastrand::Rng::new())),
-        }
+    /// Store metrics in memory for inspection
+    pub fn store_metrics(mut self, store: bool) -> Self {
+        self.config.store_metrics = store;
+        self
     }
 
-    /// Get all stored metrics for inspection in tests
-    ///
-    /// This method allows tests to verify that metrics were recorded correctly.
-    pub async fn get_stored_metrics(&self) -> Vec<MetricSnapshot> {
-        self.stored_metrics.read().await.clone()
+    /// Keep only which series were recorded, not their values
+    pub fn store_values(mut self, store_values: bool) -> Self {
+        self.config.store_values = store_values;
+        self
     }
 
-    /// Clear all stored metrics
-    ///
-    /// Useful for resetting state between tests.
-    pub async fn clear_stored_metrics(&self) {
-        self.stored_metrics.write().await.clear();
+    /// Set the maximum number of stored metrics
+    pub fn max_stored_metrics(mut self, max: usize) -> Self {
+        self.config.max_stored_metrics = max;
+        self
     }
 
-    /// Get metrics count without cloning all data
-    pub async fn get_metrics_count(&self) -> usize {
-        self.stored_metrics.read().await.len()
+    /// Set the number of storage shards
+    pub fn shards(mut self, shards: usize) -> Self {
+        self.config.shards = shards;
+        self
     }
 
-    /// Find metrics by name
-    pub async fn find_metrics_by_name(&self, name: &str) -> Vec<MetricSnapshot> {
-        self.stored_metrics
-            .read()
-            .await
-            .iter()
-            .filter(|m| m.name == name)
-            .cloned()
-            .collect()
+    /// Set what happens to new metrics once storage is full
+    pub fn drop_strategy(mut self, strategy: DropStrategy) -> Self {
+        self.config.drop_strategy = strategy;
+        self
     }
 
-    /// Find metrics by type
-    pub async fn find_metrics_by_type(&self, metric_type: MetricType) -> Vec<MetricSnapshot> {
-        self.stored_metrics
-            .read()
-            .await
-            .iter()
-            .filter(|m| m.metric_type == metric_type)
-            .cloned()
-            .collect()
+    /// Expire stored metrics older than `ttl`
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.config.ttl = Some(ttl);
+        self
     }
 
-    /// Find metrics with specific label
-    pub async fn find_metrics_with_label(&self, key: &str, value: &str) -> Vec<MetricSnapshot> {
-        self.stored_metrics
-            .read()
-            .await
-            .iter()
-            .filter(|m| m.labels.get(key) == Some(&value.to_string()))
-            .cloned()
-            .collect()
+    /// Read the current time from `clock` instead of the system clock
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
     }
 
-    /// Manually set health status for testing
-    pub async fn set_health_status(&self, status: HealthStatus) {
-        *self.health_status.write().await = status;
+    /// Set the size limits `record` validates against
+    pub fn validation_limits(mut self, limits: ValidationLimits) -> Self {
+        self.config.validation_limits = limits;
+        self
     }
 
-    /// Get current configuration
-    pub fn config(&self) -> &MockMetricsConfig {
-        &self.config
+    /// Enforce or skip validation in `record`
+    pub fn strict_validation(mut self, strict: bool) -> Self {
+        self.config.strict_validation = strict;
+        self
     }
 
-    /// Check if we should simulate a failure
-    async fn should_fail(&self) -> bool {
-        if !self.config.simulate_failures {
-            return false;
-        }
+    /// Simulate recording failures with the given probability
+    ///
+    /// Unlike `MockMetricsConfig::with_failures` the rate isn't clamped;
+    /// rates outside 0.0..=1.0 fail `build`.
+    pub fn failure_rate(mut self, failure_rate: f64) -> Self {
+        self.config.simulate_failures = failure_rate > 0.0;
+        self.config.failure_rate = failure_rate;
+        self
+    }
 
-        let random_value = {
-            let mut rng = self.rng.write().await;
-            rng.f64()
-        };
-        random_value < self.config.failure_rate
+    /// Make every `record` call take at least `latency`
+    pub fn record_latency(mut self, latency: Duration) -> Self {
+        self.config.record_latency = Some(latency);
+        self
     }
-}
 
-#[async_trait]
-impl MetricsManager for MockMetricsAdapter {
-    type Config = MockMetricsConfig;
+    /// Add a random extra delay of up to `jitter` to every `record` call
+    pub fn record_latency_jitter(mut self, jitter: Duration) -> Self {
+        self.config.record_latency_jitter = Some(jitter);
+        self
+    }
 
-    async fn new(config: Self::Config) -> Result<Self> {
-        let adapter = Self::new(config);
+    /// Make every `health_check` call take at least `latency`
+    pub fn health_latency(mut self, latency: Duration) -> Self {
+        self.config.health_latency = Some(latency);
+        self
+    }
 
-        // Validate configuration
-        if adapter.config.failure_rate < 0.0 || adapter.config.failure_rate > 1.0 {
-            return Err(metrics_config_error(
-                "failure_rate",
-                "Failure rate must be between 0.0 and 1.0",
-            ));
-        }
+    /// Reject timers shorter than `min` as likely bugs
+    pub fn min_timer_duration(mut self, min: Duration) -> Self {
+        self.config.min_timer_duration = Some(min);
+        self
+    }
 
-        if adapter.config.max_stored_metrics == 0 {
-            return Err(metrics_config_error(
-                "max_stored_metrics",
-                "Maximum stored metrics must be greater than 0",
-            ));
-        }
+    /// Reject timers longer than `max` as likely bugs
+    pub fn max_timer_duration(mut self, max: Duration) -> Self {
+        self.config.max_timer_duration = Some(max);
+        self
+    }
 
-        Ok(adapter)
+    /// Aggregate gauges matching `name_pattern` across instances in `current_state`
+    pub fn gauge_aggregation(mut self, name_pattern: impl Into<String>, agg: GaugeAgg) -> Self {
+        self.config
+            .gauge_aggregations
+            .push((name_pattern.into(), agg));
+        self
     }
 
-    async fn record(&self, request: &MetricRequest) -> Result<()> {
-        // Check if we should simulate a failure
-        if self.should_fail().await {
-            return Err(metrics_recording_error(
-                request.name(),
-                "Simulated recording failure",
-            ));
-        }
+    /// Validate and return the configuration
+    pub fn build_config(self) -> Result<MockMetricsConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
 
-        // Validate the metric request
-        validate_metric_name(request.name())?;
-        validate_labels(request.labels())?;
+    /// Validate the configuration and create the adapter
+    pub fn build(self) -> Result<MockMetricsAdapter> {
+        let clock = self.clock.clone();
+        let adapter = MockMetricsAdapter::new(self.build_config()?);
+        Ok(match clock {
+            Some(clock) => adapter.with_clock(clock),
+            None => adapter,
+        })
+    }
+}
 
-        match request.metric_type() {
-            MetricType::Counter => validate_counter_value(request.value())?,
-            _ => validate_metric_value(request.value())?,
+/// Match a name against a pattern where `*` matches any run of characters
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
         }
+    }
 
-        // Store the metric if configured to do so
-        if self.config.store_metrics {
-            let mut stored = self.stored_metrics.write().await;
+    rest.ends_with(last)
+}
 
-            // Prevent memory leaks by enforcing max storage limit
-            if stored.len() >= self.config.max_stored_metrics {
-                stored.remove(0); // Remove oldest metric
-            }
+/// Check a timer value (in seconds) against optional duration bounds
+fn check_timer_bounds(
+    request: &MetricRequest,
+    min: Option<Duration>,
+    max: Option<Duration>,
+) -> Result<()> {
+    if request.metric_type() != &MetricType::Timer {
+        return Ok(());
+    }
 
-            stored.push(MetricSnapshot::from(request));
+    let seconds = request.value();
+    if let Some(min) = min.filter(|min| seconds < min.as_secs_f64()) {
+        return Err(metrics_error(
+            "timer_duration",
+            format!(
+                "Timer {} recorded {}s, below the {:?} minimum",
+                request.name(),
+                seconds,
+                min
+            ),
+        ));
+    }
+    if let Some(max) = max.filter(|max| seconds > max.as_secs_f64()) {
+        return Err(metrics_error(
+            "timer_duration",
+            format!(
+                "Timer {} recorded {}s, above the {:?} maximum",
+                request.name(),
+                seconds,
+                max
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Identify a series by metric name and labels sorted by key
+fn series_key(name: &str, labels: &Labels) -> (String, Vec<(String, String)>) {
+    let mut pairs: Vec<(String, String)> =
+        labels.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    pairs.sort();
+    (name.to_string(), pairs)
+}
+
+/// Identity of a series, as kept by the metadata-only store
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SeriesKey {
+    name: String,
+    metric_type: MetricType,
+    labels: Vec<(String, String)>,
+}
+
+impl SeriesKey {
+    fn new(snapshot: &MetricSnapshot) -> Self {
+        let (name, labels) = series_key(&snapshot.name, &snapshot.labels);
+        Self {
+            name,
+            metric_type: snapshot.metric_type,
+            labels,
+        }
+    }
+
+    /// Snapshot standing in for the series, without a value or timestamp
+    fn placeholder(&self) -> MetricSnapshot {
+        MetricSnapshot::new(
+            self.name.clone(),
+            self.metric_type,
+            MetricValue::Single(0.0),
+            self.labels.iter().cloned().collect(),
+        )
+        .with_timestamp(0)
+    }
+}
+
+/// One storage shard: raw records plus a running total per incremented series
+///
+/// Totals live outside the record queue, so `DropOldest` never evicts a
+/// series' whole count and an increment updates its total in O(1).
+#[derive(Default)]
+struct Shard {
+    /// Snapshots in insertion order
+    records: VecDeque<MetricSnapshot>,
+
+    /// No record is older than this, so TTL expiry can skip the scan until
+    /// the cutoff passes it
+    oldest_record: Option<u64>,
+
+    /// Running totals of `counter_inc` increments, in first-seen order
+    totals: Vec<MetricSnapshot>,
+
+    /// Position of each series' total in `totals`
+    total_index: HashMap<SeriesKey, usize>,
+}
+
+impl Shard {
+    fn len(&self) -> usize {
+        self.records.len() + self.totals.len()
+    }
+
+    /// Records in insertion order, then totals in first-seen order
+    fn iter(&self) -> impl Iterator<Item = &MetricSnapshot> {
+        self.records.iter().chain(&self.totals)
+    }
+
+    /// Append a record, keeping `oldest_record` a lower bound
+    fn push_record(&mut self, snapshot: MetricSnapshot) {
+        self.oldest_record = Some(
+            self.oldest_record
+                .map_or(snapshot.timestamp, |oldest| oldest.min(snapshot.timestamp)),
+        );
+        self.records.push_back(snapshot);
+    }
+
+    /// Remove records timestamped before `cutoff`, returning how many
+    ///
+    /// Backfilled records can be older than ones stored before them, so
+    /// expired records may sit anywhere in the queue.
+    fn expire_records(&mut self, cutoff: u64) -> usize {
+        if !self.oldest_record.is_some_and(|oldest| oldest < cutoff) {
+            return 0;
+        }
+
+        let before = self.records.len();
+        self.records.retain(|record| record.timestamp >= cutoff);
+        self.oldest_record = self.records.iter().map(|record| record.timestamp).min();
+        before - self.records.len()
+    }
+
+    /// Remove totals whose latest increment is timestamped before `cutoff`,
+    /// returning how many
+    fn expire_totals(&mut self, cutoff: u64) -> usize {
+        let before = self.totals.len();
+        self.totals.retain(|total| total.timestamp >= cutoff);
+        if self.totals.len() != before {
+            self.total_index = self
+                .totals
+                .iter()
+                .enumerate()
+                .map(|(position, total)| (SeriesKey::new(total), position))
+                .collect();
+        }
+        before - self.totals.len()
+    }
+
+    fn clear(&mut self) {
+        self.records.clear();
+        self.oldest_record = None;
+        self.totals.clear();
+        self.total_index.clear();
+    }
+}
+
+/// Observer invoked with every successfully recorded metric
+type RecordCallback = Box<dyn Fn(&MetricSnapshot) + Send + Sync>;
+
+/// In-memory snapshot storage split across independently locked shards
+///
+/// With a single shard this is a plain insertion-ordered list. With more,
+/// each metric name hashes to one shard, so insertion order is preserved per
+/// shard but not globally.
+///
+/// Timer guards record from a synchronous drop, so they queue snapshots in
+/// `pending`; the queue is drained into the shards before every read and on
+/// `flush`.
+///
+/// When values aren't stored, the shards stay empty and `series` records
+/// which series were seen instead.
+struct MetricStore {
+    /// Shards holding snapshots in insertion order
+    shards: Vec<RwLock<Shard>>,
+
+    /// Maximum number of snapshots held by each shard
+    shard_capacity: usize,
+
+    /// Behavior when a shard is full
+    drop_strategy: DropStrategy,
+
+    /// Snapshots queued by timer guards, not yet moved into the shards, and
+    /// whether each is an increment
+    pending: std::sync::Mutex<Vec<(MetricSnapshot, bool)>>,
+
+    /// Number of stored snapshots evicted to make room for newer ones
+    evictions: AtomicUsize,
+
+    /// How long snapshots live, from their timestamp
+    ttl: Option<Duration>,
+
+    /// Time source for TTL expiry
+    clock: Arc<dyn Clock>,
+
+    /// Number of stored snapshots removed by TTL expiry
+    expirations: AtomicUsize,
+
+    /// Series seen, when only metadata is stored
+    series: Option<std::sync::Mutex<HashSet<SeriesKey>>>,
+}
+
+impl MetricStore {
+    fn new(config: &MockMetricsConfig, clock: Arc<dyn Clock>) -> Self {
+        let shards = config.shards.max(1);
+        Self {
+            shards: (0..shards).map(|_| RwLock::new(Shard::default())).collect(),
+            shard_capacity: (config.max_stored_metrics + shards - 1) / shards,
+            drop_strategy: config.drop_strategy,
+            pending: std::sync::Mutex::new(Vec::new()),
+            evictions: AtomicUsize::new(0),
+            ttl: config.ttl,
+            clock,
+            expirations: AtomicUsize::new(0),
+            series: (!config.store_values).then(|| std::sync::Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Queue a request from synchronous code
+    fn push_pending(&self, request: &MetricRequest) {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push((MetricSnapshot::from(request), request.is_increment()));
+    }
+
+    /// Oldest timestamp a stored entry may have, if a TTL is set
+    fn ttl_cutoff(&self) -> Option<u64> {
+        let ttl = self.ttl?;
+        Some(self.clock.now_nanos().saturating_sub(ttl.as_nanos() as u64))
+    }
+
+    /// Bring storage up to date before a read
+    ///
+    /// Drains the timer queue, then removes records and totals older than
+    /// the TTL from every shard.
+    async fn settle(&self) {
+        self.drain_pending().await;
+
+        let Some(cutoff) = self.ttl_cutoff() else {
+            return;
+        };
+        for shard in &self.shards {
+            let mut shard = shard.write().await;
+            let expired = shard.expire_records(cutoff) + shard.expire_totals(cutoff);
+            self.expirations.fetch_add(expired, Ordering::Relaxed);
+        }
+    }
+
+    /// Move every queued snapshot into the shards, in queue order
+    async fn drain_pending(&self) {
+        let pending = std::mem::take(
+            &mut *self
+                .pending
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+
+        for (snapshot, increment) in pending {
+            // Timers have no caller to report a rejection to
+            let _ = self.push(snapshot, increment).await;
+        }
+    }
+
+    /// Pick the shard that owns a metric name
+    fn shard_for(&self, name: &str) -> &RwLock<Shard> {
+        if self.shards.len() == 1 {
+            return &self.shards[0];
+        }
+
+        // Name reads scan only this shard, so place by name alone
+        let hash = series_hash(name, &Labels::new());
+        &self.shards[(hash % self.shards.len() as u64) as usize]
+    }
+
+    /// Lock the metadata-only series set, if values aren't stored
+    fn series(&self) -> Option<std::sync::MutexGuard<'_, HashSet<SeriesKey>>> {
+        self.series.as_ref().map(|series| {
+            series
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        })
+    }
+
+    /// Placeholders for every series seen, sorted by name and labels
+    fn placeholders(&self, series: &HashSet<SeriesKey>) -> Vec<MetricSnapshot> {
+        let mut keys: Vec<&SeriesKey> = series.iter().collect();
+        keys.sort_by(|a, b| (&a.name, &a.labels).cmp(&(&b.name, &b.labels)));
+        keys.into_iter().map(SeriesKey::placeholder).collect()
+    }
+
+    /// Store a snapshot, applying the drop strategy when the shard is full
+    ///
+    /// An `increment` is added to its series' running total instead of being
+    /// stored as a record.
+    async fn push(&self, snapshot: MetricSnapshot, increment: bool) -> Result<()> {
+        if let Some(mut series) = self.series() {
+            series.insert(SeriesKey::new(&snapshot));
+            return Ok(());
+        }
+
+        let mut shard = self.shard_for(&snapshot.name).write().await;
+        self.expire_for_insert(&mut shard);
+        self.insert(&mut shard, snapshot, increment)
+    }
+
+    /// Store records of one metric together
+    ///
+    /// With `DropStrategy::RejectNew`, either every record fits and is
+    /// stored or none is.
+    async fn push_records(&self, name: &str, snapshots: Vec<MetricSnapshot>) -> Result<()> {
+        if let Some(mut series) = self.series() {
+            series.extend(snapshots.iter().map(SeriesKey::new));
+            return Ok(());
+        }
+
+        let mut shard = self.shard_for(name).write().await;
+        self.expire_for_insert(&mut shard);
+        if self.drop_strategy == DropStrategy::RejectNew
+            && shard.len() + snapshots.len() > self.shard_capacity
+        {
+            return Err(metrics_capacity_error(
+                name,
+                format!(
+                    "Metric storage has no room for {} observations",
+                    snapshots.len()
+                ),
+            ));
         }
 
+        for snapshot in snapshots {
+            self.insert(&mut shard, snapshot, false)?;
+        }
         Ok(())
     }
 
-    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
-        let stored_metrics = self.stored_metrics.clone();
-        let config = self.config.clone();
-        let name = name.to_string();
+    /// Expire records before an insert; totals are only expired on reads,
+    /// which scan them anyway
+    fn expire_for_insert(&self, shard: &mut Shard) {
+        if let Some(cutoff) = self.ttl_cutoff() {
+            let expired = shard.expire_records(cutoff);
+            self.expirations.fetch_add(expired, Ordering::Relaxed);
+        }
+    }
 
-        TimerGuard::new(name, labels, move |request| {
-            // This is a synchronous callback, so we need to handle async recording
-            // In a real implementation, you might want to use a channel or similar
-            let stored_metrics = stored_metrics.clone();
-            let config = config.clone();
-
-            tokio::task::spawn(async move {
-                if config.store_metrics {
-                    let mut stored = stored_metrics.write().await;
-
-                    // Enforce storage limit
-                    if stored.len() >= config.max_stored_metrics {
-                        stored.remove(0);
-                    }
+    /// Insert into a locked shard, applying the drop strategy when it is full
+    fn insert(&self, shard: &mut Shard, snapshot: MetricSnapshot, increment: bool) -> Result<()> {
+        // Increments add to the series' running total
+        let key = increment.then(|| SeriesKey::new(&snapshot));
+        if let Some(key) = &key {
+            if let Some(&position) = shard.total_index.get(key) {
+                let total = &mut shard.totals[position];
+                if let (MetricValue::Single(sum), MetricValue::Single(delta)) =
+                    (&mut total.value, &snapshot.value)
+                {
+                    *sum += delta;
+                }
+                total.timestamp = total.timestamp.max(snapshot.timestamp);
+                return Ok(());
+            }
+        }
+
+        // Prevent memory leaks by enforcing max storage limit. Totals are never
+        // evicted, so a shard full of totals can only make room by dropping new
+        // records.
+        if shard.len() >= self.shard_capacity {
+            match self.drop_strategy {
+                DropStrategy::DropOldest if !shard.records.is_empty() => {
+                    shard.records.pop_front();
+                    self.evictions.fetch_add(1, Ordering::Relaxed);
+                }
+                DropStrategy::DropOldest | DropStrategy::DropNewest => return Ok(()),
+                DropStrategy::RejectNew => {
+                    return Err(metrics_capacity_error(
+                        &snapshot.name,
+                        "Metric storage is full",
+                    ));
+                }
+            }
+        }
+
+        match key {
+            Some(key) => {
+                // The total covers every increment since the first, so it is
+                // exported as a cumulative sum
+                let total = MetricSnapshot {
+                    start_timestamp: snapshot.start_timestamp.or(Some(snapshot.timestamp)),
+                    temporality: Some(AggregationTemporality::Cumulative),
+                    ..snapshot
+                };
+                let position = shard.totals.len();
+                shard.totals.push(total);
+                shard.total_index.insert(key, position);
+            }
+            None => shard.push_record(snapshot),
+        }
+        Ok(())
+    }
+
+    /// Clone every stored snapshot that matches a predicate, shard by shard
+    async fn collect_where(
+        &self,
+        predicate: impl Fn(&MetricSnapshot) -> bool,
+    ) -> Vec<MetricSnapshot> {
+        self.settle().await;
+
+        if let Some(series) = self.series() {
+            return self
+                .placeholders(&series)
+                .into_iter()
+                .filter(|m| predicate(m))
+                .collect();
+        }
+
+        let mut matching = Vec::new();
+        for shard in &self.shards {
+            matching.extend(shard.read().await.iter().filter(|m| predicate(m)).cloned());
+        }
+        matching
+    }
+
+    /// Sum the `Single` values of matching counters without cloning
+    ///
+    /// Every snapshot of a name lives in one shard, so a single read lock
+    /// sees a consistent total.
+    async fn counter_total(&self, name: &str, labels: Option<&Labels>) -> f64 {
+        self.settle().await;
+
+        self.shard_for(name)
+            .read()
+            .await
+            .iter()
+            .filter(|m| m.name == name && m.metric_type == MetricType::Counter)
+            .filter(|m| labels.map_or(true, |labels| &m.labels == labels))
+            .filter_map(|m| match m.value {
+                MetricValue::Single(value) => Some(value),
+                MetricValue::Histogram { .. } => None,
+            })
+            .sum()
+    }
+
+    /// Value of the matching gauge with the highest timestamp
+    ///
+    /// Snapshots are scanned in insertion order, so on equal timestamps the
+    /// last stored wins.
+    async fn latest_gauge(&self, name: &str, labels: Option<&Labels>) -> Option<f64> {
+        self.settle().await;
+
+        self.shard_for(name)
+            .read()
+            .await
+            .iter()
+            .filter(|m| m.name == name && m.metric_type == MetricType::Gauge)
+            .filter(|m| labels.map_or(true, |labels| &m.labels == labels))
+            .filter_map(|m| match m.value {
+                MetricValue::Single(value) => Some((m.timestamp, value)),
+                MetricValue::Histogram { .. } => None,
+            })
+            .fold(
+                None,
+                |latest: Option<(u64, f64)>, (timestamp, value)| match latest {
+                    Some((latest_timestamp, _)) if latest_timestamp > timestamp => latest,
+                    _ => Some((timestamp, value)),
+                },
+            )
+            .map(|(_, value)| value)
+    }
+
+    /// Clone up to `limit` snapshots starting at `offset`, in `collect_where` order
+    ///
+    /// Shards are locked one at a time and only the page is cloned.
+    async fn page(&self, offset: usize, limit: usize) -> Vec<MetricSnapshot> {
+        self.settle().await;
+
+        if let Some(series) = self.series() {
+            return self
+                .placeholders(&series)
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .collect();
+        }
+
+        let mut skip = offset;
+        let mut page = Vec::new();
+        for shard in &self.shards {
+            if page.len() == limit {
+                break;
+            }
+            let shard = shard.read().await;
+            if skip >= shard.len() {
+                skip -= shard.len();
+                continue;
+            }
+            page.extend(shard.iter().skip(skip).take(limit - page.len()).cloned());
+            skip = 0;
+        }
+        page
+    }
+
+    async fn len(&self) -> usize {
+        self.settle().await;
+
+        if let Some(series) = self.series() {
+            return series.len();
+        }
+
+        let mut len = 0;
+        for shard in &self.shards {
+            len += shard.read().await.len();
+        }
+        len
+    }
+
+    /// Number of distinct series (name, type, and labels) stored
+    async fn distinct_series(&self) -> usize {
+        self.settle().await;
+
+        if let Some(series) = self.series() {
+            return series.len();
+        }
+
+        let mut distinct = HashSet::new();
+        for shard in &self.shards {
+            distinct.extend(shard.read().await.iter().map(SeriesKey::new));
+        }
+        distinct.len()
+    }
+
+    async fn clear(&self) {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+        if let Some(mut series) = self.series() {
+            series.clear();
+        }
+
+        for shard in &self.shards {
+            shard.write().await.clear();
+        }
+    }
+}
+
+/// Stream of recorded snapshots returned by `MockMetricsAdapter::subscribe`
+pub struct MetricSubscription {
+    inner: BroadcastStream<MetricSnapshot>,
+    lagged: u64,
+}
+
+impl MetricSubscription {
+    /// Get how many snapshots were skipped because this reader fell behind
+    pub fn lagged(&self) -> u64 {
+        self.lagged
+    }
+}
+
+impl Stream for MetricSubscription {
+    type Item = MetricSnapshot;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Err(BroadcastStreamRecvError::Lagged(skipped)))) => {
+                    self.lagged += skipped;
+                }
+                Poll::Ready(Some(Ok(snapshot))) => return Poll::Ready(Some(snapshot)),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Mock metrics adapter that stores metrics in memory
+///
+/// This adapter provides a complete implementation of MetricsManager for
+/// testing purposes. It stores all recorded metrics in memory and allows
+/// inspection of what was recorded.
+///
+/// ## Features
+/// - In-memory storage of all recorded metrics
+/// - Configurable failure simulation for error handling tests
+/// - Thread-safe concurrent access
+/// - Health checking simulation
+/// - Timer guard support with callback pattern
+///
+/// ## Example Usage
+/// ```rust
+/// use tyl_metrics_port::{MockMetricsAdapter, MockMetricsConfig, MetricRequest, MetricsManager};
+///
+/// # tokio_test::block_on(async {
+/// let config = MockMetricsConfig::new("test-app");
+/// let metrics = MockMetricsAdapter::new(config);
+///
+/// // Record some metrics
+/// let request = MetricRequest::counter("test_counter", 1.0);
+/// metrics.record(&request).await.unwrap();
+///
+/// // Inspect what was recorded
+/// let stored = metrics.get_stored_metrics().await;
+/// assert_eq!(stored.len(), 1);
+/// # });
+/// ```
+pub struct MockMetricsAdapter {
+    /// Configuration for this adapter
+    config: MockMetricsConfig,
+
+    /// Stored metrics for inspection (sharded behind RwLocks for thread safety)
+    stored_metrics: Arc<MetricStore>,
+
+    /// Health status tracking
+    health_status: Arc<RwLock<HealthStatus>>,
+
+    /// Random number generator for failure simulation
+    rng: Arc<RwLock<fastrand::Rng>>,
+
+    /// Observers registered with `on_record`
+    record_callbacks: Arc<std::sync::RwLock<Vec<RecordCallback>>>,
+
+    /// Broadcasts recorded snapshots to `subscribe` streams
+    subscribers: broadcast::Sender<MetricSnapshot>,
+
+    /// Number of full validations run by `record`
+    validations: Arc<AtomicU64>,
+
+    /// Number of record calls, successful or not
+    record_calls: Arc<AtomicU64>,
+
+    /// Remaining deterministic failures queued by `fail_next_records`
+    pending_failures: Arc<AtomicU64>,
+}
+
+impl MockMetricsAdapter {
+    /// Create a new mock metrics adapter
+    ///
+    /// This is a convenience constructor that doesn't require async.
+    /// Use `new_async` if you need async initialization.
+    pub fn new(config: MockMetricsConfig) -> Self {
+        Self {
+            stored_metrics: Arc::new(MetricStore::new(&config, Arc::new(SystemClock))),
+            config,
+            health_status: Arc::new(RwLock::new(HealthStatus::healthy())),
+            rng: Arc::new(RwLock::new(fastrand::Rng::new())),
+            record_callbacks: Arc::new(std::sync::RwLock::new(Vec::new())),
+            subscribers: broadcast::channel(SUBSCRIPTION_CAPACITY).0,
+            validations: Arc::new(AtomicU64::new(0)),
+            record_calls: Arc::new(AtomicU64::new(0)),
+            pending_failures: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Read the current time for TTL expiry from `clock`
+    ///
+    /// Replaces the storage, so call this before recording, e.g. with a
+    /// `MockClock` to expire metrics without waiting in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.stored_metrics = Arc::new(MetricStore::new(&self.config, clock));
+        self
+    }
+
+    /// Start a validated builder for the mock adapter
+    pub fn builder() -> MockAdapterBuilder {
+        MockAdapterBuilder::new()
+    }
+
+    /// Get all stored metrics for inspection in tests
+    ///
+    /// This method allows tests to verify that metrics were recorded correctly.
+    /// With a single shard metrics are returned in insertion order; with
+    /// multiple shards they are returned shard by shard, so only the relative
+    /// order of metrics sharing a name is guaranteed.
+    pub async fn get_stored_metrics(&self) -> Vec<MetricSnapshot> {
+        self.stored_metrics.collect_where(|_| true).await
+    }
+
+    /// Clear all stored metrics
+    ///
+    /// Useful for resetting state between tests.
+    pub async fn clear_stored_metrics(&self) {
+        self.stored_metrics.clear().await;
+    }
+
+    /// Save every stored metric to `path` as JSON
+    ///
+    /// Writes the same records `get_stored_metrics` returns, as a
+    /// pretty-printed JSON array, overwriting any existing file. Read them
+    /// back with `load_snapshots`, e.g. to attach a dump from a flaky test to
+    /// a bug report and replay it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{load_snapshots, MetricRequest, MetricsManager, MockMetricsAdapter};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let metrics = MockMetricsAdapter::default();
+    /// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+    ///
+    /// let path = std::env::temp_dir().join("tyl-metrics-save-example.json");
+    /// metrics.save_snapshots(&path).await.unwrap();
+    /// assert_eq!(load_snapshots(&path).await.unwrap(), metrics.get_stored_metrics().await);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// # });
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn save_snapshots(&self, path: &Path) -> Result<()> {
+        save_snapshots(path, &self.get_stored_metrics().await).await
+    }
+
+    /// Get how many stored metrics were evicted to make room for new ones
+    ///
+    /// Only `DropStrategy::DropOldest` evicts; `DropNewest` and `RejectNew`
+    /// turn the new metric away instead, so the count stays at zero. The
+    /// count is not reset by `clear_stored_metrics`.
+    pub fn eviction_count(&self) -> usize {
+        self.stored_metrics.evictions.load(Ordering::Relaxed)
+    }
+
+    /// Get how many stored metrics expired under `MockMetricsConfig::ttl`
+    ///
+    /// Counted separately from `eviction_count`, and likewise not reset by
+    /// `clear_stored_metrics`.
+    pub fn expired_count(&self) -> usize {
+        self.stored_metrics.expirations.load(Ordering::Relaxed)
+    }
+
+    /// Get metrics count without cloning all data
+    ///
+    /// When values aren't stored (`MockMetricsConfig::store_values`), this
+    /// counts series rather than records.
+    pub async fn get_metrics_count(&self) -> usize {
+        self.stored_metrics.len().await
+    }
+
+    /// Get how many distinct series (name, type, and labels) are stored
+    ///
+    /// Works whether or not values are stored; with values, it is computed
+    /// from the stored records, so evicted and expired records don't count.
+    pub async fn distinct_series(&self) -> usize {
+        self.stored_metrics.distinct_series().await
+    }
+
+    /// Find metrics by name
+    pub async fn find_metrics_by_name(&self, name: &str) -> Vec<MetricSnapshot> {
+        self.stored_metrics.collect_where(|m| m.name == name).await
+    }
+
+    /// Group the stored metrics named `name` by their value for `label_key`
+    ///
+    /// Metrics without the label are grouped under `""`, as Prometheus
+    /// treats a missing label as empty. Within a group, metrics keep the
+    /// `find_metrics_by_name` order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let metrics = MockMetricsAdapter::default();
+    /// metrics.record(&MetricRequest::counter("requests", 1.0).with_label("endpoint", "/users")).await.unwrap();
+    /// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+    ///
+    /// let groups = metrics.group_by_label("requests", "endpoint").await;
+    /// assert_eq!(groups["/users"].len(), 1);
+    /// assert_eq!(groups[""].len(), 1);
+    /// # });
+    /// ```
+    pub async fn group_by_label(
+        &self,
+        name: &str,
+        label_key: &str,
+    ) -> HashMap<String, Vec<MetricSnapshot>> {
+        let mut groups: HashMap<String, Vec<MetricSnapshot>> = HashMap::new();
+        for snapshot in self.find_metrics_by_name(name).await {
+            let value = snapshot.labels.get(label_key).cloned().unwrap_or_default();
+            groups.entry(value).or_default().push(snapshot);
+        }
+        groups
+    }
+
+    /// Sum a counter's stored values
+    ///
+    /// Adds the running totals kept for `counter_inc` increments and the
+    /// values of counters recorded as given. With `labels`, only series whose
+    /// labels match exactly are summed. Records of other types under the same
+    /// name are ignored. Increment totals are never evicted, but counters
+    /// recorded as given only count while stored, so those reflect the
+    /// storage limit.
+    pub async fn counter_total(&self, name: &str, labels: Option<&Labels>) -> f64 {
+        self.stored_metrics.counter_total(name, labels).await
+    }
+
+    /// Get the most recent value of a gauge
+    ///
+    /// Returns the value with the highest timestamp, the last stored one
+    /// winning ties, or `None` if no gauge matches. With `labels`, only
+    /// records whose labels match exactly are considered.
+    pub async fn latest_gauge(&self, name: &str, labels: Option<&Labels>) -> Option<f64> {
+        self.stored_metrics.latest_gauge(name, labels).await
+    }
+
+    /// Find metrics by type
+    pub async fn find_metrics_by_type(&self, metric_type: MetricType) -> Vec<MetricSnapshot> {
+        self.stored_metrics
+            .collect_where(|m| m.metric_type == metric_type)
+            .await
+    }
+
+    /// Find metrics with specific label
+    pub async fn find_metrics_with_label(&self, key: &str, value: &str) -> Vec<MetricSnapshot> {
+        self.stored_metrics
+            .collect_where(|m| m.labels.get(key).map(String::as_str) == Some(value))
+            .await
+    }
+
+    /// Collect every `Single` value recorded under a metric name
+    async fn single_values(&self, name: &str) -> Vec<f64> {
+        self.find_metrics_by_name(name)
+            .await
+            .iter()
+            .filter_map(|m| match m.value {
+                MetricValue::Single(value) => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Compute descriptive statistics over all values recorded under a name
+    ///
+    /// Only `Single` values are considered, regardless of labels. Returns
+    /// `None` when nothing matching was recorded.
+    pub async fn stats(&self, name: &str) -> Option<MetricStats> {
+        MetricStats::from_values(&self.single_values(name).await)
+    }
+
+    /// Estimate a quantile of all values recorded under a name
+    ///
+    /// Uses linear interpolation between the closest ranks of the raw
+    /// observations, so no bucket configuration is needed. `quantile` must be
+    /// within `0.0..=1.0`; returns `None` otherwise or when nothing matching
+    /// was recorded.
+    pub async fn histogram_quantile(&self, name: &str, quantile: f64) -> Option<f64> {
+        if !(0.0..=1.0).contains(&quantile) {
+            return None;
+        }
+
+        let mut values = self.single_values(name).await;
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+
+        let rank = quantile * (values.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let weight = rank - lower as f64;
+        Some(values[lower] + (values[upper] - values[lower]) * weight)
+    }
+
+    /// Suggest histogram bucket bounds from the values recorded under a name
+    ///
+    /// Collects the finite `Single` values recorded under `name`, regardless
+    /// of labels, and places `target_buckets` upper bounds at evenly spaced
+    /// quantiles (nearest rank), so each bucket holds roughly the same number
+    /// of observations; the last bound is the maximum. Repeated values
+    /// collapse into one bound, so skewed data may yield fewer buckets. The
+    /// result is validated with `HistogramBuckets::custom` (sorted, unique,
+    /// ending with `+Inf`) and can be passed to `fold_histogram`. Returns
+    /// `None` when `target_buckets` is zero or fewer than `target_buckets`
+    /// values were recorded.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let metrics = MockMetricsAdapter::default();
+    /// for value in [1.0, 2.0, 3.0, 4.0] {
+    ///     metrics.record(&MetricRequest::histogram("payload_bytes", value)).await.unwrap();
+    /// }
+    ///
+    /// let buckets = metrics.suggest_buckets("payload_bytes", 2).await.unwrap();
+    /// assert_eq!(buckets, vec![2.0, 4.0, f64::INFINITY]);
+    /// # });
+    /// ```
+    pub async fn suggest_buckets(&self, name: &str, target_buckets: usize) -> Option<Vec<f64>> {
+        let mut values: Vec<f64> = self
+            .single_values(name)
+            .await
+            .into_iter()
+            .filter(|value| value.is_finite())
+            .collect();
+        if target_buckets == 0 || values.len() < target_buckets {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+
+        let bounds: Vec<f64> = (1..=target_buckets)
+            .map(|i| values[(i * values.len() + target_buckets - 1) / target_buckets - 1])
+            .collect();
+        HistogramBuckets::custom(&bounds).ok()
+    }
+
+    /// Fold the raw observations of one series into a histogram value
+    ///
+    /// Collects the `Single` values recorded under `name` with exactly
+    /// `labels`, and counts them into cumulative buckets using the given upper
+    /// bounds (validated with `HistogramBuckets::custom`, so negative bounds
+    /// work). A `+Inf` bucket is appended unless the bounds already end with
+    /// one. Returns `None` when the bounds are invalid or nothing matching was
+    /// recorded.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{HistogramBuckets, Labels, MetricRequest, MetricValue, MetricsManager, MockMetricsAdapter};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let metrics = MockMetricsAdapter::default();
+    /// metrics.record(&MetricRequest::histogram("request_duration", 0.2)).await.unwrap();
+    ///
+    /// let folded = metrics
+    ///     .fold_histogram("request_duration", &Labels::new(), &HistogramBuckets::latency())
+    ///     .await;
+    /// assert!(matches!(folded, Some(MetricValue::Histogram { count: 1, .. })));
+    /// # });
+    /// ```
+    pub async fn fold_histogram(
+        &self,
+        name: &str,
+        labels: &Labels,
+        buckets: &[f64],
+    ) -> Option<MetricValue> {
+        let bounds = HistogramBuckets::custom(buckets).ok()?;
+
+        let observations: Vec<f64> = self
+            .stored_metrics
+            .collect_where(|m| m.name == name && &m.labels == labels)
+            .await
+            .iter()
+            .filter_map(|m| match m.value {
+                MetricValue::Single(value) => Some(value),
+                _ => None,
+            })
+            .collect();
+
+        if observations.is_empty() {
+            return None;
+        }
+
+        Some(MetricValue::from_observations(&observations, &bounds))
+    }
+
+    /// Get the current state of every recorded series
+    ///
+    /// Unlike `get_stored_metrics`, which lists every raw record, this returns
+    /// one snapshot per series (name + labels): counters are summed and every
+    /// other type keeps its latest value. Gauges matching a configured
+    /// `GaugeAgg` are then combined across their `instance` label, producing a
+    /// single series without that label.
+    pub async fn current_state(&self) -> Vec<MetricSnapshot> {
+        let stored = self.get_stored_metrics().await;
+
+        // Reduce raw records to one snapshot per series, keeping first-seen order
+        let mut order = Vec::new();
+        let mut series: HashMap<_, MetricSnapshot> = HashMap::new();
+        for snapshot in stored.iter() {
+            let key = series_key(&snapshot.name, &snapshot.labels);
+            match series.get_mut(&key) {
+                Some(current) => match (&mut current.value, &snapshot.value) {
+                    (MetricValue::Single(total), MetricValue::Single(value))
+                        if snapshot.metric_type == MetricType::Counter =>
+                    {
+                        *total += value;
+                        current.timestamp = current.timestamp.max(snapshot.timestamp);
+                    }
+                    _ => *current = snapshot.clone(),
+                },
+                None => {
+                    order.push(key.clone());
+                    series.insert(key, snapshot.clone());
+                }
+            }
+        }
+
+        // Combine gauges across instances where an aggregation is configured
+        let mut state = Vec::new();
+        let mut groups: HashMap<_, (usize, Vec<f64>)> = HashMap::new();
+        for key in order {
+            let Some(snapshot) = series.remove(&key) else {
+                continue;
+            };
+            let agg = self.config.gauge_aggregation_for(&snapshot.name);
+
+            let value = match snapshot.value {
+                MetricValue::Single(value) => value,
+                _ => {
+                    state.push(snapshot);
+                    continue;
+                }
+            };
+
+            if snapshot.metric_type != MetricType::Gauge
+                || agg == GaugeAgg::Last
+                || !snapshot.labels.contains_key(INSTANCE_LABEL)
+            {
+                state.push(snapshot);
+                continue;
+            }
+
+            let mut labels = snapshot.labels.clone();
+            labels.remove(INSTANCE_LABEL);
+            let group_key = series_key(&snapshot.name, &labels);
+
+            match groups.get_mut(&group_key) {
+                Some((index, values)) => {
+                    values.push(value);
+                    let combined = &mut state[*index];
+                    combined.value = MetricValue::Single(agg.combine(values));
+                    combined.timestamp = combined.timestamp.max(snapshot.timestamp);
+                }
+                None => {
+                    groups.insert(group_key, (state.len(), vec![value]));
+                    state.push(MetricSnapshot { labels, ..snapshot });
+                }
+            }
+        }
+
+        state
+    }
+
+    /// Get one aggregated snapshot per series, as a production backend reports
+    ///
+    /// Folds the raw records `get_stored_metrics` lists into one snapshot per
+    /// series (name + labels), in first-seen order, using
+    /// `MetricSnapshot::merge`:
+    /// - Counters are summed
+    /// - Gauges keep the latest value (the last stored wins ties)
+    /// - Histogram and timer observations are folded into cumulative buckets
+    ///   over `HistogramBuckets::latency()` plus `+Inf`; recorded
+    ///   `MetricValue::Histogram`s are merged as they are
+    ///
+    /// Unlike `current_state`, gauges are not combined across instances.
+    ///
+    /// # Returns
+    /// * `Result<Vec<MetricSnapshot>>` - The aggregated series, or a
+    ///   validation error if records of one series can't be merged (e.g. a
+    ///   name recorded as both counter and gauge, or mismatched buckets)
+    pub async fn get_aggregated_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        aggregate_snapshots(self.get_stored_metrics().await)
+    }
+
+    /// Register a callback invoked for every successfully recorded metric
+    ///
+    /// Callbacks run synchronously inside `record`, after validation and
+    /// storage succeed (whether or not storage is enabled), in registration
+    /// order.
+    /// Keep them cheap: pushing into a channel or bumping a counter is fine.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let metrics = MockMetricsAdapter::default();
+    /// let seen = Arc::new(AtomicUsize::new(0));
+    /// let counter = seen.clone();
+    /// metrics.on_record(move |_| {
+    ///     counter.fetch_add(1, Ordering::SeqCst);
+    /// });
+    ///
+    /// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+    /// assert_eq!(seen.load(Ordering::SeqCst), 1);
+    /// # });
+    /// ```
+    pub fn on_record(&self, callback: impl Fn(&MetricSnapshot) + Send + Sync + 'static) {
+        self.record_callbacks
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(Box::new(callback));
+    }
+
+    /// Subscribe to every successfully recorded metric as a stream
+    ///
+    /// The stream yields a snapshot for each record made after subscribing,
+    /// in the order they were recorded, and ends when the adapter is dropped.
+    /// Each subscription buffers up to 1024 snapshots; a reader that falls
+    /// further behind skips the oldest ones and continues with the rest,
+    /// adding the number skipped to `MetricSubscription::lagged`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tokio_stream::StreamExt;
+    /// use tyl_metrics_port::{MetricRequest, MetricsManager, MockMetricsAdapter};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let metrics = MockMetricsAdapter::default();
+    /// let mut recorded = metrics.subscribe();
+    ///
+    /// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+    /// assert_eq!(recorded.next().await.unwrap().name, "requests");
+    /// # });
+    /// ```
+    pub fn subscribe(&self) -> MetricSubscription {
+        MetricSubscription {
+            inner: BroadcastStream::new(self.subscribers.subscribe()),
+            lagged: 0,
+        }
+    }
+
+    /// Invoke every registered record callback with a snapshot and
+    /// broadcast it to subscribers
+    fn notify_record(&self, snapshot: &MetricSnapshot) {
+        let callbacks = self
+            .record_callbacks
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        for callback in callbacks.iter() {
+            callback(snapshot);
+        }
+
+        if self.subscribers.receiver_count() > 0 {
+            // Only fails when every subscriber has gone away meanwhile
+            let _ = self.subscribers.send(snapshot.clone());
+        }
+    }
+
+    /// Make the next `count` record calls fail with a transient connection error
+    ///
+    /// Unlike `with_failures`, this is deterministic, which makes it suitable
+    /// for testing retry logic: the failures are consumed in order and later
+    /// calls succeed again.
+    pub fn fail_next_records(&self, count: u64) {
+        self.pending_failures.store(count, Ordering::Relaxed);
+    }
+
+    /// Get how many times `record` or `record_validated` has been called
+    ///
+    /// Every attempt is counted, including ones that failed.
+    pub fn record_calls(&self) -> u64 {
+        self.record_calls.load(Ordering::Relaxed)
+    }
+
+    /// Common entry to every record path: count the call, then simulate
+    /// latency and any configured failures
+    async fn begin_record(&self, name: &str) -> Result<()> {
+        self.record_calls.fetch_add(1, Ordering::Relaxed);
+        self.simulate_record_latency().await;
+
+        let injected = self
+            .pending_failures
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+            .is_ok();
+        if injected {
+            return Err(metrics_connection_error(
+                "mock",
+                "Simulated transient connection failure",
+            ));
+        }
+
+        // Check if we should simulate a failure
+        if self.should_fail().await {
+            return Err(metrics_recording_error(name, "Simulated recording failure"));
+        }
+
+        Ok(())
+    }
+
+    /// Sleep for the configured record latency plus a random share of the jitter
+    async fn simulate_record_latency(&self) {
+        let mut latency = self.config.record_latency.unwrap_or_default();
+        if let Some(jitter) = self.config.record_latency_jitter {
+            latency += jitter.mul_f64(self.rng.write().await.f64());
+        }
+
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+    }
+
+    /// Get how many times `record` has run full request validation
+    ///
+    /// `record_validated` skips validation, so this lets tests confirm that
+    /// template reuse avoids the repeated checks.
+    pub fn validation_count(&self) -> u64 {
+        self.validations.load(Ordering::Relaxed)
+    }
+
+    /// Store a valid request and notify observers
+    async fn store_record(&self, request: &MetricRequest) -> Result<()> {
+        check_timer_bounds(
+            request,
+            self.config.min_timer_duration,
+            self.config.max_timer_duration,
+        )?;
+        let snapshot = MetricSnapshot::from(request);
+
+        // Bulk histogram observations are stored as if recorded one by one
+        if let Some(values) = request.observation_batch() {
+            let observations: Vec<MetricSnapshot> = values
+                .iter()
+                .map(|&value| MetricSnapshot {
+                    value: MetricValue::Single(value),
+                    ..snapshot.clone()
+                })
+                .collect();
+            if self.config.store_metrics {
+                self.stored_metrics
+                    .push_records(&snapshot.name, observations.clone())
+                    .await?;
+            }
+            for observation in &observations {
+                self.notify_record(observation);
+            }
+            return Ok(());
+        }
+
+        // Store the metric if configured to do so
+        if self.config.store_metrics {
+            self.stored_metrics
+                .push(snapshot.clone(), request.is_increment())
+                .await?;
+        }
+
+        self.notify_record(&snapshot);
+
+        Ok(())
+    }
+
+    /// Manually set health status for testing
+    pub async fn set_health_status(&self, status: HealthStatus) {
+        *self.health_status.write().await = status;
+    }
+
+    /// Get current configuration
+    pub fn config(&self) -> &MockMetricsConfig {
+        &self.config
+    }
+
+    /// Check if we should simulate a failure
+    async fn should_fail(&self) -> bool {
+        if !self.config.simulate_failures {
+            return false;
+        }
+
+        let random_value = {
+            let mut rng = self.rng.write().await;
+            rng.f64()
+        };
+        random_value < self.config.failure_rate
+    }
+}
+
+#[async_trait]
+impl MetricsManager for MockMetricsAdapter {
+    type Config = MockMetricsConfig;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        config.validate()?;
+        let adapter = Self::new(config);
+
+        Ok(adapter)
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        self.begin_record(request.name()).await?;
+
+        // Validate the metric request unless running lenient
+        if self.config.strict_validation {
+            self.validations.fetch_add(1, Ordering::Relaxed);
+            validate_metric_request_with(request, &self.config.validation_limits)?;
+        }
+
+        self.store_record(request).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        self.begin_record(request.request().name()).await?;
+
+        // Already validated - go straight to storage
+        self.store_record(request.request()).await
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        let stored_metrics = self.stored_metrics.clone();
+        let store_metrics = self.config.store_metrics;
+        let (min, max) = (
+            self.config.min_timer_duration,
+            self.config.max_timer_duration,
+        );
+        let name = name.to_string();
+
+        TimerGuard::new(name, labels, move |request| {
+            // Drop is synchronous, so queue the recording; it lands in storage
+            // on the next read or `flush`. Out-of-range timers have no caller
+            // to report to, so they are dropped.
+            if store_metrics && check_timer_bounds(&request, min, max).is_ok() {
+                stored_metrics.push_pending(&request);
+            }
+        })
+        .with_validation_limits(self.config.validation_limits)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if let Some(latency) = self.config.health_latency {
+            tokio::time::sleep(latency).await;
+        }
+
+        // Check if we should simulate a health check failure
+        if self.should_fail().await {
+            return Err(metrics_health_error(
+                "mock",
+                "Simulated health check failure",
+            ));
+        }
+
+        let status = self.health_status.read().await.clone();
+        Ok(status)
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        let adapter = ComponentHealth::new(
+            crate::port::component_name::<Self>(),
+            self.health_check().await?,
+        );
+
+        // Storage is healthy while there's room below the configured limit
+        let stored = self.get_metrics_count().await;
+        let limit = self.config.max_stored_metrics;
+        let storage_status = if stored < limit {
+            HealthStatus::healthy()
+        } else {
+            HealthStatus::unhealthy("Metric storage is at max_stored_metrics limit")
+        };
+        let storage = ComponentHealth::new(
+            "storage",
+            storage_status
+                .with_metadata("stored", stored.to_string())
+                .with_metadata("limit", limit.to_string()),
+        );
+
+        Ok(vec![adapter, storage])
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.stored_metrics.drain_pending().await;
+        Ok(())
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        if !self.config.store_metrics {
+            return Ok(Vec::new());
+        }
+
+        Ok(self.get_stored_metrics().await)
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        if !self.config.store_metrics {
+            return Ok(Vec::new());
+        }
+
+        Ok(self.stored_metrics.page(offset, limit).await)
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        if !self.config.store_metrics {
+            return Ok(0);
+        }
+
+        Ok(self.stored_metrics.len().await)
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        if !self.config.store_metrics {
+            return Ok(Vec::new());
+        }
+
+        // Only matching metrics are cloned out of storage
+        Ok(self
+            .stored_metrics
+            .collect_where(|m| filter.matches(m))
+            .await)
+    }
+}
+
+impl Default for MockMetricsAdapter {
+    fn default() -> Self {
+        Self::new(MockMetricsConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::Duration;
+
+    #[test]
+    fn test_builder_rejects_conflicting_config() {
+        let error = MockAdapterBuilder::new()
+            .max_stored_metrics(0)
+            .drop_strategy(DropStrategy::RejectNew)
+            .build_config()
+            .unwrap_err();
+        assert!(error.to_string().contains("max_stored_metrics"));
+        assert!(error.to_string().contains("RejectNew"));
+
+        let error = MockAdapterBuilder::new()
+            .max_stored_metrics(2)
+            .shards(4)
+            .build_config()
+            .unwrap_err();
+        assert!(error.to_string().contains("shards"));
+
+        assert!(MockAdapterBuilder::new().failure_rate(1.5).build().is_err());
+        assert!(MockAdapterBuilder::new().shards(0).build().is_err());
+        assert!(MockAdapterBuilder::new()
+            .gauge_aggregation("", GaugeAgg::Sum)
+            .build()
+            .is_err());
+
+        // Limits don't matter when nothing is stored
+        assert!(MockAdapterBuilder::new()
+            .store_metrics(false)
+            .max_stored_metrics(0)
+            .build()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_builder_full_config() {
+        let adapter = MockMetricsAdapter::builder()
+            .service_name("checkout")
+            .store_metrics(true)
+            .max_stored_metrics(8)
+            .shards(2)
+            .drop_strategy(DropStrategy::DropNewest)
+            .strict_validation(false)
+            .failure_rate(0.0)
+            .record_latency(Duration::from_millis(1))
+            .record_latency_jitter(Duration::from_millis(1))
+            .health_latency(Duration::from_millis(1))
+            .gauge_aggregation("memory_*", GaugeAgg::Sum)
+            .build()
+            .unwrap();
+
+        let config = adapter.config();
+        assert_eq!(config.service_name, "checkout");
+        assert_eq!(config.shards, 2);
+        assert_eq!(config.drop_strategy, DropStrategy::DropNewest);
+        assert!(!config.strict_validation);
+        assert!(!config.simulate_failures);
+        assert_eq!(config.gauge_aggregations.len(), 1);
+
+        adapter
+            .record(&MetricRequest::counter("bad name", 1.0))
+            .await
+            .unwrap();
+        assert_eq!(adapter.get_metrics_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mock_snapshot_filtered() {
+        let adapter = MockMetricsAdapter::default();
+        for request in [
+            MetricRequest::counter("http_requests_total", 1.0).with_label("method", "GET"),
+            MetricRequest::counter("http_requests_total", 1.0).with_label("method", "POST"),
+            MetricRequest::gauge("http_connections", 4.0),
+            MetricRequest::counter("db_queries_total", 1.0),
+        ] {
+            adapter.record(&request).await.unwrap();
+        }
+
+        let filter = SnapshotFilter::new()
+            .with_name_prefix("http_")
+            .with_metric_type(MetricType::Counter);
+        let counters = adapter.get_snapshot_filtered(filter.clone()).await.unwrap();
+        assert_eq!(counters.len(), 2);
+        assert!(counters.iter().all(|m| m.name == "http_requests_total"));
+
+        let get = adapter
+            .get_snapshot_filtered(filter.with_label("method", "GET"))
+            .await
+            .unwrap();
+        assert_eq!(get.len(), 1);
+
+        let all = adapter
+            .get_snapshot_filtered(SnapshotFilter::new())
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_mock_lenient_validation() {
+        let invalid = MetricRequest::counter("invalid name", -1.0).with_label("", "x");
+
+        let strict = MockMetricsAdapter::default();
+        assert!(strict.record(&invalid).await.is_err());
+        assert_eq!(strict.get_metrics_count().await, 0);
+
+        let lenient =
+            MockMetricsAdapter::new(MockMetricsConfig::default().with_strict_validation(false));
+        lenient.record(&invalid).await.unwrap();
+        let stored = lenient.find_metrics_by_name("invalid name").await;
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].value, MetricValue::Single(-1.0));
+        assert_eq!(lenient.validation_count(), 0);
+
+        // Failure simulation still applies
+        lenient.fail_next_records(1);
+        assert!(lenient.record(&invalid).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_adapter_creation() {
+        let config = MockMetricsConfig::new("test-service");
+        let adapter = MockMetricsAdapter::new(config.clone());
+
+        assert_eq!(adapter.config().service_name, "test-service");
+        assert!(adapter.config().store_metrics);
+    }
+
+    #[tokio::test]
+    async fn test_mock_adapter_default() {
+        let adapter = MockMetricsAdapter::default();
+        assert_eq!(adapter.config().service_name, "test-service");
+    }
+
+    #[tokio::test]
+    async fn test_record_counter() {
+        let adapter = MockMetricsAdapter::default();
+        let request = MetricRequest::counter("test_counter", 1.0).with_label("env", "test");
+
+        let result = adapter.record(&request).await;
+        assert!(result.is_ok());
+
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].name, "test_counter");
+        assert_eq!(stored[0].metric_type, MetricType::Counter);
+    }
+
+    #[tokio::test]
+    async fn test_record_gauge() {
+        let adapter = MockMetricsAdapter::default();
+        let request = MetricRequest::gauge("memory_usage", 512.0);
+
+        adapter.record(&request).await.unwrap();
+
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].metric_type, MetricType::Gauge);
+    }
+
+    #[tokio::test]
+    async fn test_record_histogram() {
+        let adapter = MockMetricsAdapter::default();
+        let request = MetricRequest::histogram("request_duration", 0.123);
+
+        adapter.record(&request).await.unwrap();
+
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].metric_type, MetricType::Histogram);
+    }
+
+    #[tokio::test]
+    async fn test_record_timer() {
+        let adapter = MockMetricsAdapter::default();
+        let request = MetricRequest::timer("db_query", Duration::from_millis(50));
+
+        adapter.record(&request).await.unwrap();
+
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].metric_type, MetricType::Timer);
+        assert_eq!(stored[0].value, MetricValue::Single(0.05)); // 50ms as seconds
+    }
+
+    #[tokio::test]
+    async fn test_record_data_point() {
+        let adapter = MockMetricsAdapter::default();
+        let request = MetricRequest::data_point(
+            "otlp_bytes_sent",
+            MetricType::Counter,
+            MetricValue::Single(512.0),
+            10,
+            20,
+            AggregationTemporality::Cumulative,
+        )
+        .unwrap();
+
+        adapter.record(&request).await.unwrap();
+
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored[0].start_timestamp, Some(10));
+        assert_eq!(stored[0].timestamp, 20);
+        assert_eq!(
+            stored[0].temporality,
+            Some(AggregationTemporality::Cumulative)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_stored_metrics_limit() {
+        let config = MockMetricsConfig::default().with_max_stored(2);
+        let adapter = MockMetricsAdapter::new(config);
+
+        // Record 3 metrics
+        for i in 0..3 {
+            let request = MetricRequest::counter(format!("counter_{}", i), 1.0);
+            adapter.record(&request).await.unwrap();
+        }
+
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored.len(), 2); // Should only keep 2 metrics
+
+        // Should have the last 2 metrics (counter_1 and counter_2)
+        assert_eq!(stored[0].name, "counter_1");
+        assert_eq!(stored[1].name, "counter_2");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_sharded_store_concurrent_writes() {
+        let config = MockMetricsConfig::default()
+            .with_shards(4)
+            .with_max_stored(10_000);
+        let adapter = Arc::new(MockMetricsAdapter::new(config));
+
+        let tasks: Vec<_> = (0..100)
+            .map(|task| {
+                let adapter = adapter.clone();
+                tokio::spawn(async move {
+                    for _ in 0..10 {
+                        let name = format!("metric_{}", task % 8);
+                        adapter
+                            .record(&MetricRequest::counter(name, 1.0))
+                            .await
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(adapter.get_metrics_count().await, 1000);
+        assert_eq!(adapter.get_stored_metrics().await.len(), 1000);
+        for i in 0..8 {
+            let name = format!("metric_{i}");
+            let expected = if i < 4 { 130 } else { 120 };
+            assert_eq!(adapter.find_metrics_by_name(&name).await.len(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sharded_store_splits_limit() {
+        let config = MockMetricsConfig::default()
+            .with_shards(2)
+            .with_max_stored(4);
+        let adapter = MockMetricsAdapter::new(config);
+
+        for _ in 0..10 {
+            adapter
+                .record(&MetricRequest::counter("same_name", 1.0))
+                .await
+                .unwrap();
+        }
+
+        // All records for one name land in one shard holding half the limit
+        assert_eq!(adapter.get_metrics_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_on_record_fires_per_successful_record() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let adapter = MockMetricsAdapter::default();
+        let first = Arc::new(AtomicUsize::new(0));
+        let second = Arc::new(AtomicUsize::new(0));
+        let (first_clone, second_clone) = (first.clone(), second.clone());
+        adapter.on_record(move |_| {
+            first_clone.fetch_add(1, Ordering::SeqCst);
+        });
+        adapter.on_record(move |snapshot| {
+            assert_eq!(snapshot.name, "requests");
+            second_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        for _ in 0..3 {
+            adapter
+                .record(&MetricRequest::counter("requests", 1.0))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(first.load(Ordering::SeqCst), 3);
+        assert_eq!(second.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_on_record_skipped_on_validation_failure() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let adapter = MockMetricsAdapter::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = calls.clone();
+        adapter.on_record(move |_| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert!(adapter
+            .record(&MetricRequest::counter("invalid name", 1.0))
+            .await
+            .is_err());
+        assert!(adapter
+            .record(&MetricRequest::counter("requests", -1.0))
+            .await
+            .is_err());
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_streams_recorded_metrics() {
+        use tokio_stream::StreamExt;
+
+        let adapter = MockMetricsAdapter::default();
+        let mut recorded = adapter.subscribe();
+
+        for name in ["first", "second", "third"] {
+            adapter
+                .record(&MetricRequest::counter(name, 1.0))
+                .await
+                .unwrap();
+        }
+        assert!(adapter
+            .record(&MetricRequest::counter("invalid name", 1.0))
+            .await
+            .is_err());
+        drop(adapter);
+
+        let names: Vec<String> = (&mut recorded)
+            .map(|snapshot| snapshot.name)
+            .collect()
+            .await;
+        assert_eq!(names, vec!["first", "second", "third"]);
+        assert_eq!(recorded.lagged(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_lagging_reader_skips_and_counts() {
+        use tokio_stream::StreamExt;
+
+        let adapter = MockMetricsAdapter::new(MockMetricsConfig::default().with_storage(false));
+        let mut recorded = adapter.subscribe();
+
+        let overflow = 10;
+        for i in 0..SUBSCRIPTION_CAPACITY + overflow {
+            adapter
+                .record(&MetricRequest::gauge("queue_depth", i as f64))
+                .await
+                .unwrap();
+        }
+
+        let first = recorded.next().await.unwrap();
+        assert_eq!(first.value, MetricValue::Single(overflow as f64));
+        assert_eq!(recorded.lagged(), overflow as u64);
+    }
+
+    #[tokio::test]
+    async fn test_record_at_preserves_event_time() {
+        let adapter = MockMetricsAdapter::default();
+        let request = MetricRequest::gauge("temperature", 21.5).with_label("room", "a");
+
+        adapter.record_at(&request, 2_000_000).await.unwrap();
+        adapter.record_at(&request, 1_000_000).await.unwrap();
+
+        let stored = adapter.get_stored_metrics().await;
+        let timestamps: Vec<u64> = stored.iter().map(|m| m.timestamp).collect();
+        assert_eq!(timestamps, vec![2_000_000, 1_000_000]);
+        assert!(stored.iter().all(|m| m.labels["room"] == "a"));
+        assert!(adapter
+            .record_at(&MetricRequest::gauge("bad name", 1.0), 1)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_snapshot_paged() {
+        for shards in [1, 4] {
+            let config = MockMetricsConfig::default()
+                .with_shards(shards)
+                .with_max_stored(10_000);
+            let adapter = MockMetricsAdapter::new(config);
+            for i in 0..1000 {
+                adapter
+                    .record(&MetricRequest::counter(
+                        format!("metric_{}", i % 7),
+                        i as f64,
+                    ))
+                    .await
+                    .unwrap();
+            }
+            assert_eq!(adapter.snapshot_len().await.unwrap(), 1000);
+
+            let mut paged = Vec::new();
+            let mut offset = 0;
+            loop {
+                let page = adapter.get_snapshot_paged(offset, 100).await.unwrap();
+                if page.is_empty() {
+                    break;
+                }
+                assert!(page.len() <= 100);
+                offset += page.len();
+                paged.extend(page);
+            }
+
+            assert_eq!(paged, adapter.get_snapshot().await.unwrap());
+            assert_eq!(adapter.get_snapshot_paged(995, 100).await.unwrap().len(), 5);
+            assert!(adapter.get_snapshot_paged(0, 0).await.unwrap().is_empty());
+        }
+    }
+
+    async fn record_three_at_capacity_two(
+        strategy: DropStrategy,
+    ) -> (MockMetricsAdapter, Vec<bool>) {
+        let config = MockMetricsConfig::default()
+            .with_max_stored(2)
+            .with_drop_strategy(strategy);
+        let adapter = MockMetricsAdapter::new(config);
+
+        let mut results = Vec::new();
+        for i in 1..=3 {
+            let request = MetricRequest::gauge("queue_depth", i as f64);
+            results.push(adapter.record(&request).await.is_ok());
+        }
+        (adapter, results)
+    }
+
+    fn stored_values(stored: &[MetricSnapshot]) -> Vec<f64> {
+        stored
+            .iter()
+            .map(|m| match m.value {
+                MetricValue::Single(v) => v,
+                _ => panic!("Expected single value"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_drop_strategy_drop_oldest() {
+        let (adapter, results) = record_three_at_capacity_two(DropStrategy::DropOldest).await;
+
+        assert_eq!(results, vec![true, true, true]);
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored_values(&stored), vec![2.0, 3.0]);
+        assert_eq!(adapter.eviction_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expires_stale_metrics() {
+        let clock = MockClock::new(1_000_000_000_000);
+        let adapter =
+            MockMetricsAdapter::new(MockMetricsConfig::default().with_ttl(Duration::from_secs(60)))
+                .with_clock(Arc::new(clock.clone()));
+
+        adapter
+            .record_at(&MetricRequest::counter("first", 1.0), clock.now_nanos())
+            .await
+            .unwrap();
+        clock.advance(Duration::from_secs(30));
+        adapter
+            .record_at(&MetricRequest::counter("second", 1.0), clock.now_nanos())
+            .await
+            .unwrap();
+        assert_eq!(adapter.get_metrics_count().await, 2);
+
+        // 61s after the first, 31s after the second
+        clock.advance(Duration::from_secs(31));
+        adapter
+            .record_at(&MetricRequest::counter("third", 1.0), clock.now_nanos())
+            .await
+            .unwrap();
+
+        let names: Vec<String> = adapter
+            .get_stored_metrics()
+            .await
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names, vec!["second", "third"]);
+        assert_eq!(adapter.expired_count(), 1);
+        assert_eq!(adapter.eviction_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_uses_metric_timestamp_and_expires_on_read() {
+        let clock = MockClock::new(1_000_000_000_000);
+        let adapter =
+            MockMetricsAdapter::new(MockMetricsConfig::default().with_ttl(Duration::from_secs(60)))
+                .with_clock(Arc::new(clock.clone()));
+        let now = clock.now_nanos();
+
+        adapter
+            .record_at(&MetricRequest::gauge("queue_depth", 3.0), now)
+            .await
+            .unwrap();
+        adapter
+            .record_at(&MetricRequest::counter_inc("jobs_total", 1.0), now)
+            .await
+            .unwrap();
+        // Backfilled past the TTL, so it is gone by the next read
+        adapter
+            .record_at(
+                &MetricRequest::gauge("queue_depth", 9.0),
+                now - 300_000_000_000,
+            )
+            .await
+            .unwrap();
+        // Older than the gauge stored before it, but still within the TTL
+        adapter
+            .record_at(&MetricRequest::gauge("backlog", 1.0), now - 1)
+            .await
+            .unwrap();
+
+        let names: Vec<String> = adapter
+            .get_stored_metrics()
+            .await
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names, vec!["queue_depth", "backlog", "jobs_total"]);
+        assert_eq!(adapter.expired_count(), 1);
+
+        // Nothing else is recorded; the read alone expires the rest
+        clock.advance(Duration::from_secs(61));
+        assert_eq!(adapter.get_metrics_count().await, 0);
+        assert_eq!(adapter.expired_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_keeps_counter_totals_that_are_still_incremented() {
+        let clock = MockClock::new(1_000_000_000_000);
+        let adapter =
+            MockMetricsAdapter::new(MockMetricsConfig::default().with_ttl(Duration::from_secs(60)))
+                .with_clock(Arc::new(clock.clone()));
+
+        for _ in 0..3 {
+            adapter
+                .record_at(
+                    &MetricRequest::counter_inc("jobs_total", 1.0),
+                    clock.now_nanos(),
+                )
+                .await
+                .unwrap();
+            clock.advance(Duration::from_secs(40));
+        }
+
+        assert_eq!(adapter.counter_total("jobs_total", None).await, 3.0);
+        assert_eq!(adapter.expired_count(), 0);
+    }
+
+    #[test]
+    fn test_ttl_must_be_positive() {
+        assert!(MockMetricsAdapter::builder()
+            .ttl(Duration::ZERO)
+            .build()
+            .is_err());
+        assert!(MockMetricsAdapter::builder()
+            .ttl(Duration::from_secs(1))
+            .clock(Arc::new(MockClock::default()))
+            .build()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_record_uses_configured_validation_limits() {
+        let labeled = |count: usize| {
+            (0..count).fold(MetricRequest::counter("requests", 1.0), |request, i| {
+                request.with_label(format!("label_{i}"), "value")
+            })
+        };
+        let adapter = MockMetricsAdapter::builder()
+            .validation_limits(
+                ValidationLimits::default()
+                    .with_max_labels(64)
+                    .with_max_label_value_len(8),
+            )
+            .build()
+            .unwrap();
+
+        assert!(MockMetricsAdapter::default()
+            .record(&labeled(40))
+            .await
+            .is_err());
+        assert!(adapter.record(&labeled(40)).await.is_ok());
+        assert!(adapter.record(&labeled(65)).await.is_err());
+        assert!(adapter
+            .record(&MetricRequest::counter("requests", 1.0).with_label("path", "/a/long/path"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_group_by_label() {
+        let metrics = MockMetricsAdapter::default();
+        for method in ["GET", "POST", "GET", "GET"] {
+            metrics
+                .record(&MetricRequest::counter("http_requests", 1.0).with_label("method", method))
+                .await
+                .unwrap();
+        }
+        metrics
+            .record(&MetricRequest::counter("http_requests", 1.0))
+            .await
+            .unwrap();
+        metrics
+            .record(&MetricRequest::counter("other", 1.0).with_label("method", "PUT"))
+            .await
+            .unwrap();
+
+        let groups = metrics.group_by_label("http_requests", "method").await;
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups["GET"].len(), 3);
+        assert_eq!(groups["POST"].len(), 1);
+        assert_eq!(groups[""].len(), 1);
+        assert!(groups["GET"].iter().all(|m| m.labels["method"] == "GET"));
+    }
+
+    #[tokio::test]
+    async fn test_counter_inc_aggregates_per_series() {
+        let metrics = MockMetricsAdapter::default();
+        for _ in 0..3 {
+            metrics
+                .record(&MetricRequest::counter_inc("jobs_total", 1.0).with_label("queue", "a"))
+                .await
+                .unwrap();
+            metrics
+                .record(&MetricRequest::counter("raw_total", 1.0))
+                .await
+                .unwrap();
+        }
+        metrics
+            .record(&MetricRequest::counter_inc("jobs_total", 2.0).with_label("queue", "b"))
+            .await
+            .unwrap();
+
+        let increments = metrics.find_metrics_by_name("jobs_total").await;
+        assert_eq!(increments.len(), 2);
+        assert_eq!(increments[0].value, MetricValue::Single(3.0));
+        assert_eq!(increments[1].value, MetricValue::Single(2.0));
+
+        let raw = metrics.find_metrics_by_name("raw_total").await;
+        assert_eq!(raw.len(), 3);
+        assert!(raw.iter().all(|m| m.value == MetricValue::Single(1.0)));
+
+        // Totals are cumulative, whatever temporality the increments carry
+        assert!(increments
+            .iter()
+            .all(|m| m.temporality == Some(AggregationTemporality::Cumulative)));
+    }
+
+    #[tokio::test]
+    async fn test_delta_counters_are_not_increments() {
+        let metrics = MockMetricsAdapter::default();
+        for _ in 0..2 {
+            metrics
+                .record(
+                    &MetricRequest::counter("exported_total", 5.0)
+                        .with_temporality(AggregationTemporality::Delta),
+                )
+                .await
+                .unwrap();
+        }
+
+        let stored = metrics.find_metrics_by_name("exported_total").await;
+        assert_eq!(stored.len(), 2);
+        assert!(stored
+            .iter()
+            .all(|m| m.temporality == Some(AggregationTemporality::Delta)));
+    }
+
+    #[tokio::test]
+    async fn test_counter_totals_survive_drop_oldest() {
+        let metrics = MockMetricsAdapter::new(
+            MockMetricsConfig::default()
+                .with_max_stored(2)
+                .with_drop_strategy(DropStrategy::DropOldest),
+        );
+        metrics
+            .record(&MetricRequest::counter_inc("jobs_total", 1.0))
+            .await
+            .unwrap();
+        for value in 0..5 {
+            metrics
+                .record(&MetricRequest::gauge("queue_depth", value as f64))
+                .await
+                .unwrap();
+            metrics
+                .record(&MetricRequest::counter_inc("jobs_total", 1.0))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(metrics.counter_total("jobs_total", None).await, 6.0);
+        assert_eq!(metrics.find_metrics_by_name("queue_depth").await.len(), 1);
+        assert_eq!(metrics.eviction_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_metadata_only_storage() {
+        let metrics = MockMetricsAdapter::new(
+            MockMetricsConfig::default()
+                .with_store_values(false)
+                .with_max_stored(10),
+        );
+
+        for user in 0..50 {
+            let request = MetricRequest::counter("logins_total", 1.0)
+                .with_label("user", format!("user-{}", user % 20));
+            metrics.record(&request).await.unwrap();
+        }
+        metrics
+            .record(&MetricRequest::gauge("logins_total", 3.0))
+            .await
+            .unwrap();
+
+        // The storage limit bounds records, not series
+        assert_eq!(metrics.distinct_series().await, 21);
+        assert_eq!(metrics.get_metrics_count().await, 21);
+
+        let stored = metrics.get_stored_metrics().await;
+        assert_eq!(stored.len(), 21);
+        assert!(stored
+            .iter()
+            .all(|m| m.value == MetricValue::Single(0.0) && m.timestamp == 0));
+        assert_eq!(metrics.counter_total("logins_total", None).await, 0.0);
+
+        metrics.clear_stored_metrics().await;
+        assert_eq!(metrics.distinct_series().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_distinct_series_with_values() {
+        let metrics = MockMetricsAdapter::default();
+        for value in [1.0, 2.0] {
+            metrics
+                .record(&MetricRequest::counter("jobs_total", value).with_label("queue", "a"))
+                .await
+                .unwrap();
+        }
+        metrics
+            .record(&MetricRequest::counter("jobs_total", 1.0).with_label("queue", "b"))
+            .await
+            .unwrap();
+
+        assert_eq!(metrics.get_metrics_count().await, 3);
+        assert_eq!(metrics.distinct_series().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_timer_bounds() {
+        let zero = MetricRequest::timer("db_query", Duration::ZERO);
+        let default = MockMetricsAdapter::default();
+        assert!(default.record(&zero).await.is_ok());
+
+        let strict = MockMetricsAdapter::new(
+            MockMetricsConfig::default()
+                .with_timer_bounds(Duration::from_micros(1), Duration::from_secs(3600)),
+        );
+        let error = strict.record(&zero).await.unwrap_err();
+        assert!(error.to_string().contains("below the 1µs minimum"));
+        assert!(strict
+            .record(&MetricRequest::timer("db_query", Duration::from_secs(7200)))
+            .await
+            .is_err());
+        assert!(strict
+            .record(&MetricRequest::timer("db_query", Duration::from_millis(5)))
+            .await
+            .is_ok());
+        // Only timers are checked
+        assert!(strict
+            .record(&MetricRequest::gauge("queue_depth", 0.0))
+            .await
+            .is_ok());
+
+        assert_eq!(strict.get_metrics_count().await, 2);
+
+        // Guard recordings out of range are discarded
+        let slow_only = MockMetricsAdapter::new(
+            MockMetricsConfig::default()
+                .with_timer_bounds(Duration::from_secs(60), Duration::from_secs(3600)),
+        );
+        drop(slow_only.start_timer("instant", Labels::new()));
+        assert!(slow_only.find_metrics_by_name("instant").await.is_empty());
+    }
+
+    #[test]
+    fn test_timer_bounds_must_be_ordered() {
+        assert!(MockMetricsAdapter::builder()
+            .min_timer_duration(Duration::from_secs(2))
+            .max_timer_duration(Duration::from_secs(1))
+            .build()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_drop_strategy_drop_newest() {
+        let (adapter, results) = record_three_at_capacity_two(DropStrategy::DropNewest).await;
+
+        assert_eq!(results, vec![true, true, true]);
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored_values(&stored), vec![1.0, 2.0]);
+        assert_eq!(adapter.eviction_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drop_strategy_reject_new() {
+        let (adapter, results) = record_three_at_capacity_two(DropStrategy::RejectNew).await;
+
+        assert_eq!(results, vec![true, true, false]);
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored_values(&stored), vec![1.0, 2.0]);
+
+        let error = adapter
+            .record(&MetricRequest::gauge("queue_depth", 4.0))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("storage is full"));
+        assert!(!is_retryable(&error));
+        assert_eq!(adapter.eviction_count(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_counter_total_concurrent_increments() {
+        let adapter = Arc::new(MockMetricsAdapter::new(
+            MockMetricsConfig::default().with_shards(4),
+        ));
+
+        let tasks: Vec<_> = (0..100)
+            .map(|task| {
+                let adapter = adapter.clone();
+                tokio::spawn(async move {
+                    let method = if task % 2 == 0 { "GET" } else { "POST" };
+                    adapter
+                        .record(
+                            &MetricRequest::counter("requests_total", 1.0)
+                                .with_label("method", method),
+                        )
+                        .await
+                        .unwrap();
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        adapter
+            .record(&MetricRequest::gauge("requests_total", 1000.0))
+            .await
+            .unwrap();
+
+        assert_eq!(adapter.counter_total("requests_total", None).await, 100.0);
+        let get: Labels = [("method".to_string(), "GET".to_string())].into();
+        assert_eq!(
+            adapter.counter_total("requests_total", Some(&get)).await,
+            50.0
+        );
+        assert_eq!(adapter.counter_total("unknown_total", None).await, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_latest_gauge() {
+        let adapter = MockMetricsAdapter::default();
+        for value in [10.0, 20.0, 30.0] {
+            adapter
+                .record(&MetricRequest::gauge("queue_depth", value))
+                .await
+                .unwrap();
+        }
+        adapter
+            .record(&MetricRequest::gauge("queue_depth", 99.0).with_label("queue", "slow"))
+            .await
+            .unwrap();
 
-                    stored.push(MetricSnapshot::from(&request));
-                }
-            });
-        })
+        assert_eq!(
+            adapter
+                .latest_gauge("queue_depth", Some(&Labels::new()))
+                .await,
+            Some(30.0)
+        );
+        assert_eq!(adapter.latest_gauge("queue_depth", None).await, Some(99.0));
+        assert_eq!(adapter.latest_gauge("unknown", None).await, None);
+
+        // The highest timestamp wins over insertion order; ties go to the last stored
+        let at = |value: f64, time: u64| {
+            MetricRequest::data_point(
+                "temperature",
+                MetricType::Gauge,
+                MetricValue::Single(value),
+                0,
+                time,
+                AggregationTemporality::Cumulative,
+            )
+            .unwrap()
+        };
+        for request in [at(1.0, 300), at(2.0, 100), at(3.0, 300)] {
+            adapter.record(&request).await.unwrap();
+        }
+        assert_eq!(adapter.latest_gauge("temperature", None).await, Some(3.0));
     }
 
-    async fn health_check(&self) -> Result<HealthStatus> {
-        // Check if we should simulate a health check failure
-        if self.should_fail().await {
-            return Err(metrics_health_error(
-                "mock",
-                "Simulated health check failure",
-            ));
+    #[tokio::test]
+    async fn test_drop_oldest_keeps_newest_under_heavy_overflow() {
+        let adapter = MockMetricsAdapter::new(MockMetricsConfig::default().with_max_stored(1000));
+
+        for i in 0..100_000 {
+            adapter
+                .record(&MetricRequest::gauge("queue_depth", i as f64))
+                .await
+                .unwrap();
         }
 
-        let status = self.health_status.read().await.clone();
-        Ok(status)
+        let stored = adapter.get_stored_metrics().await;
+        let expected: Vec<f64> = (99_000..100_000).map(|i| i as f64).collect();
+        assert_eq!(stored_values(&stored), expected);
+        assert_eq!(
+            adapter.find_metrics_by_name("queue_depth").await.len(),
+            1000
+        );
+        assert_eq!(adapter.eviction_count(), 99_000);
     }
 
-    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
-        if !self.config.store_metrics {
-            return Ok(Vec::new());
+    #[tokio::test]
+    async fn test_eviction_count_matches_overflow() {
+        let adapter = MockMetricsAdapter::new(MockMetricsConfig::default().with_max_stored(10));
+
+        for i in 0..25 {
+            adapter
+                .record(&MetricRequest::counter("requests_total", i as f64))
+                .await
+                .unwrap();
         }
 
-        Ok(self.get_stored_metrics().await)
+        assert_eq!(adapter.get_metrics_count().await, 10);
+        assert_eq!(adapter.eviction_count(), 15);
     }
-}
 
-impl Default for MockMetricsAdapter {
-    fn default() -> Self {
-        Self::new(MockMetricsConfig::default())
-    }
-}
+    #[tokio::test]
+    async fn test_fold_histogram_latency_buckets() {
+        let adapter = MockMetricsAdapter::default();
+        let labels: Labels = [("route".to_string(), "/users".to_string())].into();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tokio::time::Duration;
+        for duration in [0.003, 0.02, 0.02, 0.3, 4.0, 12.0] {
+            let request = MetricRequest::histogram("request_duration", duration)
+                .with_label("route", "/users");
+            adapter.record(&request).await.unwrap();
+        }
+        // Different series, must not be folded in
+        adapter
+            .record(&MetricRequest::histogram("request_duration", 0.001))
+            .await
+            .unwrap();
 
-    #[tokio::test]
-    async fn test_mock_adapter_creation() {
-        let config = MockMetricsConfig::new("test-service");
-        let adapter = MockMetricsAdapter::new(config.clone());
+        let folded = adapter
+            .fold_histogram("request_duration", &labels, &HistogramBuckets::latency())
+            .await
+            .unwrap();
 
-        assert_eq!(adapter.config().service_name, "test-service");
-        assert!(adapter.config().store_metrics);
+        let MetricValue::Histogram {
+            sum,
+            count,
+            buckets,
+        } = folded
+        else {
+            panic!("Expected histogram value");
+        };
+        assert_eq!(count, 6);
+        assert!((sum - 16.343).abs() < 1e-9);
+
+        let counts: Vec<(f64, u64)> = buckets.iter().map(|b| (b.upper_bound, b.count)).collect();
+        assert_eq!(
+            counts,
+            vec![
+                (0.005, 1),
+                (0.01, 1),
+                (0.025, 3),
+                (0.05, 3),
+                (0.1, 3),
+                (0.25, 3),
+                (0.5, 4),
+                (1.0, 4),
+                (2.5, 4),
+                (5.0, 5),
+                (10.0, 5),
+                (f64::INFINITY, 6),
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn test_mock_adapter_default() {
+    async fn test_fold_histogram_negative_bounds() {
         let adapter = MockMetricsAdapter::default();
-        assert_eq!(adapter.config().service_name, "test-service");
+        for value in [-2.0, -0.5, 0.0, 0.5, 3.0] {
+            adapter
+                .record(&MetricRequest::histogram("clock_skew_seconds", value))
+                .await
+                .unwrap();
+        }
+
+        let folded = adapter
+            .fold_histogram("clock_skew_seconds", &Labels::new(), &[-1.0, 0.0, 1.0])
+            .await
+            .unwrap();
+        let MetricValue::Histogram {
+            sum,
+            count,
+            buckets,
+        } = folded
+        else {
+            panic!("expected a histogram");
+        };
+        assert_eq!(sum, 1.0);
+        assert_eq!(count, 5);
+        let counts: Vec<u64> = buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![1, 3, 4, 5]);
     }
 
     #[tokio::test]
-    async fn test_record_counter() {
-        let adapter = MockMetricsAdapter::default();
-        let request = MetricRequest::counter("test_counter", 1.0).with_label("env", "test");
-
-        let result = adapter.record(&request).await;
-        assert!(result.is_ok());
+    async fn test_suggest_buckets_skewed_data() {
+        let metrics = MockMetricsAdapter::default();
+        assert!(metrics.suggest_buckets("latency", 4).await.is_none());
+
+        // Mostly fast requests with a long tail
+        let mut values = vec![0.01; 60];
+        values.extend((1..=30).map(|i| 0.05 * i as f64));
+        values.extend([
+            5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1200.0, 1800.0,
+        ]);
+        for value in &values {
+            metrics
+                .record(&MetricRequest::histogram("latency", *value))
+                .await
+                .unwrap();
+        }
 
-        let stored = adapter.get_stored_metrics().await;
-        assert_eq!(stored.len(), 1);
-        assert_eq!(stored[0].name, "test_counter");
-        assert_eq!(stored[0].metric_type, MetricType::Counter);
+        let buckets = metrics.suggest_buckets("latency", 5).await.unwrap();
+        assert!(buckets.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(buckets.last(), Some(&f64::INFINITY));
+        assert_eq!(buckets[0], 0.01);
+        assert_eq!(buckets[buckets.len() - 2], 1800.0);
+        // The repeated fast value fills several quantiles but yields one bound
+        assert!(buckets.len() < 6);
+
+        let folded = metrics
+            .fold_histogram("latency", &Labels::new(), &buckets)
+            .await;
+        assert!(matches!(
+            folded,
+            Some(MetricValue::Histogram { count: 100, .. })
+        ));
+        assert!(metrics.suggest_buckets("latency", 101).await.is_none());
+        assert!(metrics.suggest_buckets("latency", 0).await.is_none());
     }
 
     #[tokio::test]
-    async fn test_record_gauge() {
+    async fn test_fold_histogram_none_cases() {
         let adapter = MockMetricsAdapter::default();
-        let request = MetricRequest::gauge("memory_usage", 512.0);
-
-        adapter.record(&request).await.unwrap();
+        let buckets = HistogramBuckets::latency();
+        assert!(adapter
+            .fold_histogram("missing", &Labels::new(), &buckets)
+            .await
+            .is_none());
 
-        let stored = adapter.get_stored_metrics().await;
-        assert_eq!(stored.len(), 1);
-        assert_eq!(stored[0].metric_type, MetricType::Gauge);
+        adapter
+            .record(&MetricRequest::histogram("request_duration", 0.2))
+            .await
+            .unwrap();
+        assert!(adapter
+            .fold_histogram("request_duration", &Labels::new(), &[])
+            .await
+            .is_none());
     }
 
     #[tokio::test]
-    async fn test_record_histogram() {
+    async fn test_record_validated_skips_validation() {
         let adapter = MockMetricsAdapter::default();
-        let request = MetricRequest::histogram("request_duration", 0.123);
+        let template = MetricRequest::counter("requests", 1.0)
+            .with_label("method", "GET")
+            .validate()
+            .unwrap();
 
-        adapter.record(&request).await.unwrap();
+        for i in 0..100 {
+            let request = template.clone().with_value(i as f64).unwrap();
+            adapter.record_validated(&request).await.unwrap();
+        }
+        assert_eq!(adapter.validation_count(), 0);
 
-        let stored = adapter.get_stored_metrics().await;
-        assert_eq!(stored.len(), 1);
-        assert_eq!(stored[0].metric_type, MetricType::Histogram);
+        for _ in 0..100 {
+            adapter.record(template.request()).await.unwrap();
+        }
+        assert_eq!(adapter.validation_count(), 100);
+
+        let stored = adapter.find_metrics_by_name("requests").await;
+        assert_eq!(stored.len(), 200);
+        assert_eq!(stored[99].value, MetricValue::Single(99.0));
+        assert_eq!(stored[0].labels.get("method"), Some(&"GET".to_string()));
     }
 
     #[tokio::test]
-    async fn test_record_timer() {
-        let adapter = MockMetricsAdapter::default();
-        let request = MetricRequest::timer("db_query", Duration::from_millis(50));
+    async fn test_health_check_detailed_reports_storage() {
+        let adapter = MockMetricsAdapter::new(MockMetricsConfig::default().with_max_stored(2));
+
+        let components = adapter.health_check_detailed().await.unwrap();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].name, "MockMetricsAdapter");
+        assert_eq!(components[1].name, "storage");
+        assert!(components[1].status.is_healthy);
+
+        for _ in 0..2 {
+            adapter
+                .record(&MetricRequest::counter("requests", 1.0))
+                .await
+                .unwrap();
+        }
 
-        adapter.record(&request).await.unwrap();
+        let components = adapter.health_check_detailed().await.unwrap();
+        let storage = &components[1].status;
+        assert!(!storage.is_healthy);
+        assert_eq!(storage.metadata.get("stored"), Some(&"2".to_string()));
+        assert_eq!(storage.metadata.get("limit"), Some(&"2".to_string()));
+    }
 
-        let stored = adapter.get_stored_metrics().await;
-        assert_eq!(stored.len(), 1);
-        assert_eq!(stored[0].metric_type, MetricType::Timer);
-        assert_eq!(stored[0].value, MetricValue::Single(0.05)); // 50ms as seconds
+    #[tokio::test]
+    async fn test_record_latency_simulation() {
+        let config = MockMetricsConfig::default().with_record_latency(Duration::from_millis(50));
+        let adapter = MockMetricsAdapter::new(config);
+
+        let started = std::time::Instant::now();
+        adapter
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert_eq!(adapter.get_metrics_count().await, 1);
     }
 
     #[tokio::test]
-    async fn test_max_stored_metrics_limit() {
-        let config = MockMetricsConfig::default().with_max_stored(2);
+    async fn test_record_latency_jitter_and_health_latency() {
+        let config = MockMetricsConfig::default()
+            .with_record_latency(Duration::from_millis(10))
+            .with_record_latency_jitter(Duration::from_millis(20))
+            .with_health_latency(Duration::from_millis(30));
         let adapter = MockMetricsAdapter::new(config);
 
-        // Record 3 metrics
-        for i in 0..3 {
-            let request = MetricRequest::counter(&format!("counter_{}", i), 1.0);
-            adapter.record(&request).await.unwrap();
-        }
+        let started = std::time::Instant::now();
+        adapter
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .unwrap();
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(10));
 
-        let stored = adapter.get_stored_metrics().await;
-        assert_eq!(stored.len(), 2); // Should only keep 2 metrics
+        let started = std::time::Instant::now();
+        adapter.health_check().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(30));
+    }
 
-        // Should have the last 2 metrics (counter_1 and counter_2)
-        assert_eq!(stored[0].name, "counter_1");
-        assert_eq!(stored[1].name, "counter_2");
+    #[tokio::test]
+    async fn test_fail_next_records() {
+        let adapter = MockMetricsAdapter::default();
+        adapter.fail_next_records(2);
+
+        let request = MetricRequest::counter("requests", 1.0);
+        assert!(adapter.record(&request).await.is_err());
+        assert!(adapter.record(&request).await.is_err());
+        assert!(adapter.record(&request).await.is_ok());
+
+        assert_eq!(adapter.record_calls(), 3);
+        assert_eq!(adapter.get_metrics_count().await, 1);
     }
 
     #[tokio::test]
@@ -436,6 +3184,53 @@ mod tests {
         assert_eq!(adapter.get_metrics_count().await, 0);
     }
 
+    #[tokio::test]
+    async fn test_save_and_load_snapshots() {
+        let adapter = MockMetricsAdapter::default();
+        adapter
+            .record_batch(&[
+                MetricRequest::counter("http_requests", 1.0).with_label("method", "GET"),
+                MetricRequest::gauge("memory_usage", 512.5),
+                MetricRequest::timer("db_query", Duration::from_millis(42)),
+                MetricRequest::data_point(
+                    "latency",
+                    MetricType::Histogram,
+                    MetricValue::Histogram {
+                        sum: 1.5,
+                        count: 2,
+                        buckets: vec![HistogramBucket {
+                            upper_bound: f64::INFINITY,
+                            count: 2,
+                        }],
+                    },
+                    10,
+                    20,
+                    AggregationTemporality::Cumulative,
+                )
+                .unwrap(),
+            ])
+            .await
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("tyl-metrics-{}.json", uuid::Uuid::new_v4()));
+        adapter.save_snapshots(&path).await.unwrap();
+        let loaded = load_snapshots(&path).await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), adapter.get_stored_metrics().await);
+    }
+
+    #[tokio::test]
+    async fn test_load_snapshots_errors() {
+        let path = std::env::temp_dir().join(format!("tyl-metrics-{}.json", uuid::Uuid::new_v4()));
+        assert!(load_snapshots(&path).await.is_err());
+
+        std::fs::write(&path, "not json").unwrap();
+        let result = load_snapshots(&path).await;
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_find_metrics_by_name() {
         let adapter = MockMetricsAdapter::default();
@@ -598,15 +3393,318 @@ mod tests {
             // Timer should record when dropped
         }
 
-        // Give the async task a moment to complete
-        tokio::time::sleep(Duration::from_millis(10)).await;
-
         let stored = adapter.get_stored_metrics().await;
         assert_eq!(stored.len(), 1);
         assert_eq!(stored[0].name, "test_timer");
         assert_eq!(stored[0].metric_type, MetricType::Timer);
     }
 
+    #[tokio::test]
+    async fn test_flush_after_timer_drop() {
+        let adapter = MockMetricsAdapter::default();
+
+        drop(adapter.start_timer("flushed_timer", Labels::new()));
+        assert_eq!(adapter.stored_metrics.shards[0].read().await.len(), 0);
+
+        adapter.flush().await.unwrap();
+        assert_eq!(adapter.stored_metrics.shards[0].read().await.len(), 1);
+
+        let stored = adapter.get_stored_metrics().await;
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].name, "flushed_timer");
+        assert_eq!(stored[0].metric_type, MetricType::Timer);
+    }
+
+    async fn record_instance_gauges(adapter: &MockMetricsAdapter, name: &str) {
+        for (instance, value) in [("pod-a", 100.0), ("pod-b", 300.0)] {
+            let request = MetricRequest::gauge(name, value)
+                .with_label("instance", instance)
+                .with_label("region", "eu");
+            adapter.record(&request).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_current_state_sums_counters() {
+        let adapter = MockMetricsAdapter::default();
+        for _ in 0..3 {
+            adapter
+                .record(&MetricRequest::counter("requests", 2.0).with_label("method", "GET"))
+                .await
+                .unwrap();
+        }
+
+        let state = adapter.current_state().await;
+        assert_eq!(state.len(), 1);
+        assert_eq!(state[0].value, MetricValue::Single(6.0));
+    }
+
+    #[tokio::test]
+    async fn test_aggregated_snapshot_sums_counters() {
+        let adapter = MockMetricsAdapter::default();
+        for _ in 0..3 {
+            adapter
+                .record(&MetricRequest::counter("requests", 2.0).with_label("method", "GET"))
+                .await
+                .unwrap();
+        }
+        adapter
+            .record(&MetricRequest::counter("requests", 1.0).with_label("method", "POST"))
+            .await
+            .unwrap();
+
+        let aggregated = adapter.get_aggregated_snapshot().await.unwrap();
+        assert_eq!(adapter.get_snapshot().await.unwrap().len(), 4);
+        assert_eq!(aggregated.len(), 2);
+        assert_eq!(aggregated[0].labels["method"], "GET");
+        assert_eq!(aggregated[0].value, MetricValue::Single(6.0));
+        assert_eq!(aggregated[1].value, MetricValue::Single(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_aggregated_snapshot_folds_gauges_and_observations() {
+        let adapter = MockMetricsAdapter::default();
+        for (value, timestamp) in [(3.0, 20), (9.0, 10)] {
+            adapter
+                .record_at(&MetricRequest::gauge("queue_depth", value), timestamp)
+                .await
+                .unwrap();
+        }
+        for millis in [3, 40, 40, 20_000] {
+            adapter
+                .record(&MetricRequest::timer(
+                    "request_duration",
+                    Duration::from_millis(millis),
+                ))
+                .await
+                .unwrap();
+        }
+
+        let aggregated = adapter.get_aggregated_snapshot().await.unwrap();
+        assert_eq!(aggregated.len(), 2);
+        assert_eq!(aggregated[0].value, MetricValue::Single(3.0));
+
+        let MetricValue::Histogram {
+            sum,
+            count,
+            buckets,
+        } = &aggregated[1].value
+        else {
+            panic!("timer should aggregate into a histogram");
+        };
+        assert_eq!(*count, 4);
+        assert!((sum - 20.083).abs() < 1e-9);
+        let count_at = |bound: f64| {
+            buckets
+                .iter()
+                .find(|b| b.upper_bound == bound)
+                .unwrap()
+                .count
+        };
+        assert_eq!(count_at(0.005), 1);
+        assert_eq!(count_at(0.05), 3);
+        assert_eq!(count_at(10.0), 3);
+        assert_eq!(count_at(f64::INFINITY), 4);
+    }
+
+    #[tokio::test]
+    async fn test_aggregated_snapshot_rejects_conflicting_types() {
+        let adapter = MockMetricsAdapter::default();
+        adapter
+            .record(&MetricRequest::counter("jobs", 1.0))
+            .await
+            .unwrap();
+        adapter
+            .record(&MetricRequest::gauge("jobs", 1.0))
+            .await
+            .unwrap();
+
+        assert!(adapter.get_aggregated_snapshot().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gauge_aggregation_sum() {
+        let config = MockMetricsConfig::default().with_gauge_aggregation("memory_*", GaugeAgg::Sum);
+        let adapter = MockMetricsAdapter::new(config);
+        record_instance_gauges(&adapter, "memory_bytes").await;
+
+        let state = adapter.current_state().await;
+        assert_eq!(state.len(), 1);
+        assert_eq!(state[0].value, MetricValue::Single(400.0));
+        assert!(!state[0].labels.contains_key("instance"));
+        assert_eq!(state[0].labels.get("region"), Some(&"eu".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_gauge_aggregation_max() {
+        let config =
+            MockMetricsConfig::default().with_gauge_aggregation("queue_depth", GaugeAgg::Max);
+        let adapter = MockMetricsAdapter::new(config);
+        record_instance_gauges(&adapter, "queue_depth").await;
+
+        let state = adapter.current_state().await;
+        assert_eq!(state.len(), 1);
+        assert_eq!(state[0].value, MetricValue::Single(300.0));
+    }
+
+    #[tokio::test]
+    async fn test_gauge_aggregation_avg() {
+        let config =
+            MockMetricsConfig::default().with_gauge_aggregation("cpu_percent", GaugeAgg::Avg);
+        let adapter = MockMetricsAdapter::new(config);
+        record_instance_gauges(&adapter, "cpu_percent").await;
+
+        // A newer reading from pod-a replaces its previous one
+        adapter
+            .record(
+                &MetricRequest::gauge("cpu_percent", 200.0)
+                    .with_label("instance", "pod-a")
+                    .with_label("region", "eu"),
+            )
+            .await
+            .unwrap();
+
+        let state = adapter.current_state().await;
+        assert_eq!(state.len(), 1);
+        assert_eq!(state[0].value, MetricValue::Single(250.0));
+    }
+
+    #[tokio::test]
+    async fn test_gauge_aggregation_defaults_to_last_per_instance() {
+        let adapter = MockMetricsAdapter::default();
+        record_instance_gauges(&adapter, "memory_bytes").await;
+
+        let state = adapter.current_state().await;
+        assert_eq!(state.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stats() {
+        let adapter = MockMetricsAdapter::default();
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            adapter
+                .record(&MetricRequest::histogram("payload_bytes", value))
+                .await
+                .unwrap();
+        }
+        adapter
+            .record(&MetricRequest::gauge("other", 100.0))
+            .await
+            .unwrap();
+
+        let stats = adapter.stats("payload_bytes").await.unwrap();
+        assert_eq!(stats.count, 8);
+        assert_eq!(stats.sum, 40.0);
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.max, 9.0);
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.stddev, 2.0);
+
+        assert_eq!(adapter.stats("missing").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_histogram_quantile() {
+        let adapter = MockMetricsAdapter::default();
+        for value in [5.0, 1.0, 4.0, 2.0, 3.0] {
+            adapter
+                .record(&MetricRequest::histogram("latency", value))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(adapter.histogram_quantile("latency", 0.0).await, Some(1.0));
+        assert_eq!(adapter.histogram_quantile("latency", 0.5).await, Some(3.0));
+        assert_eq!(
+            adapter.histogram_quantile("latency", 0.875).await,
+            Some(4.5)
+        );
+        assert_eq!(adapter.histogram_quantile("latency", 1.0).await, Some(5.0));
+        assert_eq!(adapter.histogram_quantile("latency", 1.5).await, None);
+        assert_eq!(adapter.histogram_quantile("missing", 0.5).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_histogram_many_records_every_observation() {
+        let adapter = MockMetricsAdapter::default();
+        let request = MetricRequest::histogram_many("latency", vec![5.0, 1.0, 4.0, 2.0, 3.0])
+            .with_label("region", "eu");
+        adapter.record(&request).await.unwrap();
+
+        assert_eq!(adapter.get_metrics_count().await, 5);
+        assert_eq!(adapter.histogram_quantile("latency", 0.0).await, Some(1.0));
+        assert_eq!(adapter.histogram_quantile("latency", 0.5).await, Some(3.0));
+        assert_eq!(adapter.histogram_quantile("latency", 1.0).await, Some(5.0));
+
+        let labels: Labels = [("region".to_string(), "eu".to_string())].into();
+        let folded = adapter
+            .fold_histogram("latency", &labels, &[2.0, 4.0])
+            .await
+            .unwrap();
+        assert_eq!(folded.count(), Some(5));
+        assert_eq!(folded.sum(), Some(15.0));
+
+        let error = adapter
+            .record(&MetricRequest::histogram_many(
+                "latency",
+                vec![1.0, f64::NAN],
+            ))
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Observation 1 of latency is NaN"));
+        assert_eq!(adapter.get_metrics_count().await, 5);
+
+        let error = adapter
+            .record(&MetricRequest::histogram_many("latency", vec![]))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("has no observations"));
+    }
+
+    #[tokio::test]
+    async fn test_histogram_many_rejected_whole_when_storage_is_short() {
+        let adapter = MockMetricsAdapter::new(
+            MockMetricsConfig::default()
+                .with_max_stored(4)
+                .with_drop_strategy(DropStrategy::RejectNew),
+        );
+        adapter
+            .record(&MetricRequest::histogram("latency", 1.0))
+            .await
+            .unwrap();
+
+        let error = adapter
+            .record(&MetricRequest::histogram_many(
+                "latency",
+                vec![2.0, 3.0, 4.0, 5.0],
+            ))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("no room for 4 observations"));
+        assert_eq!(adapter.get_metrics_count().await, 1);
+
+        adapter
+            .record(&MetricRequest::histogram_many(
+                "latency",
+                vec![2.0, 3.0, 4.0],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(adapter.get_metrics_count().await, 4);
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("memory_bytes", "memory_bytes"));
+        assert!(matches_pattern("memory_*", "memory_bytes"));
+        assert!(matches_pattern("*_bytes", "memory_bytes"));
+        assert!(matches_pattern("m*y_*s", "memory_bytes"));
+        assert!(!matches_pattern("memory_*", "cpu_percent"));
+        assert!(!matches_pattern("memory", "memory_bytes"));
+    }
+
     #[tokio::test]
     async fn test_invalid_config() {
         let config = MockMetricsConfig {
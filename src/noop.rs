@@ -0,0 +1,88 @@
+//! No-op metrics adapter
+//!
+//! This module provides an adapter that accepts every call and does nothing.
+//! Inject it where metrics should be switched off at runtime without
+//! conditional code at every call site.
+
+use super::*;
+
+/// Configuration for `NoopMetricsAdapter`; there is nothing to configure
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoopMetricsConfig;
+
+/// Metrics adapter that discards everything
+///
+/// `record` returns `Ok(())` immediately without validating, timers record
+/// nothing when dropped, `health_check` always reports healthy, and
+/// `get_snapshot` is always empty.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{MetricRequest, MetricsManager, NoopMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let metrics = NoopMetricsAdapter::new();
+/// metrics.record(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// assert!(metrics.get_snapshot().await.unwrap().is_empty());
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetricsAdapter;
+
+impl NoopMetricsAdapter {
+    /// Create a no-op adapter
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl MetricsManager for NoopMetricsAdapter {
+    type Config = NoopMetricsConfig;
+
+    async fn new(_config: Self::Config) -> Result<Self> {
+        Ok(Self)
+    }
+
+    async fn record(&self, _request: &MetricRequest) -> Result<()> {
+        Ok(())
+    }
+
+    async fn record_batch(&self, _requests: &[MetricRequest]) -> Result<()> {
+        Ok(())
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        TimerGuard::new(name.to_string(), labels, |_| {})
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        Ok(HealthStatus::healthy())
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_noop_skips_validation_and_stores_nothing() {
+        let metrics = <NoopMetricsAdapter as MetricsManager>::new(NoopMetricsConfig)
+            .await
+            .unwrap();
+
+        assert!(metrics
+            .record(&MetricRequest::counter("invalid name", f64::NAN))
+            .await
+            .is_ok());
+        drop(metrics.start_timer("invalid timer", Labels::new()));
+        metrics.flush().await.unwrap();
+
+        assert!(metrics.get_snapshot().await.unwrap().is_empty());
+        assert!(metrics.health_check().await.unwrap().is_healthy);
+    }
+}
@@ -0,0 +1,298 @@
+//! OTLP-friendly intermediate representation
+//!
+//! This module maps `MetricSnapshot`s onto the shape of OTLP data points
+//! without depending on protobuf. OpenTelemetry adapters translate
+//! `OtlpDataPoint` field by field into their generated OTLP types instead of
+//! re-deriving the mapping rules themselves.
+
+use super::*;
+
+/// OTLP metric data kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OtlpMetricKind {
+    /// Sum data (counters)
+    Sum,
+
+    /// Gauge data
+    Gauge,
+
+    /// Explicit-bucket histogram data (histograms and timers)
+    Histogram,
+}
+
+/// Value of an OTLP data point
+#[derive(Debug, Clone, PartialEq)]
+pub enum OtlpValue {
+    /// Number data point value for sums and gauges
+    Double(f64),
+
+    /// Histogram data point value
+    ///
+    /// Follows OTLP conventions: `bucket_counts` are per bucket (not
+    /// cumulative) and have one more entry than `explicit_bounds`, the last
+    /// counting observations above the highest bound.
+    Histogram {
+        /// Number of observations
+        count: u64,
+        /// Sum of all observations
+        sum: f64,
+        /// Observations per bucket
+        bucket_counts: Vec<u64>,
+        /// Finite bucket upper bounds, ascending
+        explicit_bounds: Vec<f64>,
+    },
+}
+
+/// A metric snapshot in the shape of an OTLP data point
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpDataPoint {
+    /// Metric name
+    pub name: String,
+
+    /// Metric description, from the snapshot's help text (empty if none)
+    pub description: String,
+
+    /// UCUM unit, e.g. `"s"` (empty if unknown)
+    pub unit: String,
+
+    /// OTLP data kind
+    pub kind: OtlpMetricKind,
+
+    /// Aggregation temporality; `None` for gauges, which have none in OTLP
+    pub temporality: Option<AggregationTemporality>,
+
+    /// Whether a sum only ever increases
+    pub is_monotonic: bool,
+
+    /// Attributes, sorted by key
+    pub attributes: Vec<(String, String)>,
+
+    /// Start of the interval the value covers (Unix epoch nanoseconds, 0 if unknown)
+    pub start_time_unix_nano: u64,
+
+    /// Time the value was observed (Unix epoch nanoseconds)
+    pub time_unix_nano: u64,
+
+    /// The data point value
+    pub value: OtlpValue,
+}
+
+/// Map a snapshot onto an OTLP data point
+///
+/// - Counters become monotonic sums
+/// - Gauges become gauges
+/// - Histograms and timers become explicit-bucket histograms; timers are
+///   recorded in seconds, so their unit is `"s"`
+///
+/// Sums and histograms default to cumulative temporality when the snapshot
+/// doesn't carry one. Other units are inferred from the Prometheus-style
+/// name suffixes `_seconds`, `_bytes`, and `_ratio`.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{to_otlp_datapoint, MetricRequest, MetricSnapshot, OtlpMetricKind};
+///
+/// let snapshot = MetricSnapshot::from(&MetricRequest::counter("requests_total", 1.0));
+/// let point = to_otlp_datapoint(&snapshot);
+///
+/// assert_eq!(point.kind, OtlpMetricKind::Sum);
+/// assert!(point.is_monotonic);
+/// ```
+pub fn to_otlp_datapoint(snapshot: &MetricSnapshot) -> OtlpDataPoint {
+    let kind = match snapshot.metric_type {
+        MetricType::Counter => OtlpMetricKind::Sum,
+        MetricType::Gauge | MetricType::Info => OtlpMetricKind::Gauge,
+        MetricType::Histogram | MetricType::Timer => OtlpMetricKind::Histogram,
+    };
+
+    let temporality = match kind {
+        OtlpMetricKind::Gauge => None,
+        _ => Some(
+            snapshot
+                .temporality
+                .unwrap_or(AggregationTemporality::Cumulative),
+        ),
+    };
+
+    let value = match (kind, &snapshot.value) {
+        (OtlpMetricKind::Histogram, value) => otlp_histogram(value),
+        (_, MetricValue::Single(value)) => OtlpValue::Double(*value),
+        // A histogram value recorded under a counter or gauge keeps its sum
+        (_, MetricValue::Histogram { sum, .. }) => OtlpValue::Double(*sum),
+    };
+
+    let mut attributes: Vec<(String, String)> = snapshot
+        .labels
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    attributes.sort();
+
+    OtlpDataPoint {
+        name: snapshot.name.clone(),
+        description: snapshot.help.clone().unwrap_or_default(),
+        unit: otlp_unit(snapshot).to_string(),
+        kind,
+        temporality,
+        is_monotonic: snapshot.metric_type == MetricType::Counter,
+        attributes,
+        start_time_unix_nano: snapshot.start_timestamp.unwrap_or(0),
+        time_unix_nano: snapshot.timestamp,
+        value,
+    }
+}
+
+/// Convert a value to OTLP histogram form with per-bucket counts
+fn otlp_histogram(value: &MetricValue) -> OtlpValue {
+    match value {
+        // A single observation is a one-bucket histogram
+        MetricValue::Single(value) => OtlpValue::Histogram {
+            count: 1,
+            sum: *value,
+            bucket_counts: vec![1],
+            explicit_bounds: Vec::new(),
+        },
+        MetricValue::Histogram {
+            sum,
+            count,
+            buckets,
+        } => {
+            let mut explicit_bounds = Vec::new();
+            let mut bucket_counts = Vec::new();
+            let mut previous = 0;
+            for bucket in buckets.iter().filter(|b| b.upper_bound.is_finite()) {
+                explicit_bounds.push(bucket.upper_bound);
+                bucket_counts.push(bucket.count.saturating_sub(previous));
+                previous = bucket.count;
+            }
+            // Overflow bucket above the highest finite bound
+            bucket_counts.push(count.saturating_sub(previous));
+
+            OtlpValue::Histogram {
+                count: *count,
+                sum: *sum,
+                bucket_counts,
+                explicit_bounds,
+            }
+        }
+    }
+}
+
+/// UCUM unit for a snapshot, from its type or name suffix
+fn otlp_unit(snapshot: &MetricSnapshot) -> &'static str {
+    let name = snapshot.name.trim_end_matches("_total");
+    if snapshot.metric_type == MetricType::Timer || name.ends_with("_seconds") {
+        "s"
+    } else if name.ends_with("_bytes") {
+        "By"
+    } else if name.ends_with("_ratio") {
+        "1"
+    } else {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_otlp_kinds_and_temporality() {
+        let counter = to_otlp_datapoint(&MetricSnapshot::from(
+            &MetricRequest::counter("requests_total", 2.0).with_help("Requests served"),
+        ));
+        assert_eq!(counter.kind, OtlpMetricKind::Sum);
+        assert_eq!(
+            counter.temporality,
+            Some(AggregationTemporality::Cumulative)
+        );
+        assert!(counter.is_monotonic);
+        assert_eq!(counter.description, "Requests served");
+        assert_eq!(counter.value, OtlpValue::Double(2.0));
+
+        let gauge = to_otlp_datapoint(&MetricSnapshot::from(&MetricRequest::gauge(
+            "memory_bytes",
+            512.0,
+        )));
+        assert_eq!(gauge.kind, OtlpMetricKind::Gauge);
+        assert_eq!(gauge.temporality, None);
+        assert!(!gauge.is_monotonic);
+        assert_eq!(gauge.unit, "By");
+
+        let timer = to_otlp_datapoint(&MetricSnapshot::from(&MetricRequest::timer(
+            "db_query",
+            Duration::from_millis(250),
+        )));
+        assert_eq!(timer.kind, OtlpMetricKind::Histogram);
+        assert_eq!(timer.temporality, Some(AggregationTemporality::Cumulative));
+        assert_eq!(timer.unit, "s");
+        assert_eq!(
+            timer.value,
+            OtlpValue::Histogram {
+                count: 1,
+                sum: 0.25,
+                bucket_counts: vec![1],
+                explicit_bounds: Vec::new(),
+            }
+        );
+
+        let delta = MetricRequest::data_point(
+            "requests_total",
+            MetricType::Counter,
+            MetricValue::Single(1.0),
+            100,
+            200,
+            AggregationTemporality::Delta,
+        )
+        .unwrap();
+        let delta = to_otlp_datapoint(&MetricSnapshot::from(&delta));
+        assert_eq!(delta.temporality, Some(AggregationTemporality::Delta));
+        assert_eq!(delta.start_time_unix_nano, 100);
+        assert_eq!(delta.time_unix_nano, 200);
+    }
+
+    #[test]
+    fn test_otlp_histogram_buckets() {
+        let snapshot = MetricSnapshot::new(
+            "request_duration_seconds".to_string(),
+            MetricType::Histogram,
+            MetricValue::Histogram {
+                sum: 3.5,
+                count: 6,
+                buckets: vec![
+                    HistogramBucket {
+                        upper_bound: 0.1,
+                        count: 2,
+                    },
+                    HistogramBucket {
+                        upper_bound: 1.0,
+                        count: 5,
+                    },
+                    HistogramBucket {
+                        upper_bound: f64::INFINITY,
+                        count: 6,
+                    },
+                ],
+            },
+            [("route".to_string(), "/a".to_string())].into(),
+        );
+
+        let point = to_otlp_datapoint(&snapshot);
+        assert_eq!(point.kind, OtlpMetricKind::Histogram);
+        assert_eq!(point.unit, "s");
+        assert_eq!(
+            point.attributes,
+            vec![("route".to_string(), "/a".to_string())]
+        );
+        assert_eq!(
+            point.value,
+            OtlpValue::Histogram {
+                count: 6,
+                sum: 3.5,
+                bucket_counts: vec![2, 3, 1],
+                explicit_bounds: vec![0.1, 1.0],
+            }
+        );
+    }
+}
@@ -5,7 +5,9 @@
 //! not HOW it's implemented.
 
 use super::*;
+use crate::clock::unix_nanos;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 /// **Primary Port Interface** for metrics collection
 ///
@@ -82,6 +84,64 @@ pub trait MetricsManager: Send + Sync {
     /// * `Result<()>` - Success or error using TYL error handling
     async fn record(&self, request: &MetricRequest) -> Result<()>;
 
+    /// Record several metric events at once
+    ///
+    /// Batching adapters can override this to send one request to the
+    /// backend. Every request is attempted even if some fail.
+    ///
+    /// # Arguments
+    /// * `requests` - The metric requests to record
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or the first error encountered
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        // Default implementation records one by one
+        let mut first_error = None;
+        for request in requests {
+            if let Err(error) = self.record(request).await {
+                first_error.get_or_insert(error);
+            }
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Record a metric with an explicit event time
+    ///
+    /// Used to backfill historical data. Adapters whose backend accepts event
+    /// times should honor `timestamp_ns`; others may record at ingestion
+    /// time instead.
+    ///
+    /// # Arguments
+    /// * `request` - The metric request to record
+    /// * `timestamp_ns` - Event time in Unix epoch nanoseconds
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error using TYL error handling
+    async fn record_at(&self, request: &MetricRequest, timestamp_ns: u64) -> Result<()> {
+        // Default implementation records a copy carrying the event time
+        self.record(&request.clone().with_timestamp(timestamp_ns))
+            .await
+    }
+
+    /// Record a metric request that was validated up front
+    ///
+    /// Adapters that validate in `record` can override this to skip the
+    /// repeated checks on hot paths that reuse a request template.
+    ///
+    /// # Arguments
+    /// * `request` - A request produced by `MetricRequest::validate`
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error using TYL error handling
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        // Default implementation records normally - validation is cheap for most adapters
+        self.record(request.request()).await
+    }
+
     /// Start a timer and return a guard that records duration when dropped
     ///
     /// This provides a convenient RAII pattern for measuring durations.
@@ -93,8 +153,29 @@ pub trait MetricsManager: Send + Sync {
     ///
     /// # Returns
     /// * `TimerGuard` - RAII guard that records duration on drop
+    ///
+    /// The default `start_counter_guard` reuses this guard's recorder, so it
+    /// may also be handed `counter_inc` requests, not only timers.
     fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard;
 
+    /// Start a guard that increments a counter when dropped
+    ///
+    /// Backs `MetricsManagerExt::start_scope_counter`. The default reuses
+    /// `start_timer`'s guard for its name, labels, and recorder, which then
+    /// receives a `counter_inc` request instead of a timer. Adapters whose
+    /// `start_timer` depends on the metric type (e.g. filtering on it) should
+    /// override this.
+    ///
+    /// # Arguments
+    /// * `name` - The counter name to increment
+    /// * `labels` - Labels to attach to the increment
+    ///
+    /// # Returns
+    /// * `CounterGuard` - RAII guard that increments the counter on drop
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        CounterGuard::from_timer(self.start_timer(name, labels))
+    }
+
     /// Check the health status of the metrics adapter
     ///
     /// This method allows monitoring systems to verify that the metrics
@@ -104,6 +185,32 @@ pub trait MetricsManager: Send + Sync {
     /// * `Result<HealthStatus>` - Current health status or error
     async fn health_check(&self) -> Result<HealthStatus>;
 
+    /// Check the health of each subsystem of the metrics adapter
+    ///
+    /// Adapters with several moving parts (connections, buffers, flushing)
+    /// can report each one separately for granular dashboards.
+    ///
+    /// # Returns
+    /// * `Result<Vec<ComponentHealth>>` - One status per component or error
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        // Default implementation reports the overall status as a single component
+        let status = self.health_check().await?;
+        Ok(vec![ComponentHealth::new(component_name::<Self>(), status)])
+    }
+
+    /// Flush any buffered metrics to the backend
+    ///
+    /// Push-based and buffering adapters should send everything pending and
+    /// return once done, e.g. on graceful shutdown. Adapters that record
+    /// synchronously have nothing to flush.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error using TYL error handling
+    async fn flush(&self) -> Result<()> {
+        // Default implementation has nothing buffered
+        Ok(())
+    }
+
     /// Get current metrics snapshot (optional, primarily for debugging)
     ///
     /// Not all adapters may implement this meaningfully (e.g., push-based systems
@@ -115,10 +222,225 @@ pub trait MetricsManager: Send + Sync {
         // Default implementation returns empty - push-based systems don't store metrics
         Ok(Vec::new())
     }
+
+    /// Get the snapshots matching `filter`
+    ///
+    /// Adapters holding many metrics should override this to avoid building
+    /// the full snapshot first.
+    ///
+    /// # Arguments
+    /// * `filter` - Criteria every returned snapshot must satisfy
+    ///
+    /// # Returns
+    /// * `Result<Vec<MetricSnapshot>>` - Matching metrics or empty if not applicable
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        // Default implementation filters the full snapshot
+        let mut snapshot = self.get_snapshot().await?;
+        snapshot.retain(|s| filter.matches(s));
+        Ok(snapshot)
+    }
+
+    /// Get up to `limit` snapshots starting at `offset`
+    ///
+    /// Pages follow the order of `get_snapshot`. Adapters holding many
+    /// metrics should override this to avoid building the full snapshot for
+    /// every page.
+    ///
+    /// # Arguments
+    /// * `offset` - Number of snapshots to skip
+    /// * `limit` - Maximum number of snapshots to return
+    ///
+    /// # Returns
+    /// * `Result<Vec<MetricSnapshot>>` - The page, empty past the end
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        // Default implementation pages through the full snapshot
+        let snapshot = self.get_snapshot().await?;
+        Ok(snapshot.into_iter().skip(offset).take(limit).collect())
+    }
+
+    /// Get the number of snapshots `get_snapshot` would return
+    ///
+    /// # Returns
+    /// * `Result<usize>` - Snapshot count, for sizing `get_snapshot_paged` loops
+    async fn snapshot_len(&self) -> Result<usize> {
+        // Default implementation counts the full snapshot
+        Ok(self.get_snapshot().await?.len())
+    }
+}
+
+/// Object-safe view of a `MetricsManager`
+///
+/// `MetricsManager` can't be used as `dyn MetricsManager` because of its
+/// associated `Config` type and `new` constructor. This shim exposes the
+/// runtime operations only, and is implemented for every adapter, so any
+/// adapter can be stored as `Arc<dyn DynMetricsManager>`, e.g. as a global
+/// recorder. Methods carry a `_dyn` suffix so they never clash with the
+/// `MetricsManager` methods when both traits are in scope.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{DynMetricsManager, MetricRequest, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let recorder: Arc<dyn DynMetricsManager> = Arc::new(MockMetricsAdapter::default());
+/// recorder.record_dyn(&MetricRequest::counter("requests", 1.0)).await.unwrap();
+/// assert_eq!(recorder.get_snapshot_dyn().await.unwrap().len(), 1);
+/// # });
+/// ```
+#[async_trait]
+pub trait DynMetricsManager: Send + Sync {
+    /// See `MetricsManager::record`
+    async fn record_dyn(&self, request: &MetricRequest) -> Result<()>;
+
+    /// See `MetricsManager::record_batch`
+    async fn record_batch_dyn(&self, requests: &[MetricRequest]) -> Result<()>;
+
+    /// See `MetricsManager::record_at`
+    async fn record_at_dyn(&self, request: &MetricRequest, timestamp_ns: u64) -> Result<()>;
+
+    /// See `MetricsManager::record_validated`
+    async fn record_validated_dyn(&self, request: &ValidatedMetricRequest) -> Result<()>;
+
+    /// See `MetricsManager::start_timer`
+    fn start_timer_dyn(&self, name: &str, labels: Labels) -> TimerGuard;
+
+    /// See `MetricsManager::start_counter_guard`
+    fn start_counter_guard_dyn(&self, name: &str, labels: Labels) -> CounterGuard;
+
+    /// See `MetricsManager::health_check`
+    async fn health_check_dyn(&self) -> Result<HealthStatus>;
+
+    /// See `MetricsManager::health_check_detailed`
+    async fn health_check_detailed_dyn(&self) -> Result<Vec<ComponentHealth>>;
+
+    /// See `MetricsManager::flush`
+    async fn flush_dyn(&self) -> Result<()>;
+
+    /// See `MetricsManager::get_snapshot`
+    async fn get_snapshot_dyn(&self) -> Result<Vec<MetricSnapshot>>;
+
+    /// See `MetricsManager::get_snapshot_filtered`
+    async fn get_snapshot_filtered_dyn(
+        &self,
+        filter: SnapshotFilter,
+    ) -> Result<Vec<MetricSnapshot>>;
+}
+
+#[async_trait]
+impl<M: MetricsManager> DynMetricsManager for M {
+    async fn record_dyn(&self, request: &MetricRequest) -> Result<()> {
+        self.record(request).await
+    }
+
+    async fn record_batch_dyn(&self, requests: &[MetricRequest]) -> Result<()> {
+        self.record_batch(requests).await
+    }
+
+    async fn record_at_dyn(&self, request: &MetricRequest, timestamp_ns: u64) -> Result<()> {
+        self.record_at(request, timestamp_ns).await
+    }
+
+    async fn record_validated_dyn(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        self.record_validated(request).await
+    }
+
+    fn start_timer_dyn(&self, name: &str, labels: Labels) -> TimerGuard {
+        self.start_timer(name, labels)
+    }
+
+    fn start_counter_guard_dyn(&self, name: &str, labels: Labels) -> CounterGuard {
+        self.start_counter_guard(name, labels)
+    }
+
+    async fn health_check_dyn(&self) -> Result<HealthStatus> {
+        self.health_check().await
+    }
+
+    async fn health_check_detailed_dyn(&self) -> Result<Vec<ComponentHealth>> {
+        self.health_check_detailed().await
+    }
+
+    async fn flush_dyn(&self) -> Result<()> {
+        self.flush().await
+    }
+
+    async fn get_snapshot_dyn(&self) -> Result<Vec<MetricSnapshot>> {
+        self.get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered_dyn(
+        &self,
+        filter: SnapshotFilter,
+    ) -> Result<Vec<MetricSnapshot>> {
+        self.get_snapshot_filtered(filter).await
+    }
+}
+
+/// Shared adapters are adapters too, so one instance can back several owners
+#[async_trait]
+impl<M: MetricsManager> MetricsManager for std::sync::Arc<M> {
+    type Config = M::Config;
+
+    async fn new(config: Self::Config) -> Result<Self> {
+        Ok(std::sync::Arc::new(M::new(config).await?))
+    }
+
+    async fn record(&self, request: &MetricRequest) -> Result<()> {
+        (**self).record(request).await
+    }
+
+    async fn record_batch(&self, requests: &[MetricRequest]) -> Result<()> {
+        (**self).record_batch(requests).await
+    }
+
+    async fn record_at(&self, request: &MetricRequest, timestamp_ns: u64) -> Result<()> {
+        (**self).record_at(request, timestamp_ns).await
+    }
+
+    async fn record_validated(&self, request: &ValidatedMetricRequest) -> Result<()> {
+        (**self).record_validated(request).await
+    }
+
+    fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+        (**self).start_timer(name, labels)
+    }
+
+    fn start_counter_guard(&self, name: &str, labels: Labels) -> CounterGuard {
+        (**self).start_counter_guard(name, labels)
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        (**self).health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> Result<Vec<ComponentHealth>> {
+        (**self).health_check_detailed().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        (**self).flush().await
+    }
+
+    async fn get_snapshot(&self) -> Result<Vec<MetricSnapshot>> {
+        (**self).get_snapshot().await
+    }
+
+    async fn get_snapshot_filtered(&self, filter: SnapshotFilter) -> Result<Vec<MetricSnapshot>> {
+        (**self).get_snapshot_filtered(filter).await
+    }
+
+    async fn get_snapshot_paged(&self, offset: usize, limit: usize) -> Result<Vec<MetricSnapshot>> {
+        (**self).get_snapshot_paged(offset, limit).await
+    }
+
+    async fn snapshot_len(&self) -> Result<usize> {
+        (**self).snapshot_len().await
+    }
 }
 
 /// Health status information for metrics adapters
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HealthStatus {
     /// Whether the adapter is healthy
     pub is_healthy: bool,
@@ -127,6 +449,7 @@ pub struct HealthStatus {
     pub message: String,
 
     /// Optional additional metadata
+    #[serde(serialize_with = "crate::types::serialize_sorted_labels")]
     pub metadata: std::collections::HashMap<String, String>,
 
     /// Timestamp of health check (Unix epoch seconds)
@@ -140,10 +463,7 @@ impl HealthStatus {
             is_healthy: true,
             message: "Metrics adapter is healthy".to_string(),
             metadata: std::collections::HashMap::new(),
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
+            timestamp: unix_nanos() / 1_000_000_000,
         }
     }
 
@@ -153,10 +473,7 @@ impl HealthStatus {
             is_healthy: false,
             message: message.into(),
             metadata: std::collections::HashMap::new(),
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
+            timestamp: unix_nanos() / 1_000_000_000,
         }
     }
 
@@ -165,6 +482,14 @@ impl HealthStatus {
         self.metadata.insert(key.into(), value.into());
         self
     }
+
+    /// Override the health check timestamp (Unix epoch seconds)
+    ///
+    /// Useful for deterministic fixtures and golden tests.
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
 }
 
 impl Default for HealthStatus {
@@ -173,6 +498,33 @@ impl Default for HealthStatus {
     }
 }
 
+/// Health of one subsystem of a metrics adapter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentHealth {
+    /// Component name, e.g. "connection" or "storage"
+    pub name: String,
+
+    /// Status of this component
+    pub status: HealthStatus,
+}
+
+impl ComponentHealth {
+    /// Create a component health entry
+    pub fn new(name: impl Into<String>, status: HealthStatus) -> Self {
+        Self {
+            name: name.into(),
+            status,
+        }
+    }
+}
+
+/// Short name of an adapter type, without module path or generics
+pub(crate) fn component_name<T: ?Sized>() -> &'static str {
+    let full = std::any::type_name::<T>();
+    let base = full.split('<').next().unwrap_or(full);
+    base.rsplit("::").next().unwrap_or(base)
+}
+
 impl std::fmt::Display for HealthStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let status = if self.is_healthy {
@@ -226,4 +578,115 @@ mod tests {
         assert!(unhealthy.to_string().contains("[UNHEALTHY]"));
         assert!(unhealthy.to_string().contains("Error occurred"));
     }
+
+    #[test]
+    fn test_health_status_serde_round_trip() {
+        let status = HealthStatus::healthy()
+            .with_metadata("version", "1.0.0")
+            .with_metadata("endpoint", "localhost:9090")
+            .with_timestamp(1_700_000_000);
+
+        let json = serde_json::to_string(&status).unwrap();
+        assert!(json.contains(r#""metadata":{"endpoint":"localhost:9090","version":"1.0.0"}"#));
+        assert!(json.contains(r#""timestamp":1700000000"#));
+
+        let parsed: HealthStatus = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, status);
+    }
+
+    struct MinimalAdapter;
+
+    #[async_trait]
+    impl MetricsManager for MinimalAdapter {
+        type Config = ();
+
+        async fn new(_config: Self::Config) -> Result<Self> {
+            Ok(Self)
+        }
+
+        async fn record(&self, _request: &MetricRequest) -> Result<()> {
+            Ok(())
+        }
+
+        fn start_timer(&self, name: &str, labels: Labels) -> TimerGuard {
+            TimerGuard::new(name.to_string(), labels, |_| {})
+        }
+
+        async fn health_check(&self) -> Result<HealthStatus> {
+            Ok(HealthStatus::unhealthy("Backend unreachable"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check_detailed_default_wraps_health_check() {
+        let components = MinimalAdapter.health_check_detailed().await.unwrap();
+
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].name, "MinimalAdapter");
+        assert!(!components[0].status.is_healthy);
+        assert_eq!(components[0].status.message, "Backend unreachable");
+    }
+
+    #[tokio::test]
+    async fn test_record_batch_default_attempts_all() {
+        let metrics = MockMetricsAdapter::default();
+        let requests = vec![
+            MetricRequest::counter("first", 1.0),
+            MetricRequest::counter("invalid name", 1.0),
+            MetricRequest::counter("third", 1.0),
+        ];
+
+        assert!(metrics.record_batch(&requests).await.is_err());
+        assert_eq!(metrics.get_metrics_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_dyn_manager_forwards_overridable_methods() {
+        let metrics = std::sync::Arc::new(MockMetricsAdapter::default());
+        let recorder: std::sync::Arc<dyn DynMetricsManager> = metrics.clone();
+
+        recorder
+            .record_at_dyn(&MetricRequest::gauge("backfilled", 1.0), 42)
+            .await
+            .unwrap();
+        drop(recorder.start_counter_guard_dyn("jobs_total", Labels::new()));
+
+        let gauges = recorder
+            .get_snapshot_filtered_dyn(SnapshotFilter::new().with_metric_type(MetricType::Gauge))
+            .await
+            .unwrap();
+        assert_eq!(gauges.len(), 1);
+        assert_eq!(gauges[0].timestamp, 42);
+
+        let stored = metrics.get_stored_metrics().await;
+        assert!(stored
+            .iter()
+            .any(|m| m.name == "jobs_total" && m.metric_type == MetricType::Counter));
+
+        let names = |components: Vec<ComponentHealth>| -> Vec<String> {
+            components.into_iter().map(|c| c.name).collect()
+        };
+        assert_eq!(
+            names(recorder.health_check_detailed_dyn().await.unwrap()),
+            names(metrics.health_check_detailed().await.unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_arc_adapter_forwards() {
+        let metrics = std::sync::Arc::new(MockMetricsAdapter::default());
+        let shared = metrics.clone();
+
+        shared
+            .record(&MetricRequest::counter("requests", 1.0))
+            .await
+            .unwrap();
+        assert_eq!(metrics.get_metrics_count().await, 1);
+        assert!(
+            MetricsManager::health_check(&shared)
+                .await
+                .unwrap()
+                .is_healthy
+        );
+    }
 }
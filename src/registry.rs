@@ -0,0 +1,437 @@
+//! Up-front metric declarations and typed recording handles
+//!
+//! A `MetricRegistry` is the single source of truth for which metrics a
+//! service emits. Metrics are declared once at startup, where invalid or
+//! conflicting definitions fail fast, and recorded afterwards through typed
+//! handles that reuse a pre-validated request template.
+
+use super::*;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Declaration of one metric
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricDefinition {
+    /// Metric name
+    pub name: String,
+
+    /// Metric type (counter, gauge, or histogram)
+    pub metric_type: MetricType,
+
+    /// Help text, attached to every recorded request
+    pub help: Option<String>,
+
+    /// Unit, e.g. `"seconds"` or `"bytes"`
+    pub unit: Option<String>,
+
+    /// Histogram bucket bounds, for adapters that configure them up front
+    pub buckets: Option<Vec<f64>>,
+}
+
+impl MetricDefinition {
+    /// Declare a metric of the given type
+    pub fn new(name: impl Into<String>, metric_type: MetricType) -> Self {
+        Self {
+            name: name.into(),
+            metric_type,
+            help: None,
+            unit: None,
+            buckets: None,
+        }
+    }
+
+    /// Declare a counter
+    pub fn counter(name: impl Into<String>) -> Self {
+        Self::new(name, MetricType::Counter)
+    }
+
+    /// Declare a gauge
+    pub fn gauge(name: impl Into<String>) -> Self {
+        Self::new(name, MetricType::Gauge)
+    }
+
+    /// Declare a histogram
+    pub fn histogram(name: impl Into<String>) -> Self {
+        Self::new(name, MetricType::Histogram)
+    }
+
+    /// Set the help text
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Set the unit
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// Set the histogram bucket bounds
+    pub fn with_buckets(mut self, buckets: Vec<f64>) -> Self {
+        self.buckets = Some(buckets);
+        self
+    }
+
+    /// Check the name, unit, and buckets, normalizing bucket bounds
+    fn validate(mut self) -> Result<Self> {
+        validate_metric_name(&self.name)?;
+
+        if self.metric_type == MetricType::Timer {
+            return Err(metrics_config_error(
+                &self.name,
+                "Timers are recorded through start_timer and can't be declared",
+            ));
+        }
+
+        if matches!(&self.unit, Some(unit) if unit.trim().is_empty()) {
+            return Err(metrics_config_error(&self.name, "Unit can't be empty"));
+        }
+
+        if let Some(buckets) = &self.buckets {
+            if self.metric_type != MetricType::Histogram {
+                return Err(metrics_config_error(
+                    &self.name,
+                    format!("Buckets only apply to histograms, not {}", self.metric_type),
+                ));
+            }
+            self.buckets = Some(validate_histogram_buckets(buckets)?);
+        }
+
+        Ok(self)
+    }
+
+    /// Build the validated request template handles record from
+    fn template(&self) -> Result<ValidatedMetricRequest> {
+        let mut request = match self.metric_type {
+            MetricType::Counter => MetricRequest::counter_inc(self.name.as_str(), 0.0),
+            MetricType::Gauge => MetricRequest::gauge(self.name.as_str(), 0.0),
+            _ => MetricRequest::histogram(self.name.as_str(), 0.0),
+        };
+        if let Some(help) = &self.help {
+            request = request.with_help(help.as_str());
+        }
+        request.validate()
+    }
+}
+
+/// Registry of declared metrics producing typed recording handles
+///
+/// Declaring the same name twice is only allowed with an identical
+/// definition, so two modules may share a declaration but can't disagree on
+/// a metric's type or buckets.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use tyl_metrics_port::{MetricDefinition, MetricRegistry, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let mock = Arc::new(MockMetricsAdapter::default());
+/// let registry = MetricRegistry::new(mock.clone());
+/// registry
+///     .declare(MetricDefinition::counter("requests_total").with_help("Requests served"))
+///     .unwrap();
+///
+/// let requests = registry.counter("requests_total").unwrap().with_label("method", "GET").unwrap();
+/// requests.incr().await.unwrap();
+/// assert_eq!(mock.get_metrics_count().await, 1);
+/// # });
+/// ```
+pub struct MetricRegistry {
+    manager: Arc<dyn DynMetricsManager>,
+    definitions: RwLock<HashMap<String, MetricDefinition>>,
+}
+
+impl MetricRegistry {
+    /// Create an empty registry recording through `manager`
+    pub fn new(manager: Arc<dyn DynMetricsManager>) -> Self {
+        Self {
+            manager,
+            definitions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Declare a metric
+    ///
+    /// Fails if the definition is invalid or the name is already declared
+    /// with a different definition.
+    pub fn declare(&self, definition: MetricDefinition) -> Result<()> {
+        let definition = definition.validate()?;
+        let mut definitions = self.definitions.write().unwrap_or_else(|p| p.into_inner());
+
+        match definitions.get(&definition.name) {
+            Some(existing) if existing.metric_type != definition.metric_type => {
+                Err(metrics_config_error(
+                    &definition.name,
+                    format!(
+                        "Already declared as {}, can't redeclare as {}",
+                        existing.metric_type, definition.metric_type
+                    ),
+                ))
+            }
+            Some(existing) if *existing != definition => Err(metrics_config_error(
+                &definition.name,
+                "Already declared with a different definition",
+            )),
+            Some(_) => Ok(()),
+            None => {
+                definitions.insert(definition.name.clone(), definition);
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the definition of a declared metric
+    pub fn definition(&self, name: &str) -> Option<MetricDefinition> {
+        self.definitions
+            .read()
+            .unwrap_or_else(|p| p.into_inner())
+            .get(name)
+            .cloned()
+    }
+
+    /// Get every definition, sorted by name
+    pub fn definitions(&self) -> Vec<MetricDefinition> {
+        let mut definitions: Vec<MetricDefinition> = self
+            .definitions
+            .read()
+            .unwrap_or_else(|p| p.into_inner())
+            .values()
+            .cloned()
+            .collect();
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        definitions
+    }
+
+    /// Get a handle to a declared counter
+    pub fn counter(&self, name: &str) -> Result<CounterHandle> {
+        self.handle(name, MetricType::Counter).map(CounterHandle)
+    }
+
+    /// Get a handle to a declared gauge
+    pub fn gauge(&self, name: &str) -> Result<GaugeHandle> {
+        self.handle(name, MetricType::Gauge).map(GaugeHandle)
+    }
+
+    /// Get a handle to a declared histogram
+    pub fn histogram(&self, name: &str) -> Result<HistogramHandle> {
+        self.handle(name, MetricType::Histogram)
+            .map(HistogramHandle)
+    }
+
+    /// Build an unlabeled handle for a declared metric of the expected type
+    fn handle(&self, name: &str, expected: MetricType) -> Result<Handle> {
+        let definition = self
+            .definition(name)
+            .ok_or_else(|| metrics_config_error(name, "Metric was not declared"))?;
+
+        if definition.metric_type != expected {
+            return Err(metrics_config_error(
+                name,
+                format!("Declared as {}, not {}", definition.metric_type, expected),
+            ));
+        }
+
+        Ok(Handle {
+            template: definition.template()?,
+            manager: self.manager.clone(),
+        })
+    }
+}
+
+/// Validated template plus the manager it records through
+#[derive(Clone)]
+struct Handle {
+    template: ValidatedMetricRequest,
+    manager: Arc<dyn DynMetricsManager>,
+}
+
+impl Handle {
+    /// Copy the handle with one more label, validating the labels once
+    fn with_label(&self, key: impl Into<String>, value: impl Into<String>) -> Result<Self> {
+        let request = self.template.request().clone().with_label(key, value);
+        Ok(Self {
+            template: request.validate()?,
+            manager: self.manager.clone(),
+        })
+    }
+
+    /// Record the template with `value`, checking only the value
+    async fn record(&self, value: f64) -> Result<()> {
+        let request = self.template.clone().with_value(value)?;
+        self.manager.record_validated_dyn(&request).await
+    }
+}
+
+/// Recording handle for a declared counter
+#[derive(Clone)]
+pub struct CounterHandle(Handle);
+
+impl CounterHandle {
+    /// Copy the handle with an extra label, validated here rather than per record
+    pub fn with_label(&self, key: impl Into<String>, value: impl Into<String>) -> Result<Self> {
+        self.0.with_label(key, value).map(Self)
+    }
+
+    /// Get the labels every recording carries
+    pub fn labels(&self) -> &Labels {
+        self.0.template.request().labels()
+    }
+
+    /// Increment by one
+    pub async fn incr(&self) -> Result<()> {
+        self.0.record(1.0).await
+    }
+
+    /// Increment by `value`, which must not be negative
+    pub async fn incr_by(&self, value: f64) -> Result<()> {
+        self.0.record(value).await
+    }
+}
+
+/// Recording handle for a declared gauge
+#[derive(Clone)]
+pub struct GaugeHandle(Handle);
+
+impl GaugeHandle {
+    /// Copy the handle with an extra label, validated here rather than per record
+    pub fn with_label(&self, key: impl Into<String>, value: impl Into<String>) -> Result<Self> {
+        self.0.with_label(key, value).map(Self)
+    }
+
+    /// Get the labels every recording carries
+    pub fn labels(&self) -> &Labels {
+        self.0.template.request().labels()
+    }
+
+    /// Set the gauge to `value`
+    pub async fn set(&self, value: f64) -> Result<()> {
+        self.0.record(value).await
+    }
+}
+
+/// Recording handle for a declared histogram
+#[derive(Clone)]
+pub struct HistogramHandle(Handle);
+
+impl HistogramHandle {
+    /// Copy the handle with an extra label, validated here rather than per record
+    pub fn with_label(&self, key: impl Into<String>, value: impl Into<String>) -> Result<Self> {
+        self.0.with_label(key, value).map(Self)
+    }
+
+    /// Get the labels every recording carries
+    pub fn labels(&self) -> &Labels {
+        self.0.template.request().labels()
+    }
+
+    /// Record one observation
+    pub async fn observe(&self, value: f64) -> Result<()> {
+        self.0.record(value).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_rejects_conflicting_declarations() {
+        let registry = MetricRegistry::new(Arc::new(MockMetricsAdapter::default()));
+        registry
+            .declare(MetricDefinition::counter("jobs_total").with_help("Jobs run"))
+            .unwrap();
+
+        // Identical redeclaration is allowed
+        assert!(registry
+            .declare(MetricDefinition::counter("jobs_total").with_help("Jobs run"))
+            .is_ok());
+
+        let error = registry
+            .declare(MetricDefinition::gauge("jobs_total"))
+            .unwrap_err();
+        assert!(error.to_string().contains("Already declared as counter"));
+        assert!(registry
+            .declare(MetricDefinition::counter("jobs_total"))
+            .is_err());
+
+        assert!(registry
+            .declare(MetricDefinition::counter("bad name"))
+            .is_err());
+        assert!(registry
+            .declare(MetricDefinition::gauge("queue_depth").with_buckets(vec![1.0]))
+            .is_err());
+        assert!(registry
+            .declare(MetricDefinition::new("db_query", MetricType::Timer))
+            .is_err());
+
+        assert!(registry.gauge("jobs_total").is_err());
+        assert!(registry.counter("undeclared_total").is_err());
+        assert_eq!(registry.definitions().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_registry_handles_record() {
+        let mock = Arc::new(MockMetricsAdapter::default());
+        let registry = MetricRegistry::new(mock.clone());
+        registry
+            .declare(MetricDefinition::counter("requests_total").with_help("Requests served"))
+            .unwrap();
+        registry
+            .declare(MetricDefinition::gauge("queue_depth").with_unit("jobs"))
+            .unwrap();
+        registry
+            .declare(
+                MetricDefinition::histogram("payload_bytes")
+                    .with_unit("bytes")
+                    .with_buckets(vec![1024.0, 256.0]),
+            )
+            .unwrap();
+        assert_eq!(
+            registry.definition("payload_bytes").unwrap().buckets,
+            Some(vec![256.0, 1024.0])
+        );
+
+        let requests = registry
+            .counter("requests_total")
+            .unwrap()
+            .with_label("method", "GET")
+            .unwrap();
+        requests.incr().await.unwrap();
+        requests.incr_by(2.0).await.unwrap();
+        assert!(requests.incr_by(-1.0).await.is_err());
+        registry
+            .gauge("queue_depth")
+            .unwrap()
+            .set(7.0)
+            .await
+            .unwrap();
+        registry
+            .histogram("payload_bytes")
+            .unwrap()
+            .observe(512.0)
+            .await
+            .unwrap();
+
+        let counters = mock.find_metrics_by_name("requests_total").await;
+        assert_eq!(counters.len(), 1);
+        assert_eq!(counters[0].metric_type, MetricType::Counter);
+        assert_eq!(counters[0].labels["method"], "GET");
+        assert_eq!(counters[0].help.as_deref(), Some("Requests served"));
+        assert_eq!(counters[0].value, MetricValue::Single(3.0));
+
+        let gauge = mock.find_metrics_by_name("queue_depth").await.remove(0);
+        assert_eq!(gauge.metric_type, MetricType::Gauge);
+        assert_eq!(gauge.value, MetricValue::Single(7.0));
+
+        let histogram = mock.find_metrics_by_name("payload_bytes").await.remove(0);
+        assert_eq!(histogram.metric_type, MetricType::Histogram);
+
+        assert!(registry
+            .counter("requests_total")
+            .unwrap()
+            .with_label("bad key", "x")
+            .is_err());
+    }
+}
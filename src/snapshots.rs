@@ -0,0 +1,602 @@
+//! Snapshot comparison, aggregation, and deduplication helpers
+//!
+//! Regression tests often capture a baseline with `get_snapshot` (or the
+//! mock's `current_state`), exercise some code, and capture again. This
+//! module compares the two sets series by series, fingerprints a whole set
+//! for golden tests, and folds raw records into one snapshot per series.
+
+use super::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// A series present in both snapshot sets with a different value
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotChange {
+    /// The series as it was before
+    pub before: MetricSnapshot,
+
+    /// The series as it is after
+    pub after: MetricSnapshot,
+
+    /// How much the value moved
+    ///
+    /// Counters, histograms, and timers report the increase (`after - before`,
+    /// using `sum` for histogram values); gauges report the absolute
+    /// difference.
+    pub delta: f64,
+}
+
+/// Differences between two snapshot sets, keyed by name and labels
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotDiff {
+    /// Series only present after
+    pub added: Vec<MetricSnapshot>,
+
+    /// Series only present before
+    pub removed: Vec<MetricSnapshot>,
+
+    /// Series present in both with different values
+    pub changed: Vec<SnapshotChange>,
+}
+
+impl SnapshotDiff {
+    /// Whether the two sets hold the same series with the same values
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Find the change for the series with this name and labels
+    pub fn change_for(&self, name: &str, labels: &Labels) -> Option<&SnapshotChange> {
+        self.changed
+            .iter()
+            .find(|change| change.after.name == name && &change.after.labels == labels)
+    }
+}
+
+/// Compare two snapshot sets series by series
+///
+/// Series are keyed by name plus labels sorted by key. Each set should hold
+/// one snapshot per series, as `MockMetricsAdapter::current_state` returns;
+/// if a series appears more than once, its last snapshot is used. Timestamps
+/// are ignored, so only value changes count. Results keep the order of the
+/// input sets.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{diff_snapshots, Labels, MetricRequest, MetricSnapshot};
+///
+/// let before = vec![MetricSnapshot::from(&MetricRequest::counter("requests_total", 5.0))];
+/// let after = vec![MetricSnapshot::from(&MetricRequest::counter("requests_total", 8.0))];
+///
+/// let diff = diff_snapshots(&before, &after);
+/// assert_eq!(diff.change_for("requests_total", &Labels::new()).unwrap().delta, 3.0);
+/// ```
+pub fn diff_snapshots(before: &[MetricSnapshot], after: &[MetricSnapshot]) -> SnapshotDiff {
+    let before_index = index_by_series(before);
+    let after_index = index_by_series(after);
+    let mut diff = SnapshotDiff::default();
+
+    for (key, position) in ordered(&after_index) {
+        let current = &after[position];
+        match before_index.get(&key) {
+            None => diff.added.push(current.clone()),
+            Some(&previous) => {
+                let previous = &before[previous];
+                if previous.value != current.value || previous.metric_type != current.metric_type {
+                    diff.changed.push(SnapshotChange {
+                        before: previous.clone(),
+                        after: current.clone(),
+                        delta: value_delta(previous, current),
+                    });
+                }
+            }
+        }
+    }
+
+    for (key, position) in ordered(&before_index) {
+        if !after_index.contains_key(&key) {
+            diff.removed.push(before[position].clone());
+        }
+    }
+
+    diff
+}
+
+/// Keep only the latest snapshot of each series
+///
+/// Series are keyed by name plus labels sorted by key, and the snapshot with
+/// the highest timestamp survives; on equal timestamps the later one in the
+/// input wins. Series keep the order they first appear in.
+///
+/// This is meant for gauge-like "current state" views, e.g. replaying a
+/// recorded stream. Counter increments and histogram observations are
+/// dropped rather than combined, so for those use
+/// `MockMetricsAdapter::get_aggregated_snapshot`, which sums them.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{dedup_latest, MetricRequest, MetricSnapshot, MetricValue};
+///
+/// let snapshots = vec![
+///     MetricSnapshot::from(&MetricRequest::gauge("queue_depth", 4.0)).with_timestamp(1),
+///     MetricSnapshot::from(&MetricRequest::gauge("queue_depth", 9.0)).with_timestamp(2),
+/// ];
+///
+/// let latest = dedup_latest(snapshots);
+/// assert_eq!(latest.len(), 1);
+/// assert_eq!(latest[0].value, MetricValue::Single(9.0));
+/// ```
+pub fn dedup_latest(snapshots: Vec<MetricSnapshot>) -> Vec<MetricSnapshot> {
+    let mut order = Vec::new();
+    let mut latest: HashMap<SeriesKey, MetricSnapshot> = HashMap::new();
+    for snapshot in snapshots {
+        let key = series_key(&snapshot);
+        match latest.get_mut(&key) {
+            Some(current) if current.timestamp > snapshot.timestamp => {}
+            Some(current) => *current = snapshot,
+            None => {
+                order.push(key.clone());
+                latest.insert(key, snapshot);
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| latest.remove(&key))
+        .collect()
+}
+
+/// Compare two snapshot sets pairwise, ignoring timestamps
+///
+/// True when both sets have the same length and each pair is equal under
+/// `MetricSnapshot::eq_ignoring_timestamp`. Order matters; use
+/// `diff_snapshots` to compare sets series by series regardless of order.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{snapshots_eq_ignoring_timestamps, MetricRequest, MetricSnapshot};
+///
+/// let request = MetricRequest::gauge("queue_depth", 4.0);
+/// let expected = vec![MetricSnapshot::from(&request).with_timestamp(0)];
+/// let actual = vec![MetricSnapshot::from(&request)];
+///
+/// assert!(snapshots_eq_ignoring_timestamps(&expected, &actual));
+/// ```
+pub fn snapshots_eq_ignoring_timestamps(a: &[MetricSnapshot], b: &[MetricSnapshot]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(left, right)| left.eq_ignoring_timestamp(right))
+}
+
+/// Stable SHA-256 fingerprint of a snapshot set, as a lowercase hex digest
+///
+/// Each snapshot is encoded from the fields
+/// `MetricSnapshot::eq_ignoring_timestamp` compares, in a fixed order: name,
+/// type, value, labels sorted by key, help, and temporality. Strings are
+/// length-prefixed, floats are hashed by their bits (`f64::to_bits`), and
+/// enums by fixed tags, so the digest doesn't depend on `Debug` output. The
+/// encodings are sorted before hashing, so the fingerprint ignores
+/// timestamps and order, but not duplicates: recording a series twice
+/// changes it. Useful as a one-line golden-test assertion.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{fingerprint, MetricRequest, MetricSnapshot};
+///
+/// let counter = MetricSnapshot::from(&MetricRequest::counter("jobs_total", 2.0));
+/// let gauge = MetricSnapshot::from(&MetricRequest::gauge("queue_depth", 7.0));
+///
+/// let digest = fingerprint(&[counter.clone(), gauge.clone()]);
+/// assert_eq!(digest.len(), 64);
+/// assert_eq!(digest, fingerprint(&[gauge, counter.with_timestamp(0)]));
+/// ```
+pub fn fingerprint(snapshots: &[MetricSnapshot]) -> String {
+    let mut canonical: Vec<Vec<u8>> = snapshots.iter().map(canonical_encoding).collect();
+    canonical.sort();
+
+    let mut hasher = Sha256::new();
+    for encoding in &canonical {
+        hasher.update((encoding.len() as u64).to_le_bytes());
+        hasher.update(encoding);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Canonical byte encoding of the fields `fingerprint` covers
+fn canonical_encoding(snapshot: &MetricSnapshot) -> Vec<u8> {
+    fn put_str(out: &mut Vec<u8>, value: &str) {
+        out.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        out.extend_from_slice(value.as_bytes());
+    }
+
+    let mut out = Vec::new();
+    let (name, labels) = series_key(snapshot);
+    put_str(&mut out, &name);
+
+    out.push(match snapshot.metric_type {
+        MetricType::Counter => 0,
+        MetricType::Gauge => 1,
+        MetricType::Histogram => 2,
+        MetricType::Timer => 3,
+        MetricType::Info => 4,
+    });
+
+    match &snapshot.value {
+        MetricValue::Single(value) => {
+            out.push(0);
+            out.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+        MetricValue::Histogram {
+            sum,
+            count,
+            buckets,
+        } => {
+            out.push(1);
+            out.extend_from_slice(&sum.to_bits().to_le_bytes());
+            out.extend_from_slice(&count.to_le_bytes());
+            out.extend_from_slice(&(buckets.len() as u64).to_le_bytes());
+            for bucket in buckets {
+                out.extend_from_slice(&bucket.upper_bound.to_bits().to_le_bytes());
+                out.extend_from_slice(&bucket.count.to_le_bytes());
+            }
+        }
+    }
+
+    out.extend_from_slice(&(labels.len() as u64).to_le_bytes());
+    for (key, value) in &labels {
+        put_str(&mut out, key);
+        put_str(&mut out, value);
+    }
+
+    match &snapshot.help {
+        Some(help) => {
+            out.push(1);
+            put_str(&mut out, help);
+        }
+        None => out.push(0),
+    }
+
+    out.push(match snapshot.temporality {
+        None => 0,
+        Some(AggregationTemporality::Cumulative) => 1,
+        Some(AggregationTemporality::Delta) => 2,
+    });
+    out
+}
+
+/// Fold raw records into one snapshot per series, in first-seen order
+///
+/// Records are merged with `MetricSnapshot::merge`; histogram and timer
+/// observations (`Single` values) are first turned into one-observation
+/// histograms over `HistogramBuckets::latency()` plus `+Inf`. Snapshots that
+/// are already one per series with histogram values pass through unchanged.
+pub(crate) fn aggregate_snapshots(snapshots: Vec<MetricSnapshot>) -> Result<Vec<MetricSnapshot>> {
+    let bounds = HistogramBuckets::custom(&HistogramBuckets::latency())?;
+
+    let mut order = Vec::new();
+    let mut series: HashMap<SeriesKey, MetricSnapshot> = HashMap::new();
+    for mut snapshot in snapshots {
+        if let (MetricType::Histogram | MetricType::Timer, MetricValue::Single(value)) =
+            (snapshot.metric_type, &snapshot.value)
+        {
+            snapshot.value = MetricValue::from_observations(&[*value], &bounds);
+        }
+
+        let key = series_key(&snapshot);
+        match series.get_mut(&key) {
+            Some(current) => *current = current.merge(&snapshot)?,
+            None => {
+                order.push(key.clone());
+                series.insert(key, snapshot);
+            }
+        }
+    }
+
+    Ok(order
+        .into_iter()
+        .filter_map(|key| series.remove(&key))
+        .collect())
+}
+
+/// Outcome of `replay_snapshots`
+#[derive(Debug, Default)]
+pub struct ReplayReport {
+    /// Number of snapshots the target accepted
+    pub recorded: usize,
+
+    /// Snapshots the target rejected, by position in the input
+    pub failures: Vec<(usize, TylError)>,
+}
+
+impl ReplayReport {
+    /// Whether every snapshot was recorded
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Feed recorded snapshots back into another adapter
+///
+/// Each snapshot is turned back into a `MetricRequest` with the same name,
+/// type, value, labels, and help, and recorded with `record_at` at the
+/// snapshot's timestamp. A rejected snapshot does not stop the replay; its
+/// error is collected in the report instead. Useful for migrating captured
+/// data between backends or reproducing a production snapshot in a test.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{replay_snapshots, MetricRequest, MetricSnapshot, MockMetricsAdapter};
+///
+/// # tokio_test::block_on(async {
+/// let captured = vec![
+///     MetricSnapshot::from(&MetricRequest::counter("jobs_total", 3.0)),
+///     MetricSnapshot::from(&MetricRequest::gauge("queue_depth", 7.0)),
+/// ];
+///
+/// let target = MockMetricsAdapter::default();
+/// let report = replay_snapshots(&target, &captured).await.unwrap();
+/// assert!(report.is_complete());
+/// assert_eq!(report.recorded, 2);
+/// # });
+/// ```
+pub async fn replay_snapshots<M: MetricsManager>(
+    target: &M,
+    snapshots: &[MetricSnapshot],
+) -> Result<ReplayReport> {
+    let mut report = ReplayReport::default();
+    for (position, snapshot) in snapshots.iter().enumerate() {
+        let request = MetricRequest::from(snapshot);
+        match target.record_at(&request, snapshot.timestamp).await {
+            Ok(()) => report.recorded += 1,
+            Err(error) => report.failures.push((position, error)),
+        }
+    }
+    Ok(report)
+}
+
+/// Series key: name plus labels sorted by key
+type SeriesKey = (String, Vec<(String, String)>);
+
+/// Map each series to the position of its last snapshot
+fn index_by_series(snapshots: &[MetricSnapshot]) -> HashMap<SeriesKey, usize> {
+    snapshots
+        .iter()
+        .enumerate()
+        .map(|(position, snapshot)| (series_key(snapshot), position))
+        .collect()
+}
+
+/// Key a snapshot by its series
+fn series_key(snapshot: &MetricSnapshot) -> SeriesKey {
+    let mut labels: Vec<(String, String)> = snapshot
+        .labels
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    labels.sort();
+    (snapshot.name.clone(), labels)
+}
+
+/// Index entries in input order
+fn ordered(index: &HashMap<SeriesKey, usize>) -> Vec<(SeriesKey, usize)> {
+    let mut entries: Vec<(SeriesKey, usize)> = index.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    entries.sort_by_key(|(_, position)| *position);
+    entries
+}
+
+/// Scalar movement between two snapshots of a series
+fn value_delta(before: &MetricSnapshot, after: &MetricSnapshot) -> f64 {
+    let scalar = |value: &MetricValue| match value {
+        MetricValue::Single(value) => *value,
+        MetricValue::Histogram { sum, .. } => *sum,
+    };
+    let delta = scalar(&after.value) - scalar(&before.value);
+
+    match after.metric_type {
+        MetricType::Gauge => delta.abs(),
+        _ => delta,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(request: MetricRequest) -> MetricSnapshot {
+        MetricSnapshot::from(&request)
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let before = vec![
+            snapshot(MetricRequest::counter("requests_total", 10.0).with_label("method", "GET")),
+            snapshot(MetricRequest::gauge("queue_depth", 7.0)),
+            snapshot(MetricRequest::gauge("legacy_gauge", 1.0)),
+            snapshot(MetricRequest::counter("errors_total", 2.0)),
+        ];
+        let after = vec![
+            snapshot(MetricRequest::counter("requests_total", 13.0).with_label("method", "GET")),
+            snapshot(MetricRequest::gauge("queue_depth", 4.0)),
+            snapshot(MetricRequest::counter("errors_total", 2.0)),
+            snapshot(MetricRequest::counter("retries_total", 1.0)),
+        ];
+
+        let diff = diff_snapshots(&before, &after);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "retries_total");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "legacy_gauge");
+
+        assert_eq!(diff.changed.len(), 2);
+        let labels: Labels = [("method".to_string(), "GET".to_string())].into();
+        assert_eq!(
+            diff.change_for("requests_total", &labels).unwrap().delta,
+            3.0
+        );
+        assert_eq!(
+            diff.change_for("queue_depth", &Labels::new())
+                .unwrap()
+                .delta,
+            3.0
+        );
+        assert!(diff.change_for("errors_total", &Labels::new()).is_none());
+        assert!(diff.change_for("requests_total", &Labels::new()).is_none());
+    }
+
+    #[test]
+    fn test_dedup_latest_keeps_highest_timestamp() {
+        let gauge = |value: f64, timestamp: u64, region: &str| {
+            MetricSnapshot::from(
+                &MetricRequest::gauge("queue_depth", value).with_label("region", region),
+            )
+            .with_timestamp(timestamp)
+        };
+
+        let latest = dedup_latest(vec![
+            gauge(1.0, 10, "eu"),
+            gauge(3.0, 30, "eu"),
+            gauge(7.0, 5, "us"),
+            gauge(2.0, 20, "eu"),
+        ]);
+
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].labels["region"], "eu");
+        assert_eq!(latest[0].value, MetricValue::Single(3.0));
+        assert_eq!(latest[0].timestamp, 30);
+        assert_eq!(latest[1].value, MetricValue::Single(7.0));
+
+        // Equal timestamps: the later input wins
+        let tied = dedup_latest(vec![gauge(1.0, 10, "eu"), gauge(2.0, 10, "eu")]);
+        assert_eq!(tied[0].value, MetricValue::Single(2.0));
+    }
+
+    #[test]
+    fn test_snapshots_eq_ignoring_timestamps() {
+        let requests = [
+            MetricRequest::counter("jobs_total", 2.0),
+            MetricRequest::gauge("queue_depth", 7.0).with_label("queue", "emails"),
+        ];
+        let expected: Vec<_> = requests
+            .iter()
+            .map(|r| MetricSnapshot::from(r).with_timestamp(0))
+            .collect();
+        let actual: Vec<_> = requests.iter().map(MetricSnapshot::from).collect();
+
+        assert_ne!(expected, actual);
+        assert!(snapshots_eq_ignoring_timestamps(&expected, &actual));
+        assert!(!snapshots_eq_ignoring_timestamps(&expected, &actual[..1]));
+
+        let reversed: Vec<_> = actual.iter().rev().cloned().collect();
+        assert!(!snapshots_eq_ignoring_timestamps(&expected, &reversed));
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_order_and_timestamps() {
+        let a = vec![
+            snapshot(MetricRequest::counter("jobs_total", 2.0).with_label("queue", "emails")),
+            snapshot(
+                MetricRequest::gauge("queue_depth", 7.0)
+                    .with_label("queue", "emails")
+                    .with_label("region", "eu"),
+            ),
+        ];
+        let b = vec![
+            snapshot(
+                MetricRequest::gauge("queue_depth", 7.0)
+                    .with_label("region", "eu")
+                    .with_label("queue", "emails"),
+            )
+            .with_timestamp(0),
+            a[0].clone().with_timestamp(42),
+        ];
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+
+        let mut changed = a.clone();
+        changed[1].value = MetricValue::Single(8.0);
+        assert_ne!(fingerprint(&a), fingerprint(&changed));
+        assert_ne!(fingerprint(&a), fingerprint(&a[..1]));
+        assert_ne!(fingerprint(&[]), fingerprint(&a));
+    }
+
+    #[test]
+    fn test_fingerprint_is_pinned() {
+        let snapshots = vec![
+            snapshot(MetricRequest::counter("jobs_total", 2.0).with_label("queue", "emails")),
+            snapshot(MetricRequest::gauge("queue_depth", 7.0).with_help("Jobs waiting")),
+        ];
+
+        // Changing this digest breaks every stored golden fingerprint
+        assert_eq!(
+            fingerprint(&snapshots),
+            "742bfa768fccc0d2ba7b6638b9e3ae27a2400264fbba12b823b000dd91b4ca41"
+        );
+        assert_eq!(
+            fingerprint(&[]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_separates_fields() {
+        // Adjacent strings can't run together
+        let a = snapshot(MetricRequest::gauge("temperature", 1.0).with_label("ab", "c"));
+        let b = snapshot(MetricRequest::gauge("temperature", 1.0).with_label("a", "bc"));
+        assert_ne!(fingerprint(&[a]), fingerprint(&[b]));
+
+        // Float bits are hashed, so -0.0 and 0.0 differ
+        let zero = snapshot(MetricRequest::gauge("temperature", 0.0));
+        let negative_zero = snapshot(MetricRequest::gauge("temperature", -0.0));
+        assert_ne!(fingerprint(&[zero]), fingerprint(&[negative_zero]));
+    }
+
+    #[test]
+    fn test_diff_snapshots_identical_sets() {
+        let snapshots = vec![snapshot(MetricRequest::counter("requests_total", 1.0))];
+        let later: Vec<MetricSnapshot> = snapshots
+            .iter()
+            .map(|s| s.clone().with_timestamp(s.timestamp + 1))
+            .collect();
+
+        assert!(diff_snapshots(&snapshots, &later).is_empty());
+        assert!(diff_snapshots(&[], &[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replay_snapshots_into_mock() {
+        let captured = vec![
+            snapshot(
+                MetricRequest::counter("requests_total", 3.0)
+                    .with_label("method", "GET")
+                    .with_help("Total requests"),
+            )
+            .with_timestamp(1_000),
+            snapshot(MetricRequest::gauge("queue_depth", 7.0)).with_timestamp(2_000),
+            snapshot(MetricRequest::counter("invalid name", 1.0)),
+            snapshot(MetricRequest::histogram("latency_seconds", 0.25)).with_timestamp(3_000),
+        ];
+
+        let target = MockMetricsAdapter::default();
+        let report = replay_snapshots(&target, &captured).await.unwrap();
+        assert_eq!(report.recorded, 3);
+        assert!(!report.is_complete());
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, 2);
+
+        let replayed = target.get_snapshot().await.unwrap();
+        assert_eq!(replayed.len(), 3);
+        let expected: Vec<MetricSnapshot> = captured
+            .into_iter()
+            .enumerate()
+            .filter(|(position, _)| *position != 2)
+            .map(|(_, snapshot)| snapshot)
+            .collect();
+        assert_eq!(replayed, expected);
+    }
+}
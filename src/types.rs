@@ -4,13 +4,147 @@
 //! the metrics system. Following domain-driven design principles, these
 //! types represent the core concepts of the metrics domain.
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::time::{Duration, Instant};
+use crate::clock::{unix_nanos, Instant};
+use crate::errors::{from_serde_json_error, metrics_error, metrics_recording_error};
+use crate::utils::bucket_index;
+use serde::{Deserialize, Serialize, Serializer};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
 
 /// Type alias for metric labels - a map of string key-value pairs
 pub type Labels = HashMap<String, String>;
 
+/// Serialize labels as an object sorted by key
+///
+/// `Labels` is a `HashMap`, so its iteration order is random. Sorting keeps
+/// serialized output deterministic for exporters and golden tests.
+pub(crate) fn serialize_sorted_labels<S: Serializer>(
+    labels: &Labels,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    labels
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
+/// A metric name that has passed `validate_metric_name`
+///
+/// Validating when the name is defined surfaces naming mistakes at the
+/// definition site instead of deep inside `record`. `MetricName` derefs to
+/// `str` and converts into `String`, so it can be passed anywhere the request
+/// constructors take a name.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{MetricName, MetricRequest};
+///
+/// // Checked at compile time; "bad name" would fail the build
+/// const REQUESTS: MetricName = MetricName::from_static("http_requests_total");
+///
+/// assert!(MetricName::new("bad name").is_err());
+///
+/// let request = MetricRequest::counter(REQUESTS, 1.0);
+/// assert_eq!(request.name(), "http_requests_total");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MetricName(Cow<'static, str>);
+
+impl MetricName {
+    /// Validate `name` and wrap it
+    pub fn new(name: impl Into<String>) -> crate::Result<Self> {
+        let name = name.into();
+        crate::utils::validate_metric_name(&name)?;
+        Ok(Self(Cow::Owned(name)))
+    }
+
+    /// Validate a static name and wrap it
+    ///
+    /// This is `const` so names can be declared as constants or statics,
+    /// where an invalid name fails the build. Applies the same rules as
+    /// `validate_metric_name` (Prometheus convention, default length limit).
+    ///
+    /// # Panics
+    /// Panics if `name` is invalid; in a `const` this is a compile error.
+    ///
+    /// ```compile_fail
+    /// use tyl_metrics_port::MetricName;
+    ///
+    /// const BAD: MetricName = MetricName::from_static("bad name");
+    /// ```
+    pub const fn from_static(name: &'static str) -> Self {
+        if !is_valid_static_name(name) {
+            panic!("invalid metric name in MetricName::from_static");
+        }
+        Self(Cow::Borrowed(name))
+    }
+
+    /// Get the name as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// `validate_metric_name` in a form usable in `const` contexts
+const fn is_valid_static_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    if bytes.is_empty() || bytes.len() > crate::utils::MAX_METRIC_NAME_LENGTH {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let valid = byte.is_ascii_alphabetic()
+            || byte == b'_'
+            || byte == b':'
+            || (i > 0 && byte.is_ascii_digit());
+        if !valid {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+impl std::ops::Deref for MetricName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for MetricName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for MetricName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<MetricName> for String {
+    fn from(name: MetricName) -> Self {
+        name.0.into_owned()
+    }
+}
+
+impl From<&MetricName> for String {
+    fn from(name: &MetricName) -> Self {
+        name.0.to_string()
+    }
+}
+
 /// Core metric request that encapsulates all information needed to record a metric
 ///
 /// This is the primary value object that flows through the metrics system.
@@ -44,6 +178,7 @@ pub struct MetricRequest {
     value: MetricValue,
 
     /// Labels attached to this metric
+    #[serde(serialize_with = "serialize_sorted_labels")]
     labels: Labels,
 
     /// Optional help text describing what this metric measures
@@ -51,6 +186,22 @@ pub struct MetricRequest {
 
     /// Timestamp when the metric was created (Unix epoch nanoseconds)
     timestamp: u64,
+
+    /// Start of the interval the value covers (Unix epoch nanoseconds), if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_timestamp: Option<u64>,
+
+    /// Aggregation temporality of the value, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    temporality: Option<AggregationTemporality>,
+
+    /// Raw observations behind a `histogram_many` value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    observations: Option<Vec<f64>>,
+
+    /// Whether the value is an increment to add to the series (`counter_inc`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    increment: bool,
 }
 
 impl MetricRequest {
@@ -58,12 +209,70 @@ impl MetricRequest {
     ///
     /// # Arguments
     /// * `name` - The metric name (will be validated)
-    /// * `value` - The counter increment value (must be >= 0)
+    /// * `value` - The counter value (must be >= 0)
+    ///
+    /// The value is recorded as given, one record per call, with cumulative
+    /// temporality; use `counter_inc` to state that the value is an
+    /// increment to add to the series.
     ///
     /// # Returns
     /// * `MetricRequest` - A new metric request builder
     pub fn counter(name: impl Into<String>, value: f64) -> Self {
         Self::new(name.into(), MetricType::Counter, MetricValue::Single(value))
+            .with_temporality(AggregationTemporality::Cumulative)
+    }
+
+    /// Create a counter increment
+    ///
+    /// `delta` (must be >= 0) is added to the series rather than recorded
+    /// as its value, as Prometheus counters increment. The request carries
+    /// delta temporality and is marked as an increment; only marked requests
+    /// are summed, so a counter that merely has delta temporality (e.g. from
+    /// `data_point` or a replayed snapshot) is still recorded as given. The
+    /// mock keeps one running cumulative total per series for increments.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{AggregationTemporality, MetricRequest};
+    ///
+    /// let request = MetricRequest::counter_inc("jobs_total", 1.0);
+    /// assert_eq!(request.temporality(), Some(AggregationTemporality::Delta));
+    /// assert!(request.is_increment());
+    /// assert!(!MetricRequest::counter("jobs_total", 1.0).is_increment());
+    /// assert!(!MetricRequest::counter("jobs_total", 1.0)
+    ///     .with_temporality(AggregationTemporality::Delta)
+    ///     .is_increment());
+    /// ```
+    pub fn counter_inc(name: impl Into<String>, delta: f64) -> Self {
+        let mut request = Self::new(name.into(), MetricType::Counter, MetricValue::Single(delta))
+            .with_temporality(AggregationTemporality::Delta);
+        request.increment = true;
+        request
+    }
+
+    /// Create an info metric request carrying metadata in its labels
+    ///
+    /// Info metrics (OpenMetrics `info`) are constant series whose value is
+    /// always `1.0`; the interesting part is the label set, e.g. the version
+    /// and commit of a build. Validation rejects an info metric without
+    /// labels. By convention the name ends in `_info`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{MetricRequest, MetricType};
+    ///
+    /// let request = MetricRequest::info("build_info", [("version", "1.4.2"), ("commit", "9f3c2a1")]);
+    /// assert_eq!(request.metric_type(), &MetricType::Info);
+    /// assert_eq!(request.value(), 1.0);
+    /// assert!(request.validate().is_ok());
+    /// ```
+    pub fn info<I, K, V>(name: impl Into<String>, labels: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self::new(name.into(), MetricType::Info, MetricValue::Single(1.0)).with_labels(labels)
     }
 
     /// Create a new gauge metric request
@@ -94,11 +303,47 @@ impl MetricRequest {
         )
     }
 
+    /// Create a histogram metric request carrying many observations
+    ///
+    /// For bulk imports, e.g. of latency samples: one request, recorded with
+    /// one `record` call, holds every observation. Its value is the
+    /// observations folded into a histogram over `HistogramBuckets::latency()`
+    /// plus `+Inf`, so adapters that record values see the whole batch; the
+    /// raw observations stay available through `observations`, and the mock
+    /// stores each one as if recorded separately. Validation rejects NaN and
+    /// infinite observations, reporting the index of the first, and an empty
+    /// batch.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::MetricRequest;
+    ///
+    /// let request = MetricRequest::histogram_many("request_duration_seconds", vec![0.1, 0.2, 0.4]);
+    /// assert_eq!(request.observations(), &[0.1, 0.2, 0.4]);
+    /// assert_eq!(request.value(), (0.1 + 0.2 + 0.4) / 3.0);
+    /// ```
+    pub fn histogram_many(name: impl Into<String>, values: Vec<f64>) -> Self {
+        let mut bounds = HistogramBuckets::latency();
+        bounds.push(f64::INFINITY);
+
+        let mut request = Self::new(
+            name.into(),
+            MetricType::Histogram,
+            MetricValue::from_observations(&values, &bounds),
+        );
+        request.observations = Some(values);
+        request
+    }
+
     /// Create a new timer metric request
     ///
+    /// Durations are recorded in seconds, like every time-valued metric
+    /// (see `gauge_duration` and `counter_duration`), following the
+    /// Prometheus base-unit convention; name such metrics `..._seconds`.
+    ///
     /// # Arguments
     /// * `name` - The metric name (will be validated)
-    /// * `duration` - The duration to record
+    /// * `duration` - The duration to record, converted to seconds
     ///
     /// # Returns
     /// * `MetricRequest` - A new metric request builder
@@ -110,6 +355,139 @@ impl MetricRequest {
         )
     }
 
+    /// Create a timer metric request from milliseconds
+    ///
+    /// Timers are stored in seconds, so `timer_millis(name, 50.0)` records the
+    /// same `0.05` as `timer(name, Duration::from_millis(50))`. Prefer
+    /// `timer` when a `Duration` is at hand.
+    ///
+    /// # Arguments
+    /// * `name` - The metric name (will be validated)
+    /// * `millis` - The duration in milliseconds
+    ///
+    /// # Returns
+    /// * `MetricRequest` - A new metric request builder
+    pub fn timer_millis(name: impl Into<String>, millis: f64) -> Self {
+        Self::timer_in(name, millis, TimeUnit::Milliseconds)
+    }
+
+    /// Create a timer metric request from microseconds
+    ///
+    /// Like `timer_millis`, the value is converted to seconds.
+    ///
+    /// # Arguments
+    /// * `name` - The metric name (will be validated)
+    /// * `micros` - The duration in microseconds
+    ///
+    /// # Returns
+    /// * `MetricRequest` - A new metric request builder
+    pub fn timer_micros(name: impl Into<String>, micros: f64) -> Self {
+        Self::timer_in(name, micros, TimeUnit::Microseconds)
+    }
+
+    /// Create a timer metric request from a duration in any `TimeUnit`
+    fn timer_in(name: impl Into<String>, value: f64, unit: TimeUnit) -> Self {
+        Self::new(
+            name.into(),
+            MetricType::Timer,
+            MetricValue::Single(value).convert(unit, TimeUnit::Seconds),
+        )
+    }
+
+    /// Create a gauge metric request from a duration, in seconds
+    ///
+    /// For time-valued levels such as uptime or the age of the oldest queued
+    /// job; `gauge_duration(name, Duration::from_secs(90))` records `90.0`.
+    ///
+    /// # Arguments
+    /// * `name` - The metric name (will be validated)
+    /// * `duration` - The gauge value, converted to seconds
+    ///
+    /// # Returns
+    /// * `MetricRequest` - A new metric request builder
+    pub fn gauge_duration(name: impl Into<String>, duration: Duration) -> Self {
+        Self::gauge(name, duration.as_secs_f64())
+    }
+
+    /// Create a counter metric request from a duration, in seconds
+    ///
+    /// For accumulated time such as CPU seconds or total time spent waiting
+    /// on a lock. Like `counter`, the value is recorded as given; to add time
+    /// to the series, use `counter_inc` with `Duration::as_secs_f64`.
+    ///
+    /// # Arguments
+    /// * `name` - The metric name (will be validated)
+    /// * `duration` - The counter value, converted to seconds
+    ///
+    /// # Returns
+    /// * `MetricRequest` - A new metric request builder
+    pub fn counter_duration(name: impl Into<String>, duration: Duration) -> Self {
+        Self::counter(name, duration.as_secs_f64())
+    }
+
+    /// Start a reusable template for repeated recordings of one series
+    ///
+    /// The value is a zero placeholder. Add the constant labels, then call
+    /// `validate` once; record the result with
+    /// `MetricsManagerExt::record_template`, which fills in the value per
+    /// call and only checks that value.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{MetricRequest, MetricType};
+    ///
+    /// let template = MetricRequest::template("http_requests_total", MetricType::Counter)
+    ///     .with_label("endpoint", "/users")
+    ///     .validate()
+    ///     .unwrap();
+    /// let request = template.with_value(3.0).unwrap();
+    /// assert_eq!(request.request().value(), 3.0);
+    /// ```
+    pub fn template(name: impl Into<String>, metric_type: MetricType) -> Self {
+        match metric_type {
+            MetricType::Counter => Self::counter(name, 0.0),
+            _ => Self::new(name.into(), metric_type, MetricValue::Single(0.0)),
+        }
+    }
+
+    /// Create a raw data point with explicit timing, as received from OTLP
+    ///
+    /// This is a low-level constructor for ingestion adapters: unlike the other
+    /// constructors it does not stamp the current time, so ingested data keeps
+    /// its exact start and observation timestamps.
+    ///
+    /// # Arguments
+    /// * `name` - The metric name (will be validated)
+    /// * `metric_type` - The type of metric the data point belongs to
+    /// * `value` - The data point value
+    /// * `start_unix_nanos` - Start of the interval the value covers
+    /// * `time_unix_nanos` - Time the value was observed
+    /// * `temporality` - Aggregation temporality of the value
+    ///
+    /// # Returns
+    /// * `Result<MetricRequest>` - The data point, or an error if `time < start`
+    pub fn data_point(
+        name: impl Into<String>,
+        metric_type: MetricType,
+        value: MetricValue,
+        start_unix_nanos: u64,
+        time_unix_nanos: u64,
+        temporality: AggregationTemporality,
+    ) -> crate::Result<Self> {
+        if time_unix_nanos < start_unix_nanos {
+            return Err(metrics_error(
+                "time_unix_nanos",
+                "Data point time must not be earlier than its start time",
+            ));
+        }
+
+        let mut request = Self::new(name.into(), metric_type, value);
+        request.timestamp = time_unix_nanos;
+        request.start_timestamp = Some(start_unix_nanos);
+        request.temporality = Some(temporality);
+        Ok(request)
+    }
+
     /// Internal constructor for creating metric requests
     fn new(name: String, metric_type: MetricType, value: MetricValue) -> Self {
         Self {
@@ -118,10 +496,11 @@ impl MetricRequest {
             value,
             labels: Labels::new(),
             help: None,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_nanos() as u64,
+            timestamp: unix_nanos(),
+            start_timestamp: None,
+            temporality: None,
+            observations: None,
+            increment: false,
         }
     }
 
@@ -157,6 +536,73 @@ impl MetricRequest {
         self
     }
 
+    /// Add labels from the fields of a serializable struct or map
+    ///
+    /// `labels` must serialize to a flat map: string values are used as-is,
+    /// numbers and booleans are stringified, and `None` fields are skipped.
+    /// Nested structs, maps, and sequences fail with a validation error, as
+    /// does anything that doesn't serialize to a map.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    /// use tyl_metrics_port::MetricRequest;
+    ///
+    /// #[derive(Serialize)]
+    /// struct RequestLabels<'a> {
+    ///     method: &'a str,
+    ///     status: u16,
+    /// }
+    ///
+    /// let request = MetricRequest::counter("http_requests_total", 1.0)
+    ///     .with_labels_from(&RequestLabels { method: "GET", status: 200 })
+    ///     .unwrap();
+    /// assert_eq!(request.labels()["status"], "200");
+    /// ```
+    pub fn with_labels_from<T: Serialize>(mut self, labels: &T) -> crate::Result<Self> {
+        let fields = match serde_json::to_value(labels).map_err(from_serde_json_error)? {
+            serde_json::Value::Object(fields) => fields,
+            other => {
+                return Err(metrics_error(
+                    "labels",
+                    format!("Labels must serialize to a map, not {other}"),
+                ))
+            }
+        };
+
+        for (key, value) in fields {
+            let value = match value {
+                serde_json::Value::String(value) => value,
+                serde_json::Value::Number(number) => number.to_string(),
+                serde_json::Value::Bool(flag) => flag.to_string(),
+                serde_json::Value::Null => continue,
+                serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+                    return Err(metrics_error(
+                        "labels",
+                        format!("Label {key} is nested; label values must be strings, numbers, or booleans"),
+                    ))
+                }
+            };
+            self.labels.insert(key, value);
+        }
+        Ok(self)
+    }
+
+    /// Set the aggregation temporality of the value
+    ///
+    /// Counters default to `Cumulative`; other types have none unless set.
+    /// Delta temporality is meaningless for gauges and fails validation.
+    ///
+    /// # Arguments
+    /// * `temporality` - Whether the value is cumulative or a delta
+    ///
+    /// # Returns
+    /// * `Self` - The metric request for chaining
+    pub fn with_temporality(mut self, temporality: AggregationTemporality) -> Self {
+        self.temporality = Some(temporality);
+        self
+    }
+
     /// Add help text to the metric request
     ///
     /// # Arguments
@@ -169,6 +615,21 @@ impl MetricRequest {
         self
     }
 
+    /// Override the observation timestamp
+    ///
+    /// Constructors stamp the current time; use this to backfill historical
+    /// values or build deterministic fixtures.
+    ///
+    /// # Arguments
+    /// * `timestamp` - Event time in Unix epoch nanoseconds
+    ///
+    /// # Returns
+    /// * `Self` - The metric request for chaining
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
     /// Get the metric name
     pub fn name(&self) -> &str {
         &self.name
@@ -181,14 +642,8 @@ impl MetricRequest {
 
     /// Get the metric value
     pub fn value(&self) -> f64 {
-        match &self.value {
-            MetricValue::Single(v) => *v,
-            MetricValue::Histogram {
-                sum,
-                count,
-                buckets: _,
-            } => sum / (*count as f64),
-        }
+        // An empty histogram has no mean; keep reporting it as NaN
+        self.value.as_f64().unwrap_or(f64::NAN)
     }
 
     /// Get the metric value as the full value object
@@ -201,6 +656,21 @@ impl MetricRequest {
         &self.labels
     }
 
+    /// Replace the metric name, for decorators that rewrite requests
+    pub(crate) fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// Replace the metric value, for decorators that rewrite requests
+    pub(crate) fn set_value(&mut self, value: MetricValue) {
+        self.value = value;
+    }
+
+    /// Get mutable labels, for decorators that rewrite requests
+    pub(crate) fn labels_mut(&mut self) -> &mut Labels {
+        &mut self.labels
+    }
+
     /// Get the help text if available
     pub fn help(&self) -> Option<&str> {
         self.help.as_deref()
@@ -210,25 +680,217 @@ impl MetricRequest {
     pub fn timestamp(&self) -> u64 {
         self.timestamp
     }
+
+    /// Get the start timestamp if available
+    pub fn start_timestamp(&self) -> Option<u64> {
+        self.start_timestamp
+    }
+
+    /// Get the aggregation temporality if available
+    pub fn temporality(&self) -> Option<AggregationTemporality> {
+        self.temporality
+    }
+
+    /// Whether this is a counter increment created with `counter_inc`
+    pub fn is_increment(&self) -> bool {
+        self.increment && self.metric_type == MetricType::Counter
+    }
+
+    /// Get the raw observations of a `histogram_many` request
+    ///
+    /// Empty for every other request.
+    pub fn observations(&self) -> &[f64] {
+        self.observations.as_deref().unwrap_or_default()
+    }
+
+    /// Get the observations of a `histogram_many` request, even an empty one
+    ///
+    /// `None` for every other request.
+    pub(crate) fn observation_batch(&self) -> Option<&[f64]> {
+        self.observations.as_deref()
+    }
+
+    /// Validate this request once, for reuse on hot paths
+    ///
+    /// Runs the same checks as `validate_metric_request`. The returned
+    /// `ValidatedMetricRequest` can be recorded repeatedly with
+    /// `MetricsManager::record_validated`, letting adapters skip re-validation.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::MetricRequest;
+    ///
+    /// let template = MetricRequest::counter("http_requests_total", 1.0)
+    ///     .with_label("method", "GET")
+    ///     .validate()
+    ///     .unwrap();
+    /// assert_eq!(template.request().name(), "http_requests_total");
+    ///
+    /// assert!(MetricRequest::counter("bad name", 1.0).validate().is_err());
+    /// ```
+    pub fn validate(self) -> crate::Result<ValidatedMetricRequest> {
+        crate::utils::validate_metric_request(&self)?;
+        Ok(ValidatedMetricRequest { request: self })
+    }
+
+    /// Report every validation problem instead of only the first
+    ///
+    /// Runs the same checks as `validate` against the default
+    /// `ValidationLimits` (name, label count, each label key and value in key
+    /// order, type-specific rules, and the value) and returns all failures.
+    /// An empty vector means the request is valid. Meant for tooling such as
+    /// linting declared metrics; `record` still stops at the first error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::MetricRequest;
+    ///
+    /// assert!(MetricRequest::counter("requests_total", 1.0).validate_all().is_empty());
+    ///
+    /// let errors = MetricRequest::counter("bad name", -1.0).validate_all();
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    pub fn validate_all(&self) -> Vec<crate::TylError> {
+        crate::utils::metric_request_violations(self, &crate::ValidationLimits::default())
+    }
+}
+
+/// A metric request that has already passed validation
+///
+/// Only obtainable through `MetricRequest::validate`, so holding one proves
+/// the name, labels, and value are valid. Use `with_value` to reuse it as a
+/// template without re-checking the name and labels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatedMetricRequest {
+    request: MetricRequest,
+}
+
+impl ValidatedMetricRequest {
+    /// Wrap a request derived from a validated one by a validity-preserving
+    /// rewrite (e.g. dropping labels), without checking it again
+    pub(crate) fn assume_valid(request: MetricRequest) -> Self {
+        Self { request }
+    }
+
+    /// Get the validated request
+    pub fn request(&self) -> &MetricRequest {
+        &self.request
+    }
+
+    /// Unwrap into the plain request
+    pub fn into_inner(self) -> MetricRequest {
+        self.request
+    }
+
+    /// Replace the value, validating only the new value
+    ///
+    /// The timestamp is refreshed so the result looks freshly created.
+    pub fn with_value(mut self, value: f64) -> crate::Result<Self> {
+        match self.request.metric_type {
+            MetricType::Counter => crate::utils::validate_counter_value(value)?,
+            _ => crate::utils::validate_metric_value(value)?,
+        }
+
+        self.request.value = MetricValue::Single(value);
+        self.request.timestamp = unix_nanos();
+        Ok(self)
+    }
+}
+
+/// Compact one-line rendering for logs and test failure messages
+///
+/// Renders `<type> <name>{<labels>} = <value> @ <timestamp>`, with labels
+/// sorted by key, single values in `Debug` form (`1.0`), histogram values as
+/// their count and sum, and the timestamp in UTC RFC 3339 with milliseconds.
+/// The braces are omitted when there are no labels.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::MetricRequest;
+///
+/// let request = MetricRequest::gauge("queue_depth", 4.0).with_timestamp(0);
+/// assert_eq!(request.to_string(), "gauge queue_depth = 4.0 @ 1970-01-01T00:00:00.000Z");
+/// ```
+impl std::fmt::Display for MetricRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.metric_type, self.name)?;
+
+        if !self.labels.is_empty() {
+            let mut labels: Vec<(&String, &String)> = self.labels.iter().collect();
+            labels.sort();
+            let labels: Vec<String> = labels
+                .into_iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect();
+            write!(f, "{{{}}}", labels.join(","))?;
+        }
+
+        match &self.value {
+            MetricValue::Single(value) => write!(f, " = {value:?}")?,
+            MetricValue::Histogram { sum, count, .. } => write!(f, " = count={count} sum={sum:?}")?,
+        }
+        write!(f, " @ {}", format_rfc3339(self.timestamp))
+    }
+}
+
+/// Format Unix epoch nanoseconds as a UTC RFC 3339 timestamp with milliseconds
+fn format_rfc3339(nanos: u64) -> String {
+    let secs = nanos / 1_000_000_000;
+    let millis = nanos % 1_000_000_000 / 1_000_000;
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+        millis
+    )
 }
 
 /// Enumeration of supported metric types
 ///
 /// Each type represents a different way of measuring and aggregating data.
 /// The choice of metric type affects how the data is stored and queried.
+///
+/// Serializes as the lowercase name used by `Display` (`"counter"`). The
+/// capitalized variant names written by earlier versions are still accepted
+/// when deserializing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum MetricType {
     /// Counter - Monotonically increasing value (requests, errors, bytes sent)
+    #[serde(alias = "Counter")]
     Counter,
 
     /// Gauge - Value that can go up or down (memory usage, CPU, active connections)
+    #[serde(alias = "Gauge")]
     Gauge,
 
     /// Histogram - Statistical distribution of values (request latencies, payload sizes)
+    #[serde(alias = "Histogram")]
     Histogram,
 
     /// Timer - Duration measurements (typically converted to histograms by adapters)
+    #[serde(alias = "Timer")]
     Timer,
+
+    /// Info - Constant 1-valued series carrying metadata in its labels (build, version)
+    Info,
 }
 
 impl std::fmt::Display for MetricType {
@@ -238,10 +900,25 @@ impl std::fmt::Display for MetricType {
             MetricType::Gauge => write!(f, "gauge"),
             MetricType::Histogram => write!(f, "histogram"),
             MetricType::Timer => write!(f, "timer"),
+            MetricType::Info => write!(f, "info"),
         }
     }
 }
 
+/// Aggregation temporality of a metric value
+///
+/// Follows OTLP semantics: a cumulative value covers everything since a fixed
+/// start time, while a delta value covers only the interval since the previous
+/// report. Mixing the two for one series corrupts dashboards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AggregationTemporality {
+    /// Value accumulated since a fixed start time
+    Cumulative,
+
+    /// Value accumulated since the previous report
+    Delta,
+}
+
 /// Metric value that can represent either simple values or histogram data
 ///
 /// This enum allows the metrics system to handle both simple numeric values
@@ -262,30 +939,362 @@ pub enum MetricValue {
     },
 }
 
-/// Histogram bucket for statistical distribution
-///
-/// Represents a bucket in a histogram with an upper bound and count.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct HistogramBucket {
-    /// Upper bound for this bucket (inclusive)
-    pub upper_bound: f64,
-    /// Number of observations that fell into this bucket
-    pub count: u64,
-}
+impl MetricValue {
+    /// Create a single value
+    pub const fn single(value: f64) -> Self {
+        MetricValue::Single(value)
+    }
 
-/// RAII timer guard for automatic duration recording
-///
-/// This guard automatically records the elapsed duration when it's dropped,
-/// providing a convenient way to measure execution time without manual
-/// start/stop calls.
-///
-/// ## Example Usage
-/// ```rust
-/// use tyl_metrics_port::{MockMetricsAdapter, MockMetricsConfig, Labels, MetricsManager};
-///
-/// # tokio_test::block_on(async {
-/// let metrics = MockMetricsAdapter::new(MockMetricsConfig::default());
-/// let labels = Labels::new();
+    /// Fold observations into a histogram with cumulative counts over `bounds`
+    ///
+    /// `bounds` must be sorted ascending and end with `+Inf`, as
+    /// `HistogramBuckets::custom` returns them.
+    pub(crate) fn from_observations(values: &[f64], bounds: &[f64]) -> Self {
+        let mut counts = vec![0u64; bounds.len()];
+        for value in values {
+            counts[bucket_index(*value, bounds)] += 1;
+        }
+        let buckets = bounds
+            .iter()
+            .zip(counts)
+            .scan(0, |cumulative, (&upper_bound, count)| {
+                *cumulative += count;
+                Some(HistogramBucket {
+                    upper_bound,
+                    count: *cumulative,
+                })
+            })
+            .collect();
+
+        MetricValue::Histogram {
+            sum: values.iter().sum(),
+            count: values.len() as u64,
+            buckets,
+        }
+    }
+
+    /// Get the value as one number
+    ///
+    /// Returns the single value, or the mean (`sum / count`) of a histogram,
+    /// as `MetricRequest::value` does. An empty histogram has no mean, so it
+    /// returns `None`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::MetricValue;
+    ///
+    /// assert_eq!(MetricValue::single(3.0).as_f64(), Some(3.0));
+    /// let histogram = MetricValue::Histogram { sum: 9.0, count: 3, buckets: vec![] };
+    /// assert_eq!(histogram.as_f64(), Some(3.0));
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            MetricValue::Single(value) => Some(*value),
+            MetricValue::Histogram { count: 0, .. } => None,
+            MetricValue::Histogram { sum, count, .. } => Some(sum / *count as f64),
+        }
+    }
+
+    /// Whether this is a single value
+    pub fn is_single(&self) -> bool {
+        matches!(self, MetricValue::Single(_))
+    }
+
+    /// Whether this is a histogram distribution
+    pub fn is_histogram(&self) -> bool {
+        matches!(self, MetricValue::Histogram { .. })
+    }
+
+    /// Get the histogram sum, or `None` for a single value
+    pub fn sum(&self) -> Option<f64> {
+        match self {
+            MetricValue::Histogram { sum, .. } => Some(*sum),
+            MetricValue::Single(_) => None,
+        }
+    }
+
+    /// Get the histogram observation count, or `None` for a single value
+    pub fn count(&self) -> Option<u64> {
+        match self {
+            MetricValue::Histogram { count, .. } => Some(*count),
+            MetricValue::Single(_) => None,
+        }
+    }
+
+    /// Get the histogram buckets, or `None` for a single value
+    pub fn buckets(&self) -> Option<&[HistogramBucket]> {
+        match self {
+            MetricValue::Histogram { buckets, .. } => Some(buckets),
+            MetricValue::Single(_) => None,
+        }
+    }
+
+    /// Convert a value measured in one time unit into another
+    ///
+    /// Single values, histogram sums, and bucket bounds are scaled; counts are
+    /// unchanged. Scaling multiplies or divides by the exact ratio between
+    /// the units, so e.g. milliseconds to seconds matches
+    /// `Duration::as_secs_f64`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{MetricValue, TimeUnit};
+    ///
+    /// let value = MetricValue::Single(250.0).convert(TimeUnit::Milliseconds, TimeUnit::Seconds);
+    /// assert_eq!(value, MetricValue::Single(0.25));
+    /// ```
+    pub fn convert(self, from: TimeUnit, to: TimeUnit) -> Self {
+        let scale = |value: f64| {
+            let (from, to) = (from.nanos(), to.nanos());
+            if from >= to {
+                value * (from / to) as f64
+            } else {
+                value / (to / from) as f64
+            }
+        };
+
+        match self {
+            MetricValue::Single(value) => MetricValue::Single(scale(value)),
+            MetricValue::Histogram {
+                sum,
+                count,
+                buckets,
+            } => MetricValue::Histogram {
+                sum: scale(sum),
+                count,
+                buckets: buckets
+                    .into_iter()
+                    .map(|bucket| HistogramBucket {
+                        upper_bound: scale(bucket.upper_bound),
+                        count: bucket.count,
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// Single values order by value; histograms only compare equal to themselves
+///
+/// Lets assertions read `assert!(value > MetricValue::single(0.0))`. Comparing
+/// a single value with a histogram, or two different histograms, returns
+/// `None`, so every ordering operator is `false`.
+impl PartialOrd for MetricValue {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (MetricValue::Single(a), MetricValue::Single(b)) => a.partial_cmp(b),
+            _ if self == other => Some(std::cmp::Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+/// Unit of a duration value
+///
+/// Timers are always stored in seconds; the other units exist for
+/// converting values measured elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeUnit {
+    /// Nanoseconds
+    Nanoseconds,
+
+    /// Microseconds
+    Microseconds,
+
+    /// Milliseconds
+    Milliseconds,
+
+    /// Seconds
+    Seconds,
+}
+
+impl TimeUnit {
+    /// Length of one unit in nanoseconds
+    fn nanos(self) -> u64 {
+        match self {
+            TimeUnit::Nanoseconds => 1,
+            TimeUnit::Microseconds => 1_000,
+            TimeUnit::Milliseconds => 1_000_000,
+            TimeUnit::Seconds => 1_000_000_000,
+        }
+    }
+}
+
+/// Descriptive statistics over a set of recorded values
+///
+/// Computed from raw observations, which is handy for verifying what was fed
+/// into a histogram without choosing buckets. `stddev` is the population
+/// standard deviation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricStats {
+    /// Number of values
+    pub count: u64,
+    /// Sum of all values
+    pub sum: f64,
+    /// Smallest value
+    pub min: f64,
+    /// Largest value
+    pub max: f64,
+    /// Arithmetic mean
+    pub mean: f64,
+    /// Population standard deviation (0.0 for a single value)
+    pub stddev: f64,
+}
+
+impl MetricStats {
+    /// Compute statistics over a set of values
+    ///
+    /// Returns `None` for an empty set rather than a NaN mean.
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let count = values.len();
+        let sum: f64 = values.iter().sum();
+        let mean = sum / count as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count as f64;
+
+        Some(Self {
+            count: count as u64,
+            sum,
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean,
+            stddev: variance.sqrt(),
+        })
+    }
+}
+
+/// Histogram bucket for statistical distribution
+///
+/// Represents a bucket in a histogram with an upper bound and count.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    /// Upper bound for this bucket (inclusive)
+    #[serde(with = "bucket_bound")]
+    pub upper_bound: f64,
+    /// Number of observations that fell into this bucket
+    pub count: u64,
+}
+
+/// Common histogram bucket bound layouts
+///
+/// The layout constructors return finite upper bounds in ascending order;
+/// the implicit `+Inf` bucket is left to whoever builds the histogram.
+/// `custom` validates arbitrary bounds, including negative ones, and appends
+/// `+Inf` itself. Each bucket's lower bound is the previous bucket's upper
+/// bound (exclusive), or `-Inf` for the first bucket.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::HistogramBuckets;
+///
+/// assert_eq!(HistogramBuckets::linear(1.0, 1.0, 3), vec![1.0, 2.0, 3.0]);
+/// assert_eq!(HistogramBuckets::exponential(1.0, 2.0, 3), vec![1.0, 2.0, 4.0]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistogramBuckets;
+
+impl HistogramBuckets {
+    /// Request latency bounds in seconds, from 5ms to 10s
+    ///
+    /// These match the Prometheus client default buckets.
+    pub fn latency() -> Vec<f64> {
+        vec![
+            0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+        ]
+    }
+
+    /// `count` bounds starting at `start`, each `width` apart
+    pub fn linear(start: f64, width: f64, count: usize) -> Vec<f64> {
+        (0..count).map(|i| start + width * i as f64).collect()
+    }
+
+    /// `count` bounds starting at `start`, each `factor` times the previous
+    ///
+    /// `start` must be positive and `factor` greater than 1 for the bounds to
+    /// be ascending; use `linear` or `custom` for layouts crossing zero.
+    pub fn exponential(start: f64, factor: f64, count: usize) -> Vec<f64> {
+        (0..count).map(|i| start * factor.powi(i as i32)).collect()
+    }
+
+    /// Validate arbitrary bounds, returning them sorted and unique with `+Inf` appended
+    ///
+    /// Negative bounds are allowed; NaN and empty input are rejected.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::HistogramBuckets;
+    ///
+    /// assert_eq!(
+    ///     HistogramBuckets::custom(&[1.0, -1.0, 0.0]).unwrap(),
+    ///     vec![-1.0, 0.0, 1.0, f64::INFINITY]
+    /// );
+    /// ```
+    pub fn custom(bounds: &[f64]) -> crate::Result<Vec<f64>> {
+        let mut bounds = crate::utils::validate_histogram_buckets(bounds)?;
+        if bounds.last() != Some(&f64::INFINITY) {
+            bounds.push(f64::INFINITY);
+        }
+        Ok(bounds)
+    }
+}
+
+/// Serde support for histogram bucket bounds
+///
+/// JSON has no representation for infinity, so infinite bounds are written as
+/// the Prometheus-style strings `"+Inf"` and `"-Inf"`. Finite bounds stay numeric.
+mod bucket_bound {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Bound {
+        Number(f64),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(bound: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        if bound.is_infinite() {
+            let text = if bound.is_sign_positive() {
+                "+Inf"
+            } else {
+                "-Inf"
+            };
+            serializer.serialize_str(text)
+        } else {
+            serializer.serialize_f64(*bound)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        match Bound::deserialize(deserializer)? {
+            Bound::Number(value) => Ok(value),
+            Bound::Text(text) => match text.as_str() {
+                "+Inf" | "Inf" => Ok(f64::INFINITY),
+                "-Inf" => Ok(f64::NEG_INFINITY),
+                other => Err(serde::de::Error::custom(format!(
+                    "invalid histogram bucket bound: {other}"
+                ))),
+            },
+        }
+    }
+}
+
+/// RAII timer guard for automatic duration recording
+///
+/// This guard automatically records the elapsed duration when it's dropped,
+/// providing a convenient way to measure execution time without manual
+/// start/stop calls.
+///
+/// ## Example Usage
+/// ```rust
+/// use tyl_metrics_port::{MockMetricsAdapter, MockMetricsConfig, Labels, MetricsManager};
+///
+/// # tokio_test::block_on(async {
+/// let metrics = MockMetricsAdapter::new(MockMetricsConfig::default());
+/// let labels = Labels::new();
 /// {
 ///     let _timer = metrics.start_timer("database_query", labels);
 ///     // ... perform database operation ...
@@ -303,9 +1312,33 @@ pub struct TimerGuard {
     /// Start time for calculating duration
     start_time: Instant,
 
+    /// Time of the previous lap, or the start time before any lap
+    last_lap: Mutex<Instant>,
+
     /// Callback function to record the metric when dropped
     /// Uses trait object to abstract over different adapter types
-    recorder: Box<dyn Fn(MetricRequest) + Send + Sync>,
+    recorder: TimerRecorder,
+
+    /// Where async recordings report their result
+    outcome: Option<TimerOutcome>,
+
+    /// Set by `cancel` so drop records nothing
+    cancelled: bool,
+
+    /// Size limits `add_label` checks against
+    limits: crate::ValidationLimits,
+}
+
+/// Future returned by an async timer recorder
+type RecordFuture = Pin<Box<dyn Future<Output = crate::Result<()>> + Send>>;
+
+/// How a `TimerGuard` hands its measurement to the adapter
+enum TimerRecorder {
+    /// Called synchronously in `drop`
+    Sync(Box<dyn Fn(MetricRequest) + Send + Sync>),
+
+    /// Spawned on the current Tokio runtime from `drop`
+    Async(Box<dyn Fn(MetricRequest) -> RecordFuture + Send + Sync>),
 }
 
 impl TimerGuard {
@@ -319,32 +1352,390 @@ impl TimerGuard {
     where
         F: Fn(MetricRequest) + Send + Sync + 'static,
     {
+        let start_time = Instant::now();
         Self {
             name,
             labels,
-            start_time: Instant::now(),
-            recorder: Box::new(recorder),
+            start_time,
+            last_lap: Mutex::new(start_time),
+            recorder: TimerRecorder::Sync(Box::new(recorder)),
+            outcome: None,
+            cancelled: false,
+            limits: crate::ValidationLimits::default(),
         }
     }
 
+    /// Create a timer guard whose recorder is async
+    ///
+    /// When the guard drops, the future returned by `recorder` is spawned on
+    /// the current Tokio runtime, and its result is reported through the
+    /// guard's `outcome` handle. Without a runtime the recording fails with a
+    /// recording error instead.
+    ///
+    /// # Arguments
+    /// * `name` - The metric name to record to
+    /// * `labels` - Labels to attach to the metric
+    /// * `recorder` - Async callback recording the metric
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{Labels, TimerGuard};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let timer = TimerGuard::new_async("db_query".to_string(), Labels::new(), |request| async move {
+    ///     // e.g. adapter.record(&request).await
+    ///     Ok(())
+    /// });
+    /// let outcome = timer.outcome().unwrap();
+    ///
+    /// drop(timer);
+    /// outcome.finished().await;
+    /// assert!(outcome.take_error().is_none());
+    /// # });
+    /// ```
+    pub fn new_async<F, Fut>(name: String, labels: Labels, recorder: F) -> Self
+    where
+        F: Fn(MetricRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::Result<()>> + Send + 'static,
+    {
+        let start_time = Instant::now();
+        Self {
+            name,
+            labels,
+            start_time,
+            last_lap: Mutex::new(start_time),
+            recorder: TimerRecorder::Async(Box::new(move |request| Box::pin(recorder(request)))),
+            outcome: Some(TimerOutcome::default()),
+            cancelled: false,
+            limits: crate::ValidationLimits::default(),
+        }
+    }
+
+    /// Check labels added with `add_label` against custom size limits
+    ///
+    /// Adapters with configurable limits pass theirs when creating the guard.
+    pub fn with_validation_limits(mut self, limits: crate::ValidationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Get the elapsed duration so far (without stopping the timer)
     pub fn elapsed(&self) -> Duration {
         self.start_time.elapsed()
     }
 
+    /// Record the time since the guard started as `{name}_{checkpoint}`
+    ///
+    /// For multi-stage operations: each lap records a cumulative duration,
+    /// and dropping the guard still records the total under the base name.
+    /// Laps carry the guard's labels. Use `split` to record the time since
+    /// the previous lap instead.
+    ///
+    /// # Returns
+    /// * `Duration` - The recorded time since the guard started
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{Labels, TimerGuard};
+    ///
+    /// let timer = TimerGuard::new("checkout".to_string(), Labels::new(), |request| {
+    ///     println!("{} took {}s", request.name(), request.value());
+    /// });
+    /// timer.lap("validated"); // checkout_validated
+    /// timer.split("charged"); // checkout_charged, time since "validated"
+    /// drop(timer); // checkout
+    /// ```
+    pub fn lap(&self, checkpoint: &str) -> Duration {
+        let now = Instant::now();
+        *self.last_lap.lock().unwrap_or_else(|p| p.into_inner()) = now;
+
+        let since_start = now.duration_since(self.start_time);
+        self.dispatch(self.checkpoint_request(checkpoint, since_start), false);
+        since_start
+    }
+
+    /// Record the time since the previous lap (or the start) as `{name}_{checkpoint}`
+    ///
+    /// Like `lap`, but records the duration of the stage that just ended
+    /// rather than the cumulative time.
+    ///
+    /// # Returns
+    /// * `Duration` - The recorded time since the previous lap
+    pub fn split(&self, checkpoint: &str) -> Duration {
+        let now = Instant::now();
+        let previous = std::mem::replace(
+            &mut *self.last_lap.lock().unwrap_or_else(|p| p.into_inner()),
+            now,
+        );
+
+        let since_previous = now.duration_since(previous);
+        self.dispatch(self.checkpoint_request(checkpoint, since_previous), false);
+        since_previous
+    }
+
+    /// Get a handle to the result of an async recording
+    ///
+    /// Returns `None` for guards created with `new`, whose recorder can't fail.
+    pub fn outcome(&self) -> Option<TimerOutcome> {
+        self.outcome.clone()
+    }
+
     /// Manually record the timer and consume the guard
     pub fn record(self) {
         // Dropping will trigger the recording
     }
+
+    /// Attach a label learned after the timer started, e.g. a result status
+    ///
+    /// The label is validated here against the guard's limits (see
+    /// `with_validation_limits`) and, if valid, carried by the final
+    /// recording and any later laps; an existing key is overwritten.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tyl_metrics_port::{Labels, TimerGuard};
+    ///
+    /// let mut timer = TimerGuard::new("request".to_string(), Labels::new(), |request| {
+    ///     assert_eq!(request.labels()["status"], "error");
+    /// });
+    /// timer.add_label("status", "error").unwrap();
+    /// ```
+    pub fn add_label(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> crate::Result<()> {
+        let (key, value) = (key.into(), value.into());
+        crate::utils::validate_label_key_with(&key, &self.limits)?;
+        crate::utils::validate_label_value_with(
+            &value,
+            crate::LabelValueCharset::Lenient,
+            &self.limits,
+        )?;
+        if !self.labels.contains_key(&key) && self.labels.len() >= self.limits.max_labels {
+            return Err(metrics_error(
+                "labels",
+                format!("Too many labels (max {})", self.limits.max_labels),
+            ));
+        }
+
+        self.labels.insert(key, value);
+        Ok(())
+    }
+
+    /// Consume the guard without recording the total duration
+    ///
+    /// For error paths where the duration is meaningless and would skew the
+    /// latency distribution. Laps already taken stay recorded, and an async
+    /// guard's outcome finishes once they complete.
+    pub fn cancel(mut self) {
+        self.cancelled = true;
+    }
+
+    /// Build the timer request for a checkpoint, carrying the guard's labels
+    fn checkpoint_request(&self, checkpoint: &str, duration: Duration) -> MetricRequest {
+        MetricRequest::timer(format!("{}_{}", self.name, checkpoint), duration)
+            .with_labels(self.labels.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+    }
+
+    /// Hand a measurement to the recorder
+    ///
+    /// Async recordings report to the outcome; it finishes once the final
+    /// recording (from `drop`) and every lap before it have completed.
+    fn dispatch(&self, request: MetricRequest, is_final: bool) {
+        match &self.recorder {
+            TimerRecorder::Sync(recorder) => recorder(request),
+            TimerRecorder::Async(recorder) => {
+                let outcome = self.outcome.clone().unwrap_or_default();
+                outcome.begin(is_final);
+                match tokio::runtime::Handle::try_current() {
+                    Ok(runtime) => {
+                        let recording = recorder(request);
+                        runtime.spawn(async move { outcome.complete(recording.await) });
+                    }
+                    Err(_) => outcome.complete(Err(metrics_recording_error(
+                        request.name(),
+                        "No Tokio runtime to record the timer on",
+                    ))),
+                }
+            }
+        }
+    }
 }
 
 impl Drop for TimerGuard {
     fn drop(&mut self) {
+        if self.cancelled {
+            if let Some(outcome) = &self.outcome {
+                outcome.close();
+            }
+            return;
+        }
+
         let duration = self.start_time.elapsed();
         let request = MetricRequest::timer(self.name.clone(), duration)
             .with_labels(self.labels.iter().map(|(k, v)| (k.as_str(), v.as_str())));
 
-        (self.recorder)(request);
+        self.dispatch(request, true);
+    }
+}
+
+/// RAII guard that increments a counter when its scope exits
+///
+/// The counter analogue of `TimerGuard`, for error and event rates: dropping
+/// the guard records `+1` (an increment, see `MetricRequest::counter_inc`)
+/// under its name and labels. With `on_panic_only` it increments only while
+/// the thread is unwinding from a panic, counting failures of a scope
+/// without touching its happy path.
+///
+/// Adapters hand these out through `MetricsManager::start_counter_guard`
+/// (called by `MetricsManagerExt::start_scope_counter`), which by default
+/// routes the increment through the adapter's timer recorder.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{CounterGuard, Labels};
+///
+/// let guard = CounterGuard::new("jobs_started_total".to_string(), Labels::new(), |request| {
+///     assert!(request.is_increment());
+///     assert_eq!(request.value(), 1.0);
+/// });
+/// drop(guard);
+/// ```
+pub struct CounterGuard {
+    /// Carries the name, labels, and recorder; never records a duration
+    timer: TimerGuard,
+
+    /// Increment only while unwinding
+    panic_only: bool,
+}
+
+impl CounterGuard {
+    /// Create a counter guard
+    ///
+    /// # Arguments
+    /// * `name` - The counter name to increment
+    /// * `labels` - Labels to attach to the increment
+    /// * `recorder` - Callback function to record the increment
+    pub fn new<F>(name: String, labels: Labels, recorder: F) -> Self
+    where
+        F: Fn(MetricRequest) + Send + Sync + 'static,
+    {
+        Self::from_timer(TimerGuard::new(name, labels, recorder))
+    }
+
+    /// Reuse an adapter's timer guard for its name, labels, and recorder
+    pub(crate) fn from_timer(timer: TimerGuard) -> Self {
+        Self {
+            timer,
+            panic_only: false,
+        }
+    }
+
+    /// Increment only if the scope exits by panicking
+    pub fn on_panic_only(mut self) -> Self {
+        self.panic_only = true;
+        self
+    }
+}
+
+impl Drop for CounterGuard {
+    fn drop(&mut self) {
+        // The timer drops after this and must not record a duration
+        self.timer.cancelled = true;
+        if self.panic_only && !std::thread::panicking() {
+            return;
+        }
+
+        let request = MetricRequest::counter_inc(self.timer.name.clone(), 1.0).with_labels(
+            self.timer
+                .labels
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str())),
+        );
+        self.timer.dispatch(request, true);
+    }
+}
+
+/// Shared result of an async timer recording
+///
+/// Obtained from `TimerGuard::outcome` before the guard drops, and
+/// inspectable afterwards once the recording has finished.
+#[derive(Clone, Default)]
+pub struct TimerOutcome {
+    state: Arc<TimerOutcomeState>,
+}
+
+#[derive(Default)]
+struct TimerOutcomeState {
+    /// Whether the final recording has been started
+    closed: AtomicBool,
+    /// Recordings started but not yet completed
+    pending: AtomicUsize,
+    /// First recording error
+    error: Mutex<Option<crate::TylError>>,
+    notify: Notify,
+}
+
+impl TimerOutcome {
+    /// Whether the recording has finished, successfully or not
+    pub fn is_finished(&self) -> bool {
+        self.state.closed.load(Ordering::Acquire) && self.state.pending.load(Ordering::Acquire) == 0
+    }
+
+    /// Wait until the recording has finished
+    pub async fn finished(&self) {
+        loop {
+            let notified = self.state.notify.notified();
+            if self.is_finished() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Take the first recording error, if any recording failed
+    pub fn take_error(&self) -> Option<crate::TylError> {
+        self.state
+            .error
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .take()
+    }
+
+    /// Note a recording in flight; `is_final` marks the last one
+    fn begin(&self, is_final: bool) {
+        self.state.pending.fetch_add(1, Ordering::AcqRel);
+        if is_final {
+            self.state.closed.store(true, Ordering::Release);
+        }
+    }
+
+    /// Mark that no further recordings will start
+    fn close(&self) {
+        self.state.closed.store(true, Ordering::Release);
+        self.state.notify.notify_waiters();
+    }
+
+    /// Store a recording result and wake waiters
+    fn complete(&self, result: crate::Result<()>) {
+        if let Err(error) = result {
+            self.state
+                .error
+                .lock()
+                .unwrap_or_else(|p| p.into_inner())
+                .get_or_insert(error);
+        }
+        self.state.pending.fetch_sub(1, Ordering::AcqRel);
+        self.state.notify.notify_waiters();
+    }
+}
+
+impl std::fmt::Debug for TimerOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimerOutcome")
+            .field("finished", &self.is_finished())
+            .finish()
     }
 }
 
@@ -364,6 +1755,7 @@ pub struct MetricSnapshot {
     pub value: MetricValue,
 
     /// Labels attached to this metric
+    #[serde(serialize_with = "serialize_sorted_labels")]
     pub labels: Labels,
 
     /// Optional help text
@@ -371,6 +1763,14 @@ pub struct MetricSnapshot {
 
     /// Timestamp of this snapshot (Unix epoch nanoseconds)
     pub timestamp: u64,
+
+    /// Start of the interval the value covers (Unix epoch nanoseconds), if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_timestamp: Option<u64>,
+
+    /// Aggregation temporality of the value, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temporality: Option<AggregationTemporality>,
 }
 
 impl MetricSnapshot {
@@ -382,30 +1782,253 @@ impl MetricSnapshot {
             value,
             labels,
             help: None,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_nanos() as u64,
+            timestamp: unix_nanos(),
+            start_timestamp: None,
+            temporality: None,
         }
     }
 
-    /// Add help text to the snapshot
-    pub fn with_help(mut self, help: impl Into<String>) -> Self {
-        self.help = Some(help.into());
+    /// Add help text to the snapshot
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Override the snapshot timestamp (Unix epoch nanoseconds)
+    ///
+    /// Useful for building deterministic fixtures, e.g. exporter golden tests.
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Compare with another snapshot, ignoring when each was taken
+    ///
+    /// Compares name, type, value, labels, help, and temporality, but not
+    /// `timestamp` or `start_timestamp`, so snapshots of the same data taken
+    /// at different times compare equal. The derived `PartialEq` still
+    /// compares every field.
+    pub fn eq_ignoring_timestamp(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.metric_type == other.metric_type
+            && self.value == other.value
+            && self.labels == other.labels
+            && self.help == other.help
+            && self.temporality == other.temporality
+    }
+
+    /// Combine two snapshots of the same series, as an aggregator folding partial results would
+    ///
+    /// Both snapshots must share name, type, and labels. Values are merged by type:
+    /// - Counters are summed
+    /// - Gauges keep the value with the later timestamp (`other` wins ties)
+    /// - Histograms and timers add `sum`, `count`, and bucket counts element-wise;
+    ///   bucket bounds must match
+    ///
+    /// The merged snapshot carries the later of the two timestamps.
+    ///
+    /// # Returns
+    /// * `Result<MetricSnapshot>` - The merged snapshot, or a validation error
+    ///   if the snapshots are not mergeable
+    pub fn merge(&self, other: &MetricSnapshot) -> crate::Result<MetricSnapshot> {
+        if self.name != other.name {
+            return Err(metrics_error(
+                "snapshot",
+                format!(
+                    "Cannot merge different metrics {} and {}",
+                    self.name, other.name
+                ),
+            ));
+        }
+        if self.metric_type != other.metric_type {
+            return Err(metrics_error(
+                "snapshot",
+                format!(
+                    "Cannot merge {} with {} for {}",
+                    self.metric_type, other.metric_type, self.name
+                ),
+            ));
+        }
+        if self.labels != other.labels {
+            return Err(metrics_error(
+                "snapshot",
+                format!("Cannot merge {} series with different labels", self.name),
+            ));
+        }
+
+        let value = match (self.metric_type, &self.value, &other.value) {
+            (MetricType::Counter, MetricValue::Single(a), MetricValue::Single(b)) => {
+                MetricValue::Single(a + b)
+            }
+            (
+                MetricType::Gauge | MetricType::Info,
+                MetricValue::Single(a),
+                MetricValue::Single(b),
+            ) => {
+                if self.timestamp > other.timestamp {
+                    MetricValue::Single(*a)
+                } else {
+                    MetricValue::Single(*b)
+                }
+            }
+            (
+                MetricType::Histogram | MetricType::Timer,
+                MetricValue::Histogram {
+                    sum: sum_a,
+                    count: count_a,
+                    buckets: buckets_a,
+                },
+                MetricValue::Histogram {
+                    sum: sum_b,
+                    count: count_b,
+                    buckets: buckets_b,
+                },
+            ) => {
+                let bounds_match = buckets_a.len() == buckets_b.len()
+                    && buckets_a
+                        .iter()
+                        .zip(buckets_b)
+                        .all(|(a, b)| a.upper_bound == b.upper_bound);
+                if !bounds_match {
+                    return Err(metrics_error(
+                        "buckets",
+                        format!(
+                            "Cannot merge {} histograms with different buckets",
+                            self.name
+                        ),
+                    ));
+                }
+
+                MetricValue::Histogram {
+                    sum: sum_a + sum_b,
+                    count: count_a + count_b,
+                    buckets: buckets_a
+                        .iter()
+                        .zip(buckets_b)
+                        .map(|(a, b)| HistogramBucket {
+                            upper_bound: a.upper_bound,
+                            count: a.count + b.count,
+                        })
+                        .collect(),
+                }
+            }
+            _ => {
+                return Err(metrics_error(
+                    "value",
+                    format!(
+                        "Cannot merge these {} values for {}",
+                        self.metric_type, self.name
+                    ),
+                ))
+            }
+        };
+
+        Ok(MetricSnapshot {
+            value,
+            help: self.help.clone().or_else(|| other.help.clone()),
+            timestamp: self.timestamp.max(other.timestamp),
+            start_timestamp: match (self.start_timestamp, other.start_timestamp) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+            ..self.clone()
+        })
+    }
+}
+
+impl From<&MetricRequest> for MetricSnapshot {
+    fn from(request: &MetricRequest) -> Self {
+        Self {
+            name: request.name.clone(),
+            metric_type: request.metric_type,
+            value: request.value.clone(),
+            labels: request.labels.clone(),
+            help: request.help.clone(),
+            timestamp: request.timestamp,
+            start_timestamp: request.start_timestamp,
+            temporality: request.temporality,
+        }
+    }
+}
+
+/// Rebuild the request a snapshot was taken from, e.g. to replay it
+impl From<&MetricSnapshot> for MetricRequest {
+    fn from(snapshot: &MetricSnapshot) -> Self {
+        let mut request = Self::new(
+            snapshot.name.clone(),
+            snapshot.metric_type,
+            snapshot.value.clone(),
+        );
+        request.labels = snapshot.labels.clone();
+        request.help = snapshot.help.clone();
+        request.timestamp = snapshot.timestamp;
+        request.start_timestamp = snapshot.start_timestamp;
+        request.temporality = snapshot.temporality;
+        request
+    }
+}
+
+/// Criteria for selecting snapshots in `MetricsManager::get_snapshot_filtered`
+///
+/// Every criterion that is set must match; an empty filter matches everything.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{MetricType, SnapshotFilter};
+///
+/// let filter = SnapshotFilter::new()
+///     .with_name_prefix("http_")
+///     .with_metric_type(MetricType::Counter)
+///     .with_label("method", "GET");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotFilter {
+    /// Keep only metrics whose name starts with this prefix
+    pub name_prefix: Option<String>,
+
+    /// Keep only metrics of this type
+    pub metric_type: Option<MetricType>,
+
+    /// Keep only metrics carrying this (key, value) label
+    pub label_match: Option<(String, String)>,
+}
+
+impl SnapshotFilter {
+    /// Create a filter that matches every snapshot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only metrics whose name starts with `prefix`
+    pub fn with_name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.name_prefix = Some(prefix.into());
         self
     }
-}
 
-impl From<&MetricRequest> for MetricSnapshot {
-    fn from(request: &MetricRequest) -> Self {
-        Self {
-            name: request.name.clone(),
-            metric_type: request.metric_type,
-            value: request.value.clone(),
-            labels: request.labels.clone(),
-            help: request.help.clone(),
-            timestamp: request.timestamp,
-        }
+    /// Keep only metrics of `metric_type`
+    pub fn with_metric_type(mut self, metric_type: MetricType) -> Self {
+        self.metric_type = Some(metric_type);
+        self
+    }
+
+    /// Keep only metrics with label `key` set to `value`
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.label_match = Some((key.into(), value.into()));
+        self
+    }
+
+    /// Check whether a snapshot satisfies every criterion
+    pub fn matches(&self, snapshot: &MetricSnapshot) -> bool {
+        self.name_prefix
+            .as_deref()
+            .map_or(true, |prefix| snapshot.name.starts_with(prefix))
+            && self
+                .metric_type
+                .map_or(true, |metric_type| snapshot.metric_type == metric_type)
+            && self
+                .label_match
+                .as_ref()
+                .map_or(true, |(key, value)| snapshot.labels.get(key) == Some(value))
     }
 }
 
@@ -414,6 +2037,29 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    #[test]
+    fn test_snapshot_filter_matches() {
+        let snapshot = MetricSnapshot::from(
+            &MetricRequest::counter("http_requests_total", 1.0).with_label("method", "GET"),
+        );
+
+        assert!(SnapshotFilter::new().matches(&snapshot));
+        assert!(SnapshotFilter::new()
+            .with_name_prefix("http_")
+            .with_metric_type(MetricType::Counter)
+            .with_label("method", "GET")
+            .matches(&snapshot));
+        assert!(!SnapshotFilter::new()
+            .with_name_prefix("db_")
+            .matches(&snapshot));
+        assert!(!SnapshotFilter::new()
+            .with_metric_type(MetricType::Gauge)
+            .matches(&snapshot));
+        assert!(!SnapshotFilter::new()
+            .with_label("method", "POST")
+            .matches(&snapshot));
+    }
+
     #[test]
     fn test_metric_request_counter() {
         let request = MetricRequest::counter("http_requests", 1.0);
@@ -452,6 +2098,46 @@ mod tests {
         assert_eq!(request.help(), Some("Time spent processing HTTP requests"));
     }
 
+    #[test]
+    fn test_metric_request_validate_all() {
+        let request = MetricRequest::counter("http requests", 1.0)
+            .with_label("method", "GET")
+            .with_label("path", "x".repeat(2000));
+
+        let errors = request.validate_all();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].to_string().contains("metric_name"));
+        assert!(errors[1].to_string().contains("label_value"));
+        // Validation proper still stops at the first problem
+        assert_eq!(
+            request.clone().validate().err().unwrap().to_string(),
+            errors[0].to_string()
+        );
+
+        assert!(MetricRequest::counter("http_requests", 1.0)
+            .validate_all()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_metric_request_display() {
+        let request = MetricRequest::counter("http_requests", 1.0)
+            .with_label("status", "200")
+            .with_label("method", "GET")
+            .with_timestamp(1_700_000_000_123_456_789);
+        assert_eq!(
+            request.to_string(),
+            "counter http_requests{method=GET,status=200} = 1.0 @ 2023-11-14T22:13:20.123Z"
+        );
+
+        let leap_day = MetricRequest::histogram_many("latency_seconds", vec![0.25, 0.5])
+            .with_timestamp(1_709_210_096_000_000_000);
+        assert_eq!(
+            leap_day.to_string(),
+            "histogram latency_seconds = count=2 sum=0.75 @ 2024-02-29T12:34:56.000Z"
+        );
+    }
+
     #[test]
     fn test_metric_request_timer() {
         let duration = Duration::from_millis(150);
@@ -461,6 +2147,157 @@ mod tests {
         assert_eq!(request.value(), 0.15); // 150ms as seconds
     }
 
+    #[test]
+    fn test_metric_request_timer_units() {
+        let canonical = MetricRequest::timer("db_query", Duration::from_millis(50));
+        let millis = MetricRequest::timer_millis("db_query", 50.0);
+        let micros = MetricRequest::timer_micros("db_query", 50_000.0);
+
+        assert_eq!(millis.metric_type(), &MetricType::Timer);
+        assert_eq!(millis.value(), 0.05);
+        assert_eq!(millis.value(), canonical.value());
+        assert_eq!(micros.value(), canonical.value());
+        assert_eq!(
+            MetricRequest::timer_micros("db_query", 1_234.0).value(),
+            Duration::from_micros(1_234).as_secs_f64()
+        );
+    }
+
+    #[test]
+    fn test_with_labels_from_struct() {
+        #[derive(Serialize)]
+        struct Route {
+            endpoint: String,
+            status: u16,
+            cached: bool,
+            region: Option<String>,
+        }
+
+        let request = MetricRequest::counter("http_requests_total", 1.0)
+            .with_label("method", "GET")
+            .with_labels_from(&Route {
+                endpoint: "/users".to_string(),
+                status: 404,
+                cached: false,
+                region: None,
+            })
+            .unwrap();
+
+        let expected: Labels = [
+            ("method", "GET"),
+            ("endpoint", "/users"),
+            ("status", "404"),
+            ("cached", "false"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(request.labels(), &expected);
+    }
+
+    #[test]
+    fn test_with_labels_from_rejects_nested_values() {
+        #[derive(Serialize)]
+        struct Nested {
+            service: &'static str,
+            owner: Owner,
+        }
+
+        #[derive(Serialize)]
+        struct Owner {
+            team: &'static str,
+        }
+
+        let nested = Nested {
+            service: "billing",
+            owner: Owner { team: "payments" },
+        };
+        let error = MetricRequest::counter("jobs_total", 1.0)
+            .with_labels_from(&nested)
+            .unwrap_err();
+        assert!(error.to_string().contains("Label owner is nested"));
+
+        assert!(MetricRequest::counter("jobs_total", 1.0)
+            .with_labels_from(&["a", "b"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_histogram_many_folds_observations() {
+        let request = MetricRequest::histogram_many("batch_seconds", vec![0.004, 0.2, 30.0]);
+        assert_eq!(request.metric_type(), &MetricType::Histogram);
+        assert_eq!(request.observations(), &[0.004, 0.2, 30.0]);
+
+        let value = MetricSnapshot::from(&request).value;
+        assert_eq!(value.count(), Some(3));
+        assert_eq!(value.sum(), Some(30.204));
+        let buckets = value.buckets().unwrap();
+        let last = buckets.last().unwrap();
+        assert!(last.upper_bound.is_infinite());
+        assert_eq!(last.count, 3);
+        assert_eq!(buckets[0].count, 1);
+
+        assert!(MetricRequest::histogram("single", 1.0)
+            .observations()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_metric_request_duration_constructors() {
+        let uptime = MetricRequest::gauge_duration("uptime_seconds", Duration::from_secs(90));
+        assert_eq!(uptime.metric_type(), &MetricType::Gauge);
+        assert_eq!(uptime.value(), 90.0);
+
+        let cpu = MetricRequest::counter_duration("cpu_seconds_total", Duration::from_millis(1500));
+        assert_eq!(cpu.metric_type(), &MetricType::Counter);
+        assert_eq!(cpu.value(), 1.5);
+        assert_eq!(cpu.temporality(), Some(AggregationTemporality::Cumulative));
+    }
+
+    #[test]
+    fn test_metric_value_convert() {
+        assert_eq!(
+            MetricValue::Single(1.5).convert(TimeUnit::Seconds, TimeUnit::Milliseconds),
+            MetricValue::Single(1500.0)
+        );
+        assert_eq!(
+            MetricValue::Single(7.0).convert(TimeUnit::Nanoseconds, TimeUnit::Nanoseconds),
+            MetricValue::Single(7.0)
+        );
+
+        let histogram = MetricValue::Histogram {
+            sum: 300.0,
+            count: 2,
+            buckets: vec![
+                HistogramBucket {
+                    upper_bound: 250.0,
+                    count: 1,
+                },
+                HistogramBucket {
+                    upper_bound: f64::INFINITY,
+                    count: 2,
+                },
+            ],
+        };
+        assert_eq!(
+            histogram.convert(TimeUnit::Milliseconds, TimeUnit::Seconds),
+            MetricValue::Histogram {
+                sum: 0.3,
+                count: 2,
+                buckets: vec![
+                    HistogramBucket {
+                        upper_bound: 0.25,
+                        count: 1,
+                    },
+                    HistogramBucket {
+                        upper_bound: f64::INFINITY,
+                        count: 2,
+                    },
+                ],
+            }
+        );
+    }
+
     #[test]
     fn test_metric_types_display() {
         assert_eq!(MetricType::Counter.to_string(), "counter");
@@ -469,6 +2306,184 @@ mod tests {
         assert_eq!(MetricType::Timer.to_string(), "timer");
     }
 
+    #[test]
+    fn test_validated_request_with_value() {
+        let template = MetricRequest::counter("requests", 1.0)
+            .with_label("method", "GET")
+            .validate()
+            .unwrap();
+
+        let next = template.clone().with_value(5.0).unwrap();
+        assert_eq!(next.request().value(), 5.0);
+        assert_eq!(next.request().labels(), template.request().labels());
+        assert!(template.clone().with_value(-1.0).is_err());
+        assert!(template.with_value(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_histogram_buckets_layouts() {
+        let latency = HistogramBuckets::latency();
+        assert_eq!(latency.first(), Some(&0.005));
+        assert_eq!(latency.last(), Some(&10.0));
+        assert!(latency.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert_eq!(
+            HistogramBuckets::linear(0.5, 0.5, 4),
+            vec![0.5, 1.0, 1.5, 2.0]
+        );
+        assert_eq!(
+            HistogramBuckets::exponential(0.1, 10.0, 3),
+            vec![0.1, 0.1 * 10.0, 0.1 * 100.0]
+        );
+        assert!(HistogramBuckets::linear(0.0, 1.0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_temporality_defaults() {
+        assert_eq!(
+            MetricRequest::counter("requests", 1.0).temporality(),
+            Some(AggregationTemporality::Cumulative)
+        );
+        assert_eq!(MetricRequest::gauge("memory", 1.0).temporality(), None);
+        assert_eq!(MetricRequest::histogram("latency", 1.0).temporality(), None);
+
+        let delta =
+            MetricRequest::counter("requests", 1.0).with_temporality(AggregationTemporality::Delta);
+        assert_eq!(delta.temporality(), Some(AggregationTemporality::Delta));
+        assert_eq!(
+            MetricSnapshot::from(&delta).temporality,
+            Some(AggregationTemporality::Delta)
+        );
+    }
+
+    #[test]
+    fn test_metric_type_serializes_lowercase() {
+        // Wire format: changed from the variant names ("Counter") to match Display
+        for metric_type in [
+            MetricType::Counter,
+            MetricType::Gauge,
+            MetricType::Histogram,
+            MetricType::Timer,
+        ] {
+            let json = serde_json::to_string(&metric_type).unwrap();
+            assert_eq!(json, format!("\"{metric_type}\""));
+            assert_eq!(
+                serde_json::from_str::<MetricType>(&json).unwrap(),
+                metric_type
+            );
+        }
+
+        // Payloads written before the change still parse
+        assert_eq!(
+            serde_json::from_str::<MetricType>("\"Counter\"").unwrap(),
+            MetricType::Counter
+        );
+    }
+
+    #[test]
+    fn test_metric_request_json_pinned() {
+        let request = MetricRequest::data_point(
+            "requests",
+            MetricType::Counter,
+            MetricValue::Single(1.0),
+            500,
+            1_000,
+            AggregationTemporality::Cumulative,
+        )
+        .unwrap()
+        .with_label("method", "GET");
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"requests","metric_type":"counter","value":{"Single":1.0},"labels":{"method":"GET"},"help":null,"timestamp":1000,"start_timestamp":500,"temporality":"Cumulative"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<MetricRequest>(&json).unwrap(),
+            request
+        );
+
+        let snapshot = MetricSnapshot::from(&request);
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(json.contains(r#""metric_type":"counter""#));
+        assert_eq!(
+            serde_json::from_str::<MetricSnapshot>(&json).unwrap(),
+            snapshot
+        );
+    }
+
+    #[test]
+    fn test_metric_name_construction() {
+        let name = MetricName::new("http_requests_total").unwrap();
+        assert_eq!(&*name, "http_requests_total");
+        assert_eq!(name.to_string(), "http_requests_total");
+        assert!(name.starts_with("http_"));
+
+        assert!(MetricName::new("").is_err());
+        assert!(MetricName::new("invalid name").is_err());
+        assert!(MetricName::new("9starts_with_digit").is_err());
+
+        const QUEUE_DEPTH: MetricName = MetricName::from_static("queue_depth");
+        assert_eq!(MetricName::new(QUEUE_DEPTH.as_str()).unwrap(), QUEUE_DEPTH);
+    }
+
+    #[test]
+    fn test_metric_name_from_static_matches_validation() {
+        for name in [
+            "queue_depth",
+            "_private",
+            "ns:metric",
+            "a1",
+            "",
+            "9lives",
+            "bad name",
+        ] {
+            assert_eq!(
+                is_valid_static_name(name),
+                MetricName::new(name).is_ok(),
+                "{name}"
+            );
+        }
+        assert!(!is_valid_static_name(&"a".repeat(256)));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid metric name")]
+    fn test_metric_name_from_static_rejects_invalid() {
+        let name: &'static str = "bad name";
+        MetricName::from_static(name);
+    }
+
+    #[test]
+    fn test_metric_name_in_builders() {
+        let name = MetricName::new("queue_depth").unwrap();
+
+        let by_ref = MetricRequest::gauge(&name, 3.0);
+        let by_value = MetricRequest::counter(name, 1.0);
+        let timer = MetricRequest::timer(
+            MetricName::from_static("db_query_duration"),
+            Duration::from_millis(5),
+        );
+
+        assert_eq!(by_ref.name(), "queue_depth");
+        assert_eq!(by_value.name(), "queue_depth");
+        assert_eq!(timer.name(), "db_query_duration");
+    }
+
+    #[test]
+    fn test_histogram_buckets_custom() {
+        assert_eq!(
+            HistogramBuckets::custom(&[1.0, 0.0, -1.0, 0.0]).unwrap(),
+            vec![-1.0, 0.0, 1.0, f64::INFINITY]
+        );
+        assert_eq!(
+            HistogramBuckets::custom(&[f64::INFINITY, -0.5]).unwrap(),
+            vec![-0.5, f64::INFINITY]
+        );
+        assert!(HistogramBuckets::custom(&[]).is_err());
+        assert!(HistogramBuckets::custom(&[-1.0, f64::NAN]).is_err());
+    }
+
     #[test]
     fn test_histogram_bucket() {
         let bucket = HistogramBucket {
@@ -480,6 +2495,31 @@ mod tests {
         assert_eq!(bucket.count, 42);
     }
 
+    #[test]
+    fn test_metric_stats_known_dataset() {
+        let stats = MetricStats::from_values(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+
+        assert_eq!(stats.count, 8);
+        assert_eq!(stats.sum, 40.0);
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.max, 9.0);
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.stddev, 2.0);
+    }
+
+    #[test]
+    fn test_metric_stats_single_value() {
+        let stats = MetricStats::from_values(&[3.5]).unwrap();
+        assert_eq!(stats.count, 1);
+        assert_eq!(stats.mean, 3.5);
+        assert_eq!(stats.stddev, 0.0);
+    }
+
+    #[test]
+    fn test_metric_stats_empty() {
+        assert_eq!(MetricStats::from_values(&[]), None);
+    }
+
     #[test]
     fn test_metric_value_single() {
         let value = MetricValue::Single(123.45);
@@ -526,6 +2566,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_metric_value_accessors() {
+        let single = MetricValue::single(2.5);
+        assert_eq!(single, MetricValue::Single(2.5));
+        assert_eq!(single.as_f64(), Some(2.5));
+        assert!(single.is_single());
+        assert!(!single.is_histogram());
+        assert_eq!(single.sum(), None);
+        assert_eq!(single.count(), None);
+        assert_eq!(single.buckets(), None);
+
+        let buckets = vec![HistogramBucket {
+            upper_bound: f64::INFINITY,
+            count: 4,
+        }];
+        let histogram = MetricValue::Histogram {
+            sum: 10.0,
+            count: 4,
+            buckets: buckets.clone(),
+        };
+        assert!(histogram.is_histogram());
+        assert_eq!(histogram.sum(), Some(10.0));
+        assert_eq!(histogram.count(), Some(4));
+        assert_eq!(histogram.buckets(), Some(buckets.as_slice()));
+    }
+
+    #[test]
+    fn test_metric_value_as_f64_matches_request_value() {
+        let histogram = MetricValue::Histogram {
+            sum: 10.0,
+            count: 4,
+            buckets: vec![],
+        };
+        let request = MetricRequest::new(
+            "latency".to_string(),
+            MetricType::Histogram,
+            histogram.clone(),
+        );
+        assert_eq!(histogram.as_f64(), Some(2.5));
+        assert_eq!(histogram.as_f64(), Some(request.value()));
+
+        let empty = MetricValue::Histogram {
+            sum: 0.0,
+            count: 0,
+            buckets: vec![],
+        };
+        assert_eq!(empty.as_f64(), None);
+        assert!(
+            MetricRequest::new("latency".to_string(), MetricType::Histogram, empty)
+                .value()
+                .is_nan()
+        );
+    }
+
+    #[test]
+    fn test_metric_value_ordering() {
+        assert!(MetricValue::single(2.0) > MetricValue::single(1.0));
+        assert!(MetricValue::single(1.0) <= MetricValue::single(1.0));
+
+        let histogram = MetricValue::Histogram {
+            sum: 1.0,
+            count: 1,
+            buckets: vec![],
+        };
+        assert_eq!(
+            histogram.partial_cmp(&histogram.clone()),
+            Some(std::cmp::Ordering::Equal)
+        );
+        assert_eq!(histogram.partial_cmp(&MetricValue::single(1.0)), None);
+        let single = MetricValue::single(5.0);
+        assert!(!histogram.lt(&single) && !histogram.ge(&single));
+        assert_eq!(
+            MetricValue::single(f64::NAN).partial_cmp(&MetricValue::single(1.0)),
+            None
+        );
+    }
+
     #[test]
     fn test_metric_snapshot_creation() {
         let labels = vec![("env", "test")]
@@ -563,6 +2680,151 @@ mod tests {
         assert_eq!(snapshot.help, request.help().map(|s| s.to_string()));
     }
 
+    #[test]
+    fn test_snapshot_eq_ignoring_timestamp() {
+        let request = MetricRequest::counter("requests_total", 3.0).with_label("env", "test");
+        let earlier = MetricSnapshot::from(&request).with_timestamp(1_000);
+        let later = MetricSnapshot::from(&request).with_timestamp(9_000);
+
+        assert_ne!(earlier, later);
+        assert!(earlier.eq_ignoring_timestamp(&later));
+        assert!(!earlier.eq_ignoring_timestamp(&later.clone().with_help("Requests")));
+
+        let mut relabeled = later;
+        relabeled
+            .labels
+            .insert("env".to_string(), "prod".to_string());
+        assert!(!earlier.eq_ignoring_timestamp(&relabeled));
+    }
+
+    #[test]
+    fn test_data_point_preserves_timing() {
+        let request = MetricRequest::data_point(
+            "otlp_requests",
+            MetricType::Counter,
+            MetricValue::Single(42.0),
+            1_000,
+            5_000,
+            AggregationTemporality::Delta,
+        )
+        .unwrap()
+        .with_label("service", "api");
+
+        assert_eq!(request.start_timestamp(), Some(1_000));
+        assert_eq!(request.timestamp(), 5_000);
+        assert_eq!(request.temporality(), Some(AggregationTemporality::Delta));
+
+        let snapshot = MetricSnapshot::from(&request);
+        assert_eq!(snapshot.start_timestamp, Some(1_000));
+        assert_eq!(snapshot.timestamp, 5_000);
+        assert_eq!(snapshot.temporality, Some(AggregationTemporality::Delta));
+    }
+
+    #[test]
+    fn test_data_point_rejects_time_before_start() {
+        let result = MetricRequest::data_point(
+            "otlp_requests",
+            MetricType::Counter,
+            MetricValue::Single(1.0),
+            5_000,
+            1_000,
+            AggregationTemporality::Cumulative,
+        );
+        assert!(result.is_err());
+    }
+
+    fn snapshot_at(metric_type: MetricType, value: MetricValue, timestamp: u64) -> MetricSnapshot {
+        let mut snapshot = MetricSnapshot::new(
+            "merged_metric".to_string(),
+            metric_type,
+            value,
+            [("shard".to_string(), "all".to_string())].into(),
+        );
+        snapshot.timestamp = timestamp;
+        snapshot
+    }
+
+    fn histogram_value(sum: f64, counts: [u64; 2]) -> MetricValue {
+        MetricValue::Histogram {
+            sum,
+            count: counts[1],
+            buckets: vec![
+                HistogramBucket {
+                    upper_bound: 1.0,
+                    count: counts[0],
+                },
+                HistogramBucket {
+                    upper_bound: f64::INFINITY,
+                    count: counts[1],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_merge_counters_sums_values() {
+        let a = snapshot_at(MetricType::Counter, MetricValue::Single(3.0), 10);
+        let b = snapshot_at(MetricType::Counter, MetricValue::Single(4.0), 20);
+
+        let merged = a.merge(&b).unwrap();
+        assert_eq!(merged.value, MetricValue::Single(7.0));
+        assert_eq!(merged.timestamp, 20);
+    }
+
+    #[test]
+    fn test_merge_gauges_latest_wins() {
+        let newer = snapshot_at(MetricType::Gauge, MetricValue::Single(50.0), 30);
+        let older = snapshot_at(MetricType::Gauge, MetricValue::Single(10.0), 20);
+
+        assert_eq!(
+            newer.merge(&older).unwrap().value,
+            MetricValue::Single(50.0)
+        );
+        assert_eq!(
+            older.merge(&newer).unwrap().value,
+            MetricValue::Single(50.0)
+        );
+        assert_eq!(older.merge(&newer).unwrap().timestamp, 30);
+    }
+
+    #[test]
+    fn test_merge_histograms_bucket_wise() {
+        let a = snapshot_at(MetricType::Histogram, histogram_value(2.5, [2, 3]), 10);
+        let b = snapshot_at(MetricType::Histogram, histogram_value(4.0, [1, 4]), 20);
+
+        let merged = a.merge(&b).unwrap();
+        assert_eq!(merged.value, histogram_value(6.5, [3, 7]));
+    }
+
+    #[test]
+    fn test_merge_rejects_mismatched_series() {
+        let counter = snapshot_at(MetricType::Counter, MetricValue::Single(1.0), 10);
+
+        let mut renamed = counter.clone();
+        renamed.name = "other_metric".to_string();
+        assert!(counter.merge(&renamed).is_err());
+
+        let gauge = snapshot_at(MetricType::Gauge, MetricValue::Single(1.0), 10);
+        assert!(counter.merge(&gauge).is_err());
+
+        let mut relabeled = counter.clone();
+        relabeled
+            .labels
+            .insert("shard".to_string(), "1".to_string());
+        assert!(counter.merge(&relabeled).is_err());
+    }
+
+    #[test]
+    fn test_merge_rejects_mismatched_buckets() {
+        let a = snapshot_at(MetricType::Histogram, histogram_value(1.0, [1, 1]), 10);
+        let mut b = a.clone();
+        if let MetricValue::Histogram { buckets, .. } = &mut b.value {
+            buckets[0].upper_bound = 2.0;
+        }
+
+        assert!(a.merge(&b).is_err());
+    }
+
     #[test]
     fn test_timer_guard_creation() {
         let labels = HashMap::new();
@@ -585,4 +2847,153 @@ mod tests {
         assert_eq!(metrics[0].metric_type(), &MetricType::Timer);
         assert!(metrics[0].value() > 0.0);
     }
+
+    #[tokio::test]
+    async fn test_timer_guard_async_recording() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let sink = recorded.clone();
+        let timer = TimerGuard::new_async("db_query".to_string(), Labels::new(), move |request| {
+            let sink = sink.clone();
+            async move {
+                sink.lock().unwrap().push(request);
+                Ok(())
+            }
+        });
+        let outcome = timer.outcome().unwrap();
+        assert!(!outcome.is_finished());
+
+        drop(timer);
+        outcome.finished().await;
+        assert!(outcome.take_error().is_none());
+        assert_eq!(recorded.lock().unwrap()[0].name(), "db_query");
+    }
+
+    #[tokio::test]
+    async fn test_timer_guard_async_error_observable_after_drop() {
+        let timer = TimerGuard::new_async(
+            "db_query".to_string(),
+            Labels::new(),
+            |request| async move {
+                Err(metrics_recording_error(
+                    request.name(),
+                    "backend unavailable",
+                ))
+            },
+        );
+        let outcome = timer.outcome().unwrap();
+
+        drop(timer);
+        outcome.finished().await;
+        let error = outcome.take_error().unwrap();
+        assert!(error.to_string().contains("backend unavailable"));
+        assert!(outcome.take_error().is_none());
+    }
+
+    #[test]
+    fn test_timer_guard_async_without_runtime() {
+        let timer =
+            TimerGuard::new_async("db_query".to_string(), Labels::new(), |_| async { Ok(()) });
+        let outcome = timer.outcome().unwrap();
+
+        drop(timer);
+        assert!(outcome.is_finished());
+        assert!(outcome.take_error().is_some());
+        assert!(TimerGuard::new("sync".to_string(), Labels::new(), |_| {})
+            .outcome()
+            .is_none());
+    }
+
+    #[test]
+    fn test_timer_guard_laps() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let sink = recorded.clone();
+        let labels: Labels = [("route".to_string(), "/checkout".to_string())].into();
+
+        let timer = TimerGuard::new("checkout".to_string(), labels, move |request| {
+            sink.lock().unwrap().push(request);
+        });
+        std::thread::sleep(Duration::from_millis(2));
+        let first = timer.lap("validated");
+        std::thread::sleep(Duration::from_millis(2));
+        let second = timer.lap("charged");
+        std::thread::sleep(Duration::from_millis(2));
+        let split = timer.split("shipped");
+        drop(timer);
+
+        let recorded = recorded.lock().unwrap();
+        let names: Vec<&str> = recorded.iter().map(|r| r.name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "checkout_validated",
+                "checkout_charged",
+                "checkout_shipped",
+                "checkout"
+            ]
+        );
+        assert!(recorded.iter().all(|r| r.labels()["route"] == "/checkout"));
+
+        // Laps are cumulative; the split covers only the last stage
+        assert!(first < second);
+        assert!(recorded[0].value() < recorded[1].value());
+        assert!(recorded[1].value() < recorded[3].value());
+        assert!(split.as_secs_f64() < recorded[3].value() - recorded[1].value() + 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_timer_guard_cancel() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let sink = recorded.clone();
+        let timer = TimerGuard::new("request".to_string(), Labels::new(), move |request| {
+            sink.lock().unwrap().push(request);
+        });
+        timer.cancel();
+
+        let async_timer = TimerGuard::new_async("request".to_string(), Labels::new(), |_| async {
+            Err(metrics_recording_error("request", "must not be called"))
+        });
+        let outcome = async_timer.outcome().unwrap();
+        async_timer.cancel();
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        outcome.finished().await;
+        assert!(recorded.lock().unwrap().is_empty());
+        assert!(outcome.take_error().is_none());
+    }
+
+    #[test]
+    fn test_timer_guard_add_label() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let sink = recorded.clone();
+        let labels: Labels = [("route".to_string(), "/orders".to_string())].into();
+
+        let mut timer = TimerGuard::new("request".to_string(), labels, move |request| {
+            sink.lock().unwrap().push(request);
+        });
+        // The outcome is only known once the operation fails
+        timer.add_label("status", "error").unwrap();
+        assert!(timer.add_label("bad key", "x").is_err());
+        assert!(timer.add_label("__reserved", "x").is_err());
+        drop(timer);
+
+        let recorded = recorded.lock().unwrap();
+        let labels = recorded[0].labels();
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels["status"], "error");
+        assert_eq!(labels["route"], "/orders");
+    }
+
+    #[test]
+    fn test_timer_guard_add_label_custom_limits() {
+        let labels: Labels = (0..32)
+            .map(|i| (format!("k{i}"), "v".to_string()))
+            .collect();
+
+        let mut timer = TimerGuard::new("request".to_string(), labels.clone(), |_| {});
+        assert!(timer.add_label("status", "ok").is_err());
+
+        let mut timer = TimerGuard::new("request".to_string(), labels, |_| {})
+            .with_validation_limits(crate::ValidationLimits::default().with_max_labels(64));
+        timer.add_label("status", "ok").unwrap();
+    }
 }
@@ -10,11 +10,120 @@ use regex::Regex;
 use std::collections::HashMap;
 
 // Maximum allowed lengths for various metric components
-const MAX_METRIC_NAME_LENGTH: usize = 255;
+pub(crate) const MAX_METRIC_NAME_LENGTH: usize = 255;
 const MAX_LABEL_KEY_LENGTH: usize = 128;
 const MAX_LABEL_VALUE_LENGTH: usize = 1024;
 const MAX_LABELS_COUNT: usize = 32;
 
+/// Size limits enforced by validation
+///
+/// Backends accept different sizes: OpenTelemetry setups often allow more
+/// labels, StatsD sinks fewer. The `_with` validation functions take limits
+/// explicitly; the others use `ValidationLimits::default()`.
+///
+/// # Example
+/// ```rust
+/// use tyl_metrics_port::{validate_label_value_with, LabelValueCharset, ValidationLimits};
+///
+/// let statsd = ValidationLimits::default().with_max_label_value_len(64);
+/// assert!(validate_label_value_with(&"x".repeat(100), LabelValueCharset::Lenient, &statsd).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ValidationLimits {
+    /// Maximum number of labels per metric (default 32)
+    pub max_labels: usize,
+
+    /// Maximum label key length in bytes (default 128)
+    pub max_label_key_len: usize,
+
+    /// Maximum label value length in bytes (default 1024)
+    pub max_label_value_len: usize,
+
+    /// Maximum metric name length in bytes (default 255)
+    pub max_name_len: usize,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self {
+            max_labels: MAX_LABELS_COUNT,
+            max_label_key_len: MAX_LABEL_KEY_LENGTH,
+            max_label_value_len: MAX_LABEL_VALUE_LENGTH,
+            max_name_len: MAX_METRIC_NAME_LENGTH,
+        }
+    }
+}
+
+impl ValidationLimits {
+    /// Set the maximum number of labels per metric
+    pub fn with_max_labels(mut self, max: usize) -> Self {
+        self.max_labels = max;
+        self
+    }
+
+    /// Set the maximum label key length
+    pub fn with_max_label_key_len(mut self, max: usize) -> Self {
+        self.max_label_key_len = max;
+        self
+    }
+
+    /// Set the maximum label value length
+    pub fn with_max_label_value_len(mut self, max: usize) -> Self {
+        self.max_label_value_len = max;
+        self
+    }
+
+    /// Set the maximum metric name length
+    pub fn with_max_name_len(mut self, max: usize) -> Self {
+        self.max_name_len = max;
+        self
+    }
+}
+
+/// Identifier rules of a metrics backend
+///
+/// Backends disagree on which characters a metric name may contain, so the
+/// same port validates names against the convention of the adapter in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NamingConvention {
+    /// Prometheus: `[a-zA-Z_:][a-zA-Z0-9_:]*`
+    #[default]
+    Prometheus,
+
+    /// OpenTelemetry instrument names: `[a-zA-Z][a-zA-Z0-9_.-]*`
+    OpenTelemetry,
+
+    /// StatsD buckets: `[a-zA-Z_][a-zA-Z0-9_.]*`
+    StatsD,
+}
+
+impl NamingConvention {
+    /// The regex a metric name must match under this convention
+    fn name_regex(self) -> &'static Regex {
+        lazy_static! {
+            static ref PROMETHEUS_NAME_REGEX: Regex =
+                Regex::new(r"^[a-zA-Z_:][a-zA-Z0-9_:]*$").unwrap();
+            static ref OTEL_NAME_REGEX: Regex = Regex::new(r"^[a-zA-Z][a-zA-Z0-9_.\-]*$").unwrap();
+            static ref STATSD_NAME_REGEX: Regex = Regex::new(r"^[a-zA-Z_][a-zA-Z0-9_.]*$").unwrap();
+        }
+
+        match self {
+            NamingConvention::Prometheus => &PROMETHEUS_NAME_REGEX,
+            NamingConvention::OpenTelemetry => &OTEL_NAME_REGEX,
+            NamingConvention::StatsD => &STATSD_NAME_REGEX,
+        }
+    }
+
+    /// Human-readable form of the name pattern for error messages
+    fn name_pattern(self) -> &'static str {
+        match self {
+            NamingConvention::Prometheus => "[a-zA-Z_:][a-zA-Z0-9_:]*",
+            NamingConvention::OpenTelemetry => "[a-zA-Z][a-zA-Z0-9_.-]*",
+            NamingConvention::StatsD => "[a-zA-Z_][a-zA-Z0-9_.]*",
+        }
+    }
+}
+
 /// Validate a metric name
 ///
 /// Ensures metric names follow standard conventions:
@@ -23,6 +132,9 @@ const MAX_LABELS_COUNT: usize = 32;
 /// - Can contain letters, numbers, underscores, and colons
 /// - Must be within reasonable length limits
 ///
+/// This validates against `NamingConvention::Prometheus`; use
+/// `validate_metric_name_for` for other backends.
+///
 /// # Examples
 /// ```rust
 /// use tyl_metrics_port::validate_metric_name;
@@ -31,25 +143,49 @@ const MAX_LABELS_COUNT: usize = 32;
 /// assert!(validate_metric_name("").is_err());
 /// ```
 pub fn validate_metric_name(name: &str) -> Result<()> {
+    validate_metric_name_for(name, NamingConvention::Prometheus)
+}
+
+/// Validate a metric name against a backend's naming convention
+///
+/// Applies the same emptiness and length checks as `validate_metric_name`,
+/// then matches the name against the convention's identifier rules.
+///
+/// # Examples
+/// ```rust
+/// use tyl_metrics_port::{validate_metric_name_for, NamingConvention};
+///
+/// assert!(validate_metric_name_for("http.server.duration", NamingConvention::OpenTelemetry).is_ok());
+/// assert!(validate_metric_name_for("http.server.duration", NamingConvention::Prometheus).is_err());
+/// ```
+pub fn validate_metric_name_for(name: &str, convention: NamingConvention) -> Result<()> {
+    validate_metric_name_with(name, convention, &ValidationLimits::default())
+}
+
+/// Validate a metric name against a naming convention and size limits
+pub fn validate_metric_name_with(
+    name: &str,
+    convention: NamingConvention,
+    limits: &ValidationLimits,
+) -> Result<()> {
     if name.is_empty() {
         return Err(metrics_error("metric_name", "Metric name cannot be empty"));
     }
 
-    if name.len() > MAX_METRIC_NAME_LENGTH {
+    if name.len() > limits.max_name_len {
         return Err(metrics_error(
             "metric_name",
-            format!("Metric name too long (max {MAX_METRIC_NAME_LENGTH} chars)"),
+            format!("Metric name too long (max {} chars)", limits.max_name_len),
         ));
     }
 
-    lazy_static! {
-        static ref METRIC_NAME_REGEX: Regex = Regex::new(r"^[a-zA-Z_:][a-zA-Z0-9_:]*$").unwrap();
-    }
-
-    if !METRIC_NAME_REGEX.is_match(name) {
+    if !convention.name_regex().is_match(name) {
         return Err(metrics_error(
             "metric_name",
-            "Invalid metric name format (must match [a-zA-Z_:][a-zA-Z0-9_:]*)",
+            format!(
+                "Invalid metric name format (must match {})",
+                convention.name_pattern()
+            ),
         ));
     }
 
@@ -65,14 +201,22 @@ pub fn validate_metric_name(name: &str) -> Result<()> {
 /// - Can contain letters, numbers, and underscores
 /// - Must be within reasonable length limits
 pub fn validate_label_key(key: &str) -> Result<()> {
+    validate_label_key_with(key, &ValidationLimits::default())
+}
+
+/// Validate a label key against size limits
+pub fn validate_label_key_with(key: &str, limits: &ValidationLimits) -> Result<()> {
     if key.is_empty() {
         return Err(metrics_error("label_key", "Label key cannot be empty"));
     }
 
-    if key.len() > MAX_LABEL_KEY_LENGTH {
+    if key.len() > limits.max_label_key_len {
         return Err(metrics_error(
             "label_key",
-            format!("Label key too long (max {MAX_LABEL_KEY_LENGTH} chars)"),
+            format!(
+                "Label key too long (max {} chars)",
+                limits.max_label_key_len
+            ),
         ));
     }
 
@@ -97,24 +241,99 @@ pub fn validate_label_key(key: &str) -> Result<()> {
     Ok(())
 }
 
+/// Characters allowed in label values
+///
+/// Line-based exposition formats break on values containing newlines, and
+/// some sinks reject other control characters too. The lenient default only
+/// forbids null bytes, for backward compatibility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LabelValueCharset {
+    /// Any UTF-8 except null bytes
+    #[default]
+    Lenient,
+
+    /// No ASCII control characters (`\n`, `\t`, `\r`, DEL, ...)
+    NoControl,
+
+    /// No control characters at all, nor invisible Unicode formatting
+    /// characters (zero-width spaces, bidi overrides, line separators)
+    Printable,
+}
+
+impl LabelValueCharset {
+    /// Whether a character is rejected under this charset
+    fn rejects(self, c: char) -> bool {
+        match self {
+            LabelValueCharset::Lenient => c == '\0',
+            LabelValueCharset::NoControl => c.is_ascii_control(),
+            LabelValueCharset::Printable => {
+                c.is_control()
+                    || matches!(
+                        c,
+                        '\u{200B}'..='\u{200F}'
+                            | '\u{2028}'..='\u{202E}'
+                            | '\u{2060}'..='\u{2069}'
+                            | '\u{FEFF}'
+                    )
+            }
+        }
+    }
+}
+
 /// Validate a label value
 ///
 /// Ensures label values meet basic quality requirements:
 /// - Can be empty
 /// - Must not contain null bytes
 /// - Must be within reasonable length limits
+///
+/// Use `validate_label_value_for` to also reject control characters.
 pub fn validate_label_value(value: &str) -> Result<()> {
-    if value.len() > MAX_LABEL_VALUE_LENGTH {
+    validate_label_value_for(value, LabelValueCharset::Lenient)
+}
+
+/// Validate a label value against a character set
+///
+/// Applies the same length check as `validate_label_value`, then rejects
+/// any character the charset forbids.
+///
+/// # Examples
+/// ```rust
+/// use tyl_metrics_port::{validate_label_value_for, LabelValueCharset};
+///
+/// assert!(validate_label_value_for("line\nbreak", LabelValueCharset::Lenient).is_ok());
+/// assert!(validate_label_value_for("line\nbreak", LabelValueCharset::NoControl).is_err());
+/// ```
+pub fn validate_label_value_for(value: &str, charset: LabelValueCharset) -> Result<()> {
+    validate_label_value_with(value, charset, &ValidationLimits::default())
+}
+
+/// Validate a label value against a character set and size limits
+pub fn validate_label_value_with(
+    value: &str,
+    charset: LabelValueCharset,
+    limits: &ValidationLimits,
+) -> Result<()> {
+    if value.len() > limits.max_label_value_len {
         return Err(metrics_error(
             "label_value",
-            format!("Label value too long (max {MAX_LABEL_VALUE_LENGTH} chars)"),
+            format!(
+                "Label value too long (max {} chars)",
+                limits.max_label_value_len
+            ),
         ));
     }
 
-    if value.contains('\0') {
+    if let Some(c) = value.chars().find(|c| charset.rejects(*c)) {
         return Err(metrics_error(
             "label_value",
-            "Label values cannot contain null bytes",
+            match charset {
+                LabelValueCharset::Lenient => "Label values cannot contain null bytes".to_string(),
+                _ => format!(
+                    "Label values cannot contain {:?} ({:?} charset)",
+                    c, charset
+                ),
+            },
         ));
     }
 
@@ -127,18 +346,97 @@ pub fn validate_label_value(value: &str) -> Result<()> {
 /// - Total number of labels within limits
 /// - All keys and values individually valid
 pub fn validate_labels(labels: &HashMap<String, String>) -> Result<()> {
-    if labels.len() > MAX_LABELS_COUNT {
+    validate_labels_with(labels, &ValidationLimits::default())
+}
+
+/// Validate a complete set of labels against size limits
+///
+/// Labels are checked in key order, so the error reported for a set with
+/// several invalid labels is always the same one.
+///
+/// # Examples
+/// ```rust
+/// use std::collections::HashMap;
+/// use tyl_metrics_port::{validate_labels, validate_labels_with, ValidationLimits};
+///
+/// let labels: HashMap<String, String> = (0..40).map(|i| (format!("k{i}"), "v".to_string())).collect();
+/// assert!(validate_labels(&labels).is_err());
+/// assert!(validate_labels_with(&labels, &ValidationLimits::default().with_max_labels(64)).is_ok());
+/// ```
+pub fn validate_labels_with(
+    labels: &HashMap<String, String>,
+    limits: &ValidationLimits,
+) -> Result<()> {
+    label_checks(labels, limits).collect()
+}
+
+/// Results of every label-set check: the count, then each key and value
+///
+/// Labels are checked in key order, so the first error doesn't depend on
+/// `HashMap` iteration order.
+fn label_checks<'a>(
+    labels: &'a HashMap<String, String>,
+    limits: &'a ValidationLimits,
+) -> impl Iterator<Item = Result<()>> + 'a {
+    let count = (labels.len() > limits.max_labels).then(|| {
+        Err(metrics_error(
+            "labels",
+            format!("Too many labels (max {})", limits.max_labels),
+        ))
+    });
+
+    let mut sorted: Vec<(&String, &String)> = labels.iter().collect();
+    sorted.sort();
+    count
+        .into_iter()
+        .chain(sorted.into_iter().flat_map(move |(key, value)| {
+            [
+                validate_label_key_with(key, limits),
+                validate_label_value_with(value, LabelValueCharset::Lenient, limits),
+            ]
+        }))
+}
+
+/// Merge `extra` labels under a call site's, within `limits.max_labels`
+///
+/// Call-site keys win and don't count twice. `source` names the extra labels
+/// in the error, e.g. "default" or "trace context". Nothing is merged on
+/// error.
+pub(crate) fn merge_labels_with<I, K, V>(
+    labels: &mut Labels,
+    extra: I,
+    source: &str,
+    limits: &ValidationLimits,
+) -> Result<()>
+where
+    I: IntoIterator<Item = (K, V)> + Clone,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let added = extra
+        .clone()
+        .into_iter()
+        .filter(|(key, _)| !labels.contains_key(key.as_ref()))
+        .count();
+
+    if labels.len() + added > limits.max_labels {
         return Err(metrics_error(
             "labels",
-            format!("Too many labels (max {MAX_LABELS_COUNT})"),
+            format!(
+                "Adding {} {} labels to {} call-site labels exceeds the limit of {}",
+                added,
+                source,
+                labels.len(),
+                limits.max_labels
+            ),
         ));
     }
 
-    for (key, value) in labels {
-        validate_label_key(key)?;
-        validate_label_value(value)?;
+    for (key, value) in extra {
+        labels
+            .entry(key.as_ref().to_string())
+            .or_insert_with(|| value.as_ref().to_string());
     }
-
     Ok(())
 }
 
@@ -176,10 +474,289 @@ pub fn validate_counter_value(value: f64) -> Result<()> {
     Ok(())
 }
 
+/// Validate a complete metric request
+///
+/// Runs every check an adapter applies before recording: the metric name,
+/// the label set, and the value (counters must also be non-negative).
+/// Gauges can't have delta temporality, since a gauge has no interval to
+/// accumulate over.
+///
+/// # Examples
+/// ```rust
+/// use tyl_metrics_port::{validate_metric_request, MetricRequest};
+///
+/// assert!(validate_metric_request(&MetricRequest::counter("requests_total", 1.0)).is_ok());
+/// assert!(validate_metric_request(&MetricRequest::counter("requests_total", -1.0)).is_err());
+/// ```
+pub fn validate_metric_request(request: &MetricRequest) -> Result<()> {
+    validate_metric_request_with(request, &ValidationLimits::default())
+}
+
+/// Validate a complete metric request against size limits
+///
+/// Runs the same checks as `validate_metric_request`, with the name and
+/// labels checked against `limits`.
+pub fn validate_metric_request_with(
+    request: &MetricRequest,
+    limits: &ValidationLimits,
+) -> Result<()> {
+    let mut found = Violations::first_only();
+    collect_violations(request, limits, &mut found);
+    match found.errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Every validation error in a request, in check order
+///
+/// Runs the checks of `validate_metric_request_with` without stopping at the
+/// first failure.
+pub(crate) fn metric_request_violations(
+    request: &MetricRequest,
+    limits: &ValidationLimits,
+) -> Vec<TylError> {
+    let mut found = Violations {
+        errors: Vec::new(),
+        all: true,
+    };
+    collect_violations(request, limits, &mut found);
+    found.errors
+}
+
+/// Validation errors found so far, and whether to keep looking
+struct Violations {
+    errors: Vec<TylError>,
+    all: bool,
+}
+
+impl Violations {
+    fn first_only() -> Self {
+        Self {
+            errors: Vec::new(),
+            all: false,
+        }
+    }
+
+    /// Record a check's failure; `None` means stop checking
+    fn check(&mut self, result: Result<()>) -> Option<()> {
+        if let Err(error) = result {
+            self.errors.push(error);
+            if !self.all {
+                return None;
+            }
+        }
+        Some(())
+    }
+}
+
+/// Run every request check, stopping early unless `found.all` is set
+fn collect_violations(
+    request: &MetricRequest,
+    limits: &ValidationLimits,
+    found: &mut Violations,
+) -> Option<()> {
+    found.check(validate_metric_name_with(
+        request.name(),
+        NamingConvention::Prometheus,
+        limits,
+    ))?;
+
+    for result in label_checks(request.labels(), limits) {
+        found.check(result)?;
+    }
+
+    if request.metric_type() == &MetricType::Gauge
+        && request.temporality() == Some(AggregationTemporality::Delta)
+    {
+        found.check(Err(metrics_error(
+            "temporality",
+            "Gauges cannot have delta temporality",
+        )))?;
+    }
+
+    if request.metric_type() == &MetricType::Info {
+        if request.labels().is_empty() {
+            found.check(Err(metrics_error(
+                "labels",
+                format!(
+                    "Info metric {} must have at least one label",
+                    request.name()
+                ),
+            )))?;
+        }
+        if request.value() != 1.0 {
+            found.check(Err(metrics_error(
+                "value",
+                format!("Info metric {} must have value 1", request.name()),
+            )))?;
+        }
+    }
+
+    if request.observation_batch().is_some_and(<[f64]>::is_empty) {
+        // The folded value of an empty batch is NaN; report the cause instead
+        return found.check(Err(metrics_error(
+            "value",
+            format!("Histogram batch for {} has no observations", request.name()),
+        )));
+    }
+    for (index, value) in request.observations().iter().enumerate() {
+        if !value.is_finite() {
+            found.check(Err(metrics_error(
+                "value",
+                format!(
+                    "Observation {} of {} is {}; histogram observations must be finite",
+                    index,
+                    request.name(),
+                    value
+                ),
+            )))?;
+        }
+    }
+
+    found.check(match request.metric_type() {
+        MetricType::Counter => validate_counter_value(request.value()),
+        _ => validate_metric_value(request.value()),
+    })
+}
+
+/// Validate histogram bucket upper bounds
+///
+/// Bounds must be non-empty and not NaN; negative bounds and `+Inf` are
+/// allowed, so histograms can hold negative observations such as clock skew
+/// corrections. They don't need to be sorted: the validated bounds are
+/// returned sorted ascending with duplicates removed, ready to use as bucket
+/// boundaries.
+///
+/// # Examples
+/// ```rust
+/// use tyl_metrics_port::validate_histogram_buckets;
+///
+/// assert_eq!(validate_histogram_buckets(&[1.0, 0.5, 1.0]).unwrap(), vec![0.5, 1.0]);
+/// assert!(validate_histogram_buckets(&[]).is_err());
+/// assert_eq!(validate_histogram_buckets(&[1.0, -1.0]).unwrap(), vec![-1.0, 1.0]);
+/// assert!(validate_histogram_buckets(&[f64::NAN]).is_err());
+/// ```
+pub fn validate_histogram_buckets(buckets: &[f64]) -> Result<Vec<f64>> {
+    if buckets.is_empty() {
+        return Err(metrics_error(
+            "histogram_buckets",
+            "Histogram must have at least one bucket",
+        ));
+    }
+
+    if buckets.iter().any(|bound| bound.is_nan()) {
+        return Err(metrics_error(
+            "histogram_buckets",
+            "Histogram bucket bounds cannot be NaN",
+        ));
+    }
+
+    let mut bounds = buckets.to_vec();
+    bounds.sort_by(f64::total_cmp);
+    bounds.dedup();
+    Ok(bounds)
+}
+
+/// Find the histogram bucket an observation falls into
+///
+/// `buckets` are upper bounds sorted ascending, as returned by
+/// `validate_histogram_buckets` or `HistogramBuckets::custom`. Returns the
+/// index of the first bound `>= value`, so a value equal to a bound lands in
+/// that bucket (upper bounds are inclusive). A value above every bound
+/// returns `buckets.len()`, the implicit `+Inf` bucket; if the bounds
+/// already end in `+Inf`, that bucket's index is returned instead. NaN
+/// falls in no finite bucket and is treated the same way, landing in the
+/// catch-all last bucket.
+///
+/// # Examples
+/// ```rust
+/// use tyl_metrics_port::bucket_index;
+///
+/// let bounds = [0.1, 0.5, 1.0];
+/// assert_eq!(bucket_index(0.05, &bounds), 0);
+/// assert_eq!(bucket_index(0.5, &bounds), 1);
+/// assert_eq!(bucket_index(2.0, &bounds), 3);
+/// assert_eq!(bucket_index(2.0, &[0.1, 0.5, 1.0, f64::INFINITY]), 3);
+/// ```
+pub fn bucket_index(value: f64, buckets: &[f64]) -> usize {
+    if value.is_nan() {
+        return match buckets.last() {
+            Some(last) if *last == f64::INFINITY => buckets.len() - 1,
+            _ => buckets.len(),
+        };
+    }
+
+    buckets.partition_point(|bound| *bound < value)
+}
+
+/// Normalize a string to Unicode Normalization Form C (NFC)
+///
+/// Canonically equivalent strings, such as `café` with a precomposed `é` and
+/// `café` spelled as `e` plus a combining acute accent, become byte-identical,
+/// so they label the same series. Opt-in: nothing in the crate normalizes
+/// unless asked to, e.g. through `UnicodeNormalizingMetricsAdapter`.
+///
+/// Only available with the `unicode` feature.
+///
+/// # Examples
+/// ```rust
+/// use tyl_metrics_port::normalize_unicode;
+///
+/// assert_eq!(normalize_unicode("cafe\u{301}"), "caf\u{e9}");
+/// assert_eq!(normalize_unicode("plain"), "plain");
+/// ```
+#[cfg(feature = "unicode")]
+pub fn normalize_unicode(s: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+    s.nfc().collect()
+}
+
+/// Stable 64-bit key for a series (name plus labels)
+///
+/// Unlike hashing with the standard library's `RandomState`, the result is
+/// the same in every process, run, and machine, so it can route a series to
+/// the same shard everywhere. It is FNV-1a over the name and the label pairs
+/// sorted by key, each field prefixed with its byte length so that
+/// `("ab", "c")` and `("a", "bc")` hash differently. Label insertion order
+/// doesn't matter. Not suitable where hash flooding is a concern.
+///
+/// # Examples
+/// ```rust
+/// use tyl_metrics_port::{series_hash, Labels};
+///
+/// let a: Labels = [("method".into(), "GET".into()), ("status".into(), "200".into())].into();
+/// let b: Labels = [("status".into(), "200".into()), ("method".into(), "GET".into())].into();
+/// assert_eq!(series_hash("http_requests_total", &a), series_hash("http_requests_total", &b));
+/// ```
+pub fn series_hash(name: &str, labels: &Labels) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    let mut write = |field: &str| {
+        let length = (field.len() as u64).to_le_bytes();
+        for byte in length.iter().chain(field.as_bytes()) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    };
+
+    write(name);
+    let mut pairs: Vec<(&String, &String)> = labels.iter().collect();
+    pairs.sort();
+    for (key, value) in pairs {
+        write(key);
+        write(value);
+    }
+    hash
+}
+
 /// Format labels as a string for logging/debugging
 ///
 /// Creates a consistent string representation of labels for debugging output.
-/// The format is: "key1=value1,key2=value2"
+/// The format is: "key1=value1,key2=value2", or "{}" without labels. Use
+/// `format_labels_with` for other layouts.
 ///
 /// # Examples
 /// ```rust
@@ -192,18 +769,103 @@ pub fn validate_counter_value(value: f64) -> Result<()> {
 /// // Result: "method=GET" or similar
 /// ```
 pub fn format_labels(labels: &HashMap<String, String>) -> String {
+    format_labels_with(labels, &FormatOptions::default())
+}
+
+/// Separators and wrapping used by `format_labels_with`
+///
+/// The default matches `format_labels`: `k=v` pairs joined by `,`, no
+/// wrapping, and `{}` when there are no labels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Placed between a key and its value
+    pub kv_sep: String,
+
+    /// Placed between pairs
+    pub pair_sep: String,
+
+    /// Prefix and suffix around the pairs, if any
+    pub wrap: Option<(String, String)>,
+
+    /// Rendered instead when there are no labels
+    pub empty: String,
+}
+
+impl FormatOptions {
+    /// Create options with the given separators, no wrapping, and an empty string for no labels
+    pub fn new(kv_sep: impl Into<String>, pair_sep: impl Into<String>) -> Self {
+        Self {
+            kv_sep: kv_sep.into(),
+            pair_sep: pair_sep.into(),
+            wrap: None,
+            empty: String::new(),
+        }
+    }
+
+    /// Wrap the pairs in `prefix` and `suffix`
+    pub fn with_wrap(mut self, prefix: impl Into<String>, suffix: impl Into<String>) -> Self {
+        self.wrap = Some((prefix.into(), suffix.into()));
+        self
+    }
+
+    /// Set what is rendered when there are no labels
+    pub fn with_empty(mut self, empty: impl Into<String>) -> Self {
+        self.empty = empty.into();
+        self
+    }
+
+    /// StatsD/DogStatsD tags: `#k:v,k:v`
+    pub fn statsd() -> Self {
+        Self::new(":", ",").with_wrap("#", "")
+    }
+
+    /// logfmt: `k=v k=v`
+    pub fn logfmt() -> Self {
+        Self::new("=", " ")
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new("=", ",").with_empty("{}")
+    }
+}
+
+/// Format labels with custom separators, sorted by key
+///
+/// # Examples
+/// ```rust
+/// use std::collections::HashMap;
+/// use tyl_metrics_port::{format_labels_with, FormatOptions};
+///
+/// let labels = HashMap::from([
+///     ("status".to_string(), "200".to_string()),
+///     ("method".to_string(), "GET".to_string()),
+/// ]);
+/// assert_eq!(format_labels_with(&labels, &FormatOptions::statsd()), "#method:GET,status:200");
+/// assert_eq!(
+///     format_labels_with(&labels, &FormatOptions::new(": ", "; ").with_wrap("[", "]")),
+///     "[method: GET; status: 200]"
+/// );
+/// ```
+pub fn format_labels_with(labels: &HashMap<String, String>, options: &FormatOptions) -> String {
     if labels.is_empty() {
-        return "{}".to_string();
+        return options.empty.clone();
     }
 
     let mut pairs: Vec<(&String, &String)> = labels.iter().collect();
     pairs.sort_by_key(|(k, _)| *k); // Sort by key for consistent output
 
-    pairs
+    let joined = pairs
         .into_iter()
-        .map(|(k, v)| format!("{k}={v}"))
+        .map(|(k, v)| format!("{k}{}{v}", options.kv_sep))
         .collect::<Vec<_>>()
-        .join(",")
+        .join(&options.pair_sep);
+
+    match &options.wrap {
+        Some((prefix, suffix)) => format!("{prefix}{joined}{suffix}"),
+        None => joined,
+    }
 }
 
 /// Normalize a metric name for consistent storage and comparison
@@ -234,6 +896,128 @@ pub fn normalize_metric_name(name: &str) -> String {
     UNDERSCORE_REGEX.replace_all(&normalized, "_").to_string()
 }
 
+/// Sanitize a metric name so it always passes validation
+///
+/// Third-party names often contain spaces, dashes, or other characters that
+/// `validate_metric_name` rejects. Instead of erroring, this coerces them:
+/// - Replaces any character outside `[a-zA-Z0-9_:]` with `_`
+/// - Collapses underscores produced by replacements into a single `_`
+/// - Prefixes `_` if the name starts with a digit (or is empty)
+/// - Truncates to the maximum metric name length
+///
+/// Sanitizing is idempotent: an already-valid name is returned unchanged.
+///
+/// # Arguments
+/// * `name` - The metric name to sanitize
+///
+/// # Returns
+/// * `String` - A name that passes `validate_metric_name`
+///
+/// # Examples
+/// ```rust
+/// use tyl_metrics_port::{sanitize_metric_name, validate_metric_name};
+///
+/// assert_eq!(sanitize_metric_name("http requests-total"), "http_requests_total");
+/// assert_eq!(sanitize_metric_name("5xx_errors"), "_5xx_errors");
+/// assert!(validate_metric_name(&sanitize_metric_name("🔥 hot path")).is_ok());
+/// ```
+pub fn sanitize_metric_name(name: &str) -> String {
+    let mut sanitized = String::with_capacity(name.len());
+    let mut after_replacement = false;
+
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || c == ':' {
+            sanitized.push(c);
+            after_replacement = false;
+        } else if c == '_' {
+            if !after_replacement {
+                sanitized.push(c);
+            }
+        } else if !sanitized.ends_with('_') {
+            sanitized.push('_');
+            after_replacement = true;
+        }
+    }
+
+    if sanitized.is_empty() || sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+
+    // Only ASCII remains, so truncating by bytes cannot split a character
+    sanitized.truncate(MAX_METRIC_NAME_LENGTH);
+    sanitized
+}
+
+/// Normalize a label key to snake_case so it always passes validation
+///
+/// Teams spell the same label as `statusCode`, `status-code`, or
+/// `status_code`; normalizing maps all of them onto one series key:
+/// - Splits camelCase words (`statusCode`, `HTTPStatus`) with `_`
+/// - Replaces `-`, `.`, and whitespace with `_`
+/// - Drops any other character outside `[a-zA-Z0-9_]`
+/// - Lowercases and collapses repeated underscores (so the key never starts
+///   with the reserved `__`), trimming trailing ones
+/// - Prefixes `_` if the key starts with a digit (or is empty)
+/// - Truncates to the maximum label key length
+///
+/// Normalizing is idempotent: a snake_case key is returned unchanged.
+///
+/// # Arguments
+/// * `key` - The label key to normalize
+///
+/// # Returns
+/// * `String` - A snake_case key that passes `validate_label_key`
+///
+/// # Examples
+/// ```rust
+/// use tyl_metrics_port::{normalize_label_key, validate_label_key};
+///
+/// assert_eq!(normalize_label_key("statusCode"), "status_code");
+/// assert_eq!(normalize_label_key("Status-Code"), "status_code");
+/// assert!(validate_label_key(&normalize_label_key("2xx ratio")).is_ok());
+/// ```
+pub fn normalize_label_key(key: &str) -> String {
+    let chars: Vec<char> = key
+        .trim()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') || c.is_whitespace())
+        .collect();
+    let mut normalized = String::with_capacity(chars.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            // Word boundary: `aB`, `1B`, or the last capital of an acronym (`HTTPStatus`)
+            let boundary = match prev {
+                Some(p) if p.is_ascii_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_ascii_uppercase() => next.is_some_and(|n| n.is_ascii_lowercase()),
+                _ => false,
+            };
+            if boundary && !normalized.ends_with('_') {
+                normalized.push('_');
+            }
+            normalized.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            normalized.push(c);
+        } else if !normalized.ends_with('_') {
+            // `_`, `-`, `.`, and whitespace all separate words
+            normalized.push('_');
+        }
+    }
+
+    while normalized.len() > 1 && normalized.ends_with('_') {
+        normalized.pop();
+    }
+    if normalized.is_empty() || normalized.starts_with(|c: char| c.is_ascii_digit()) {
+        normalized.insert(0, '_');
+    }
+
+    // Only ASCII remains, so truncating by bytes cannot split a character
+    normalized.truncate(MAX_LABEL_KEY_LENGTH);
+    normalized
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +1039,38 @@ mod tests {
         assert!(validate_metric_name(&"x".repeat(256)).is_err());
     }
 
+    #[test]
+    fn test_validate_metric_name_conventions() {
+        let otel = NamingConvention::OpenTelemetry;
+        let statsd = NamingConvention::StatsD;
+        let prometheus = NamingConvention::Prometheus;
+
+        assert!(validate_metric_name_for("http.server.duration", otel).is_ok());
+        assert!(validate_metric_name_for("http.server.duration", prometheus).is_err());
+        assert!(validate_metric_name_for("http.server.duration", statsd).is_ok());
+
+        assert!(validate_metric_name_for("http.server-duration", otel).is_ok());
+        assert!(validate_metric_name_for("http.server-duration", statsd).is_err());
+
+        assert!(validate_metric_name_for("db:pool_size", prometheus).is_ok());
+        assert!(validate_metric_name_for("db:pool_size", otel).is_err());
+        assert!(validate_metric_name_for("_internal", otel).is_err());
+
+        assert!(validate_metric_name_for("", otel).is_err());
+        assert!(validate_metric_name_for(&"x".repeat(256), statsd).is_err());
+    }
+
+    #[test]
+    fn test_validate_metric_name_defaults_to_prometheus() {
+        assert_eq!(NamingConvention::default(), NamingConvention::Prometheus);
+        for name in ["http_requests_total", "http.requests", "invalid-name"] {
+            assert_eq!(
+                validate_metric_name(name).is_ok(),
+                validate_metric_name_for(name, NamingConvention::Prometheus).is_ok()
+            );
+        }
+    }
+
     #[test]
     fn test_validate_label_key_valid() {
         assert!(validate_label_key("method").is_ok());
@@ -285,6 +1101,27 @@ mod tests {
         assert!(validate_label_value(&"x".repeat(1025)).is_err());
     }
 
+    #[test]
+    fn test_validate_label_value_charsets() {
+        let value = "first line\nsecond line";
+        assert!(validate_label_value(value).is_ok());
+        assert!(validate_label_value_for(value, LabelValueCharset::NoControl).is_err());
+        assert!(validate_label_value_for(value, LabelValueCharset::Printable).is_err());
+
+        for value in ["tab\there", "cr\rhere", "del\u{7F}"] {
+            assert!(validate_label_value_for(value, LabelValueCharset::NoControl).is_err());
+        }
+
+        // Unicode controls and invisible formatting only fail the printable charset
+        for value in ["next\u{85}line", "zero\u{200B}width", "bidi\u{202E}"] {
+            assert!(validate_label_value_for(value, LabelValueCharset::NoControl).is_ok());
+            assert!(validate_label_value_for(value, LabelValueCharset::Printable).is_err());
+        }
+
+        assert!(validate_label_value_for("Zürich 東京 🚀", LabelValueCharset::Printable).is_ok());
+        assert!(validate_label_value_for("nul\0", LabelValueCharset::Lenient).is_err());
+    }
+
     #[test]
     fn test_validate_labels() {
         let mut labels = HashMap::new();
@@ -300,6 +1137,68 @@ mod tests {
         assert!(validate_labels(&too_many_labels).is_err());
     }
 
+    #[test]
+    fn test_validate_labels_with_custom_limits() {
+        let labels: HashMap<String, String> = (0..64)
+            .map(|i| (format!("label_{i}"), "value".to_string()))
+            .collect();
+        let otel = ValidationLimits::default().with_max_labels(64);
+
+        assert!(validate_labels(&labels).is_err());
+        assert!(validate_labels_with(&labels, &otel).is_ok());
+
+        let mut too_many = labels.clone();
+        too_many.insert("label_64".to_string(), "value".to_string());
+        assert!(validate_labels_with(&too_many, &otel).is_err());
+    }
+
+    #[test]
+    fn test_validate_reports_first_invalid_label_by_key() {
+        for _ in 0..8 {
+            // Many reserved keys and one malformed key, which sorts first; a
+            // fresh map each time gets a fresh iteration order
+            let request = (0..16)
+                .fold(MetricRequest::counter("requests", 1.0), |request, i| {
+                    request.with_label(format!("__reserved_{i}"), "x")
+                })
+                .with_label("0_malformed", "x");
+
+            let error = validate_metric_request(&request).unwrap_err();
+            assert!(
+                error.to_string().contains("Invalid label key format"),
+                "{error}"
+            );
+
+            let error = validate_labels(request.labels()).unwrap_err();
+            assert!(
+                error.to_string().contains("Invalid label key format"),
+                "{error}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_with_reduced_lengths() {
+        let statsd = ValidationLimits::default()
+            .with_max_label_value_len(16)
+            .with_max_label_key_len(8)
+            .with_max_name_len(20);
+
+        let value = "a".repeat(17);
+        assert!(validate_label_value(&value).is_ok());
+        assert!(validate_label_value_with(&value, LabelValueCharset::Lenient, &statsd).is_err());
+        assert!(
+            validate_label_value_with(&value[..16], LabelValueCharset::Lenient, &statsd).is_ok()
+        );
+
+        assert!(validate_label_key_with("endpoint", &statsd).is_ok());
+        assert!(validate_label_key_with("endpoints", &statsd).is_err());
+
+        let request = MetricRequest::counter("http_requests_total_v2", 1.0);
+        assert!(validate_metric_request(&request).is_ok());
+        assert!(validate_metric_request_with(&request, &statsd).is_err());
+    }
+
     #[test]
     fn test_validate_metric_value() {
         assert!(validate_metric_value(123.45).is_ok());
@@ -320,6 +1219,35 @@ mod tests {
         assert!(validate_counter_value(f64::NAN).is_err());
     }
 
+    #[test]
+    fn test_series_hash_stable_and_order_insensitive() {
+        let mut forward = Labels::new();
+        forward.insert("method".to_string(), "GET".to_string());
+        forward.insert("status".to_string(), "200".to_string());
+        let mut backward = Labels::new();
+        backward.insert("status".to_string(), "200".to_string());
+        backward.insert("method".to_string(), "GET".to_string());
+
+        // Pinned: must never change between releases
+        assert_eq!(
+            series_hash("http_requests_total", &forward),
+            14_787_379_068_986_067_799
+        );
+        assert_eq!(
+            series_hash("http_requests_total", &backward),
+            series_hash("http_requests_total", &forward)
+        );
+        assert_eq!(
+            series_hash("up", &Labels::new()),
+            11_279_542_151_090_553_202
+        );
+
+        // Field boundaries are part of the key
+        let ab: Labels = [("ab".to_string(), "c".to_string())].into();
+        let a: Labels = [("a".to_string(), "bc".to_string())].into();
+        assert_ne!(series_hash("x", &ab), series_hash("x", &a));
+    }
+
     #[test]
     fn test_format_labels() {
         let mut labels = HashMap::new();
@@ -337,6 +1265,73 @@ mod tests {
         assert_eq!(format_labels(&empty_labels), "{}");
     }
 
+    #[test]
+    fn test_format_labels_with() {
+        let labels = HashMap::from([
+            ("status".to_string(), "200".to_string()),
+            ("method".to_string(), "GET".to_string()),
+            ("zone".to_string(), "eu".to_string()),
+        ]);
+
+        assert_eq!(
+            format_labels_with(&labels, &FormatOptions::statsd()),
+            "#method:GET,status:200,zone:eu"
+        );
+        assert_eq!(
+            format_labels_with(&labels, &FormatOptions::logfmt()),
+            "method=GET status=200 zone=eu"
+        );
+        assert_eq!(
+            format_labels_with(&labels, &FormatOptions::default()),
+            "method=GET,status=200,zone=eu"
+        );
+        assert_eq!(
+            format_labels_with(&HashMap::new(), &FormatOptions::logfmt()),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_sanitize_metric_name() {
+        assert_eq!(sanitize_metric_name("123_requests"), "_123_requests");
+        assert_eq!(
+            sanitize_metric_name("http-requests-total"),
+            "http_requests_total"
+        );
+        assert_eq!(
+            sanitize_metric_name("http requests total"),
+            "http_requests_total"
+        );
+        assert_eq!(sanitize_metric_name("http - requests"), "http_requests");
+        assert_eq!(sanitize_metric_name("café_latency"), "caf_latency");
+        assert_eq!(sanitize_metric_name("größe"), "gr_e");
+        assert_eq!(sanitize_metric_name(""), "_");
+        assert_eq!(sanitize_metric_name(&"x".repeat(300)).len(), 255);
+    }
+
+    #[test]
+    fn test_sanitize_metric_name_is_idempotent() {
+        for name in ["http_requests_total", "db:pool__size", "_private", "a"] {
+            assert_eq!(sanitize_metric_name(name), name);
+        }
+
+        for name in ["9 lives", "π-value", "a--b", " spaced out "] {
+            let once = sanitize_metric_name(name);
+            assert_eq!(sanitize_metric_name(&once), once);
+        }
+    }
+
+    #[test]
+    fn test_sanitize_metric_name_always_valid() {
+        for name in ["", "123", "---", "名前", "ok_name", "9 lives", " ", "::"] {
+            let sanitized = sanitize_metric_name(name);
+            assert!(
+                validate_metric_name(&sanitized).is_ok(),
+                "{name:?} sanitized to invalid {sanitized:?}"
+            );
+        }
+    }
+
     #[test]
     fn test_normalize_metric_name() {
         assert_eq!(
@@ -349,4 +1344,131 @@ mod tests {
             "already_normalized"
         );
     }
+
+    #[test]
+    fn test_normalize_label_key() {
+        assert_eq!(normalize_label_key("statusCode"), "status_code");
+        assert_eq!(normalize_label_key("Status-Code"), "status_code");
+        assert_eq!(normalize_label_key("status_code"), "status_code");
+        assert_eq!(normalize_label_key("HTTPStatus"), "http_status");
+        assert_eq!(normalize_label_key("http.route"), "http_route");
+        assert_eq!(normalize_label_key("  region id  "), "region_id");
+        assert_eq!(normalize_label_key("__internal"), "_internal");
+        assert_eq!(normalize_label_key("_private"), "_private");
+        assert_eq!(normalize_label_key("5xxCount"), "_5xx_count");
+        assert_eq!(normalize_label_key("cpu🔥load"), "cpuload");
+        assert_eq!(normalize_label_key("--"), "_");
+    }
+
+    #[test]
+    fn test_normalize_label_key_always_valid() {
+        for key in [
+            "",
+            "-",
+            "__",
+            "9",
+            "éclair",
+            "a".repeat(300).as_str(),
+            "fooBar-Baz.qux",
+        ] {
+            let normalized = normalize_label_key(key);
+            assert!(
+                validate_label_key(&normalized).is_ok(),
+                "{key:?} normalized to invalid {normalized:?}"
+            );
+            assert_eq!(normalize_label_key(&normalized), normalized);
+        }
+    }
+
+    #[test]
+    fn test_validate_histogram_buckets() {
+        assert_eq!(
+            validate_histogram_buckets(&[2.5, 0.1, 1.0, 0.1]).unwrap(),
+            vec![0.1, 1.0, 2.5]
+        );
+        assert_eq!(
+            validate_histogram_buckets(&[f64::INFINITY, 1.0]).unwrap(),
+            vec![1.0, f64::INFINITY]
+        );
+
+        assert!(validate_histogram_buckets(&[]).is_err());
+        assert!(validate_histogram_buckets(&[1.0, f64::NAN]).is_err());
+        assert_eq!(
+            validate_histogram_buckets(&[1.0, 0.0, -1.0]).unwrap(),
+            vec![-1.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_bucket_index() {
+        let bounds = [-1.0, 0.0, 0.5, 1.0];
+
+        // Below the first bound
+        assert_eq!(bucket_index(-5.0, &bounds), 0);
+        assert_eq!(bucket_index(f64::NEG_INFINITY, &bounds), 0);
+
+        // On a bound: upper bounds are inclusive
+        assert_eq!(bucket_index(-1.0, &bounds), 0);
+        assert_eq!(bucket_index(0.0, &bounds), 1);
+        assert_eq!(bucket_index(1.0, &bounds), 3);
+        assert_eq!(bucket_index(0.25, &bounds), 2);
+
+        // Above every finite bound: the implicit or explicit +Inf bucket
+        assert_eq!(bucket_index(1.5, &bounds), 4);
+        assert_eq!(bucket_index(f64::INFINITY, &bounds), 4);
+        let with_inf = [-1.0, 0.0, 0.5, 1.0, f64::INFINITY];
+        assert_eq!(bucket_index(1.5, &with_inf), 4);
+        assert_eq!(bucket_index(f64::INFINITY, &with_inf), 4);
+    }
+
+    #[test]
+    fn test_bucket_index_nan_and_empty() {
+        assert_eq!(bucket_index(f64::NAN, &[0.1, 1.0]), 2);
+        assert_eq!(bucket_index(f64::NAN, &[0.1, 1.0, f64::INFINITY]), 2);
+        assert_eq!(bucket_index(f64::NAN, &[]), 0);
+        assert_eq!(bucket_index(3.0, &[]), 0);
+    }
+
+    #[test]
+    fn test_validate_metric_request() {
+        assert!(
+            validate_metric_request(&MetricRequest::histogram_many("latency", vec![0.1, 0.2]))
+                .is_ok()
+        );
+        let error = validate_metric_request(&MetricRequest::histogram_many(
+            "latency",
+            vec![0.1, 0.2, f64::INFINITY],
+        ))
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Observation 2 of latency is inf"));
+        let error =
+            validate_metric_request(&MetricRequest::histogram_many("latency", vec![])).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Histogram batch for latency has no observations"));
+        assert!(validate_metric_request(&MetricRequest::gauge("temperature", -5.0)).is_ok());
+        assert!(validate_metric_request(&MetricRequest::counter("requests", -1.0)).is_err());
+        assert!(validate_metric_request(&MetricRequest::gauge("bad name", 1.0)).is_err());
+        assert!(validate_metric_request(
+            &MetricRequest::gauge("temperature", 1.0)
+                .with_temporality(AggregationTemporality::Delta)
+        )
+        .is_err());
+        assert!(validate_metric_request(
+            &MetricRequest::gauge("temperature", 1.0)
+                .with_temporality(AggregationTemporality::Cumulative)
+        )
+        .is_ok());
+        assert!(validate_metric_request(
+            &MetricRequest::counter("requests", 1.0)
+                .with_temporality(AggregationTemporality::Delta)
+        )
+        .is_ok());
+        assert!(validate_metric_request(
+            &MetricRequest::gauge("temperature", 1.0).with_label("", "x")
+        )
+        .is_err());
+    }
 }